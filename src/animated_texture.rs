@@ -0,0 +1,193 @@
+//! gif and apng textures, see [`crate::Ui::create_animated_texture`].
+//!
+//! every frame is kept decoded, the texture is changed when it is time to show the next one.
+
+use std::io::Cursor;
+use anyhow::Result;
+use image::AnimationDecoder;
+use image::ImageFormat;
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use nablo_shape::math::Vec2;
+use crate::Instant;
+use crate::texture::Image;
+use crate::texture::Playback;
+use crate::texture::TextureOptions;
+
+/// browsers show frames this short or shorter for 100ms, so do we
+const MIN_DELAY: f32 = 0.01;
+const DEFAULT_DELAY: f32 = 0.1;
+
+struct Frame {
+	rgba: Vec<u8>,
+	/// in seconds
+	delay: f32,
+}
+
+pub(crate) struct AnimatedTexture {
+	frames: Vec<Frame>,
+	size: Vec2,
+	options: TextureOptions,
+	pub(crate) playback: Playback,
+	frame: usize,
+	/// seconds spent on current frame
+	elapsed: f32,
+	last_update: Instant,
+	/// current frame should be uploaded
+	is_changed: bool,
+}
+
+impl AnimatedTexture {
+	/// decode a gif or apng, other images become a single frame
+	pub(crate) fn new(bytes: &[u8], options: TextureOptions) -> Result<Self> {
+		let frames = match image::guess_format(bytes)? {
+			ImageFormat::Gif => GifDecoder::new(Cursor::new(bytes))?.into_frames().collect_frames()?,
+			ImageFormat::Png => {
+				let decoder = PngDecoder::new(Cursor::new(bytes))?;
+				if decoder.is_apng() {
+					decoder.apng().into_frames().collect_frames()?
+				}else {
+					vec!(image::Frame::new(image::load_from_memory(bytes)?.to_rgba8()))
+				}
+			},
+			_ => vec!(image::Frame::new(image::load_from_memory(bytes)?.to_rgba8())),
+		};
+		let Some(first) = frames.first() else {
+			anyhow::bail!("animation has no frame");
+		};
+		let size = Vec2::new(first.buffer().width() as f32, first.buffer().height() as f32);
+		let frames = frames.into_iter().map(|frame| {
+			let (numerator, denominator) = frame.delay().numer_denom_ms();
+			let delay = numerator as f32 / denominator.max(1) as f32 / 1000.0;
+			Frame {
+				delay: if delay <= MIN_DELAY { DEFAULT_DELAY }else { delay },
+				rgba: frame.into_buffer().into_raw(),
+			}
+		}).collect();
+		Ok(Self {
+			frames,
+			size,
+			options,
+			playback: Playback::default(),
+			frame: 0,
+			elapsed: 0.0,
+			last_update: Instant::now(),
+			is_changed: false,
+		})
+	}
+
+	pub(crate) fn size(&self) -> Vec2 {
+		self.size
+	}
+
+	pub(crate) fn frame(&self) -> usize {
+		self.frame
+	}
+
+	pub(crate) fn frame_count(&self) -> usize {
+		self.frames.len()
+	}
+
+	/// current frame as texture `id`
+	pub(crate) fn image(&self, id: String) -> Image {
+		Image {
+			rgba: self.frames[self.frame].rgba.clone(),
+			id,
+			size: self.size,
+			options: self.options,
+		}
+	}
+
+	/// stopped on the last frame of a animation not looping
+	pub(crate) fn is_finished(&self) -> bool {
+		!self.playback.is_looping && self.frame + 1 == self.frames.len() && self.elapsed >= self.frames[self.frame].delay
+	}
+
+	/// show `frame` from its beginning
+	pub(crate) fn seek(&mut self, frame: usize) {
+		let frame = frame.min(self.frames.len() - 1);
+		self.is_changed |= frame != self.frame;
+		self.frame = frame;
+		self.elapsed = 0.0;
+	}
+
+	/// not shown this frame, time passed until next update is not counted
+	pub(crate) fn hold(&mut self) {
+		self.last_update = Instant::now();
+	}
+
+	/// move on by time passed since last update, returns the frame to upload if changed and seconds until next frame
+	pub(crate) fn update(&mut self, id: &str) -> (Option<Image>, Option<f32>) {
+		let delta = self.last_update.elapsed().as_seconds_f32();
+		self.last_update = Instant::now();
+		let last_frame = self.frame;
+		let frames = &self.frames;
+		let next = self.playback.advance(&mut self.frame, &mut self.elapsed, delta, frames.len(), |index| frames[index].delay);
+		if self.is_finished() {
+			self.playback.is_playing = false;
+		}
+		if self.frame == last_frame && !self.is_changed {
+			return (None, next);
+		}
+		self.is_changed = false;
+		(Some(self.image(id.to_string())), next)
+	}
+}
+
+#[cfg(test)]
+impl AnimatedTexture {
+	/// single pixel frames with `delays`, frame `n` is filled by `n`
+	pub(crate) fn from_delays(delays: &[f32]) -> Self {
+		Self {
+			frames: delays.iter().enumerate().map(|(index, delay)| Frame { rgba: vec!(index as u8; 4), delay: *delay }).collect(),
+			size: Vec2::same(1.0),
+			options: TextureOptions::default(),
+			playback: Playback::default(),
+			frame: 0,
+			elapsed: 0.0,
+			last_update: Instant::now(),
+			is_changed: false,
+		}
+	}
+
+	/// pretend last update was `seconds` earlier
+	pub(crate) fn pass_time(&mut self, seconds: f32) {
+		self.last_update.offset -= time::Duration::seconds_f32(seconds);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn update_moves_on_by_time_passed() {
+		let mut animation = AnimatedTexture::from_delays(&[1.0, 1.0, 1.0]);
+		animation.pass_time(1.5);
+		let (image, next) = animation.update("a");
+		let image = image.unwrap();
+		assert_eq!(image.id, "a");
+		assert_eq!(image.rgba, vec!(1; 4));
+		assert_eq!(animation.frame(), 1);
+		assert!((next.unwrap() - 0.5).abs() < 0.1);
+		// nothing changed since
+		assert!(animation.update("a").0.is_none());
+	}
+
+	#[test]
+	fn hold_does_not_count_time_passed() {
+		let mut animation = AnimatedTexture::from_delays(&[1.0, 1.0]);
+		animation.pass_time(1.5);
+		animation.hold();
+		assert!(animation.update("a").0.is_none());
+		assert_eq!(animation.frame(), 0);
+	}
+
+	#[test]
+	fn seek_uploads_new_frame() {
+		let mut animation = AnimatedTexture::from_delays(&[1.0, 1.0]);
+		animation.seek(5);
+		assert_eq!(animation.frame(), 1);
+		assert_eq!(animation.update("a").0.unwrap().rgba, vec!(1; 4));
+	}
+}
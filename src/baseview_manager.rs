@@ -1,5 +1,7 @@
+use crate::Instant;
 use crate::Integrator;
 use crate::state::State;
+use crate::state::shader_event;
 use crate::Ui;
 use crate::ManagerBuilder;
 use baseview::WindowHandle;
@@ -18,23 +20,30 @@ use crate::OutputEvent;
 use crate::Manager;
 use crate::App;
 use crate::Settings;
-
-#[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct Vertex {
-	position: [f32; 3],
-	tex_coords: [f32; 2],
-}
+use crate::font::TextRendering;
 
 impl<T: App + Send + 'static> WindowHandler for Manager<T> {
 	fn on_frame(&mut self, _: &mut Window) {
 		let output = self.integrator.frame_vertexs(vec!(), |ui| self.app.app(ui));
+		self.state.set_paint_callbacks(output.paint_callbacks.clone());
+		self.state.set_fonts(output.fonts.clone());
 		for event in &output.output_events {
 			self.handle_event(event.clone())
 		}
-		match self.state.render(output) {
-			Ok(_) => {}
-			Err(wgpu::SurfaceError::Lost) => self.state.resize(self.state.size),
+		for (id, result) in self.state.poll_shaders() {
+			self.integrator.event(&shader_event(id, result));
+		}
+		// host calls this in its own pace, so we can only skip drawing
+		if !output.is_changed {
+			return;
+		}
+		let cursor_position = self.integrator.ui.input().cursor_position().unwrap_or(Vec2::ZERO) / self.integrator.ui.window_area().width_and_height() * Vec2::same(2.0) - Vec2::same(1.0);
+		match self.state.render(output, cursor_position, self.timer.elapsed().as_seconds_f32()) {
+			Ok(stats) => self.integrator.report_render_stats(stats),
+			Err(wgpu::SurfaceError::Lost) => {
+				self.state.resize(self.state.size);
+				self.integrator.request_full_repaint();
+			},
 			Err(e) => eprintln!("{:?}", e),
 		};
 		
@@ -107,29 +116,44 @@ impl<T: App + Send + 'static> Manager<T> {
 				None
 			}
 		};
-		let state = State::new(window, builder.settings.size);
+		let state = State::new(window, builder.settings.size, builder.settings.text_rendering, builder.settings.msaa_samples);
 		let mut integrator = Integrator::default();
 		integrator.event(&Event::Resized(builder.settings.size));
+		integrator.ui.set_feather(builder.settings.feather);
 		Self {
 			settings: Settings::default(),
 			integrator,
 			app: builder.app,
 			clipboard,
 			state,
+			timer: Instant::now(),
 		}
 	}
 
 	fn handle_event(&mut self, event: OutputEvent) {
 		match event {
 			OutputEvent::TextureCreate(texture) => {
-				self.state.insert_texture(texture.id.clone(), texture);
+				if let Err(e) = self.state.insert_texture(texture.id.clone(), texture) {
+					#[cfg(feature = "info")]
+					println!("uploading texture failed, info: {}", e);
+					#[cfg(feature = "log")]
+					log::error!("uploading texture failed, info: {}", e);
+				}
 			},
 			OutputEvent::TextureChange(texture) => {
-				self.state.insert_texture(texture.id.clone(), texture);
+				if let Err(e) = self.state.insert_texture(texture.id.clone(), texture) {
+					#[cfg(feature = "info")]
+					println!("uploading texture failed, info: {}", e);
+					#[cfg(feature = "log")]
+					log::error!("uploading texture failed, info: {}", e);
+				}
 			},
 			OutputEvent::TextureDelete(id) => {
 				self.state.remove_texture(&id);
 			},
+			OutputEvent::Offscreen(offscreen) => {
+				self.state.render_offscreen(offscreen, Vec2::ZERO, self.timer.elapsed().as_seconds_f32());
+			},
 			OutputEvent::ClipboardCopy(text) => {
 				if let Some(clipboard) = &mut self.clipboard {
 					let data = clipboard.set_contents(text);
@@ -142,6 +166,25 @@ impl<T: App + Send + 'static> Manager<T> {
 				}
 			},
 			OutputEvent::RequireSoftKeyboard(_) => {},
+			OutputEvent::OpenUrl(url) => {
+				#[cfg(feature = "info")]
+				println!("open url requested: {}", url);
+				#[cfg(feature = "log")]
+				log::info!("open url requested: {}", url);
+			},
+			OutputEvent::ChangeShader(id) => self.state.change_shader(id),
+			OutputEvent::RemoveShader(id) => self.state.remove_shader(id),
+			OutputEvent::ShaderUniform(id, bytes) => self.state.set_shader_uniform(id, bytes),
+			OutputEvent::ShaderTextures(id, textures) => self.state.set_shader_textures(id, textures),
+			OutputEvent::RegstrateShader(id, code) => {
+				// working ones are told by `poll_shaders` later
+				if let Err(diagnostics) = self.state.registrate_shader(id.clone(), code) {
+					self.integrator.event(&shader_event(id, Err(diagnostics)));
+				}
+			},
+			// glyph cache finds it out by font generation
+			OutputEvent::FontChange => {},
+			OutputEvent::Close => {},
 		}
 	}
 }
@@ -152,6 +195,9 @@ impl Default for Settings {
 			max_clicks: 5,
 			size: Vec2::new(640.0,480.0),
 			title: String::from("nablo"),
+			text_rendering: TextRendering::default(),
+			msaa_samples: 1,
+			feather: 0.0,
 		}
 	}
 }
\ No newline at end of file
@@ -0,0 +1,46 @@
+// one direction of a gaussian blur, drawn by a triangle covering the target
+
+struct Blur {
+	/// (1, 0) or (0, 1)
+	direction: vec2f,
+	/// in pixel
+	radius: f32,
+	padding: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> blur: Blur;
+
+@group(0)@binding(0)
+var t_diffuse: texture_2d<f32>;
+@group(0)@binding(1)
+var s_diffuse: sampler;
+
+struct VertexOutput {
+	@builtin(position) clip_position: vec4f,
+	@location(0) uv: vec2f,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+	var out: VertexOutput;
+	let uv = vec2f(f32((index << 1u) & 2u), f32(index & 2u));
+	out.clip_position = vec4f(uv * vec2f(2.0, -2.0) + vec2f(-1.0, 1.0), 0.0, 1.0);
+	out.uv = uv;
+	return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+	let size = vec2f(textureDimensions(t_diffuse));
+	let sigma = max(blur.radius * 0.5, 0.5);
+	var color = vec4f(0.0);
+	var total = 0.0;
+	for (var i = -16; i <= 16; i++) {
+		let offset = f32(i) * blur.radius / 16.0;
+		let weight = exp(-(offset * offset) / (2.0 * sigma * sigma));
+		color += textureSampleLevel(t_diffuse, s_diffuse, in.uv + blur.direction * offset / size, 0.0) * weight;
+		total += weight;
+	}
+	return color / total;
+}
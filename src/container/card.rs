@@ -11,6 +11,9 @@ use nablo_shape::math::Area;
 use crate::Ui;
 use crate::Painter;
 use crate::container::Card;
+use crate::widgets::Effect;
+use crate::widgets::Gradient;
+use crate::widgets::Shadow;
 
 #[derive(serde::Deserialize, serde::Serialize, Default, Debug)]
 struct CardTemp {
@@ -95,7 +98,21 @@ impl Container for Card {
 			3.0
 		};
 		painter.set_position(response.area.left_top() + Vec2::same(stroke_width));
-		painter.rect(Vec2::new(response.area.width(), response.area.height()) - Vec2::same(stroke_width * 2.0), self.rounding);
+		let size = Vec2::new(response.area.width(), response.area.height()) - Vec2::same(stroke_width * 2.0);
+		if self.effects.is_empty() {
+			painter.rect(size, self.rounding);
+		}else {
+			// drawn once with its stroke, given to `ui` so it goes before shapes of `painter`
+			let mut background = Painter::from_area(&painter.paint_area);
+			*background.style_mut() = painter.style().clone();
+			// drop shadows go beyond the card
+			background.set_clip(ui.window_crossed());
+			if let Some(color) = self.effects.iter().find_map(|effect| if let Effect::Gradient(gradient) = effect { gradient.fallback_color() } else { None }) {
+				background.set_color(color);
+			}
+			background.rect(size, self.rounding);
+			ui.paint_with_effects(self.effects.clone(), background);
+		}
 		painter.set_position(Vec2::ZERO);
 		let mut temp: CardTemp = ui.memory_read(id).unwrap_or_default();
 		painter.set_offset(temp.current());
@@ -343,6 +360,36 @@ impl Card {
 		}
 	}
 
+	/// draw a shadow behind the card, see [`Effect::DropShadow`]
+	pub fn set_shadow(self, shadow: Shadow) -> Self {
+		self.add_effect(Effect::DropShadow(shadow))
+	}
+
+	/// draw a shadow inside the card, see [`Effect::InnerShadow`]
+	pub fn set_inner_shadow(self, shadow: Shadow) -> Self {
+		self.add_effect(Effect::InnerShadow(shadow))
+	}
+
+	/// fill the card by a gradient instead of its color, the stroke as well
+	pub fn set_gradient(self, gradient: Gradient) -> Self {
+		self.add_effect(Effect::Gradient(gradient))
+	}
+
+	/// blur what is behind the card, give it a translucent color to see through
+	pub fn set_backdrop_blur(self, blur: f32) -> Self {
+		self.add_effect(Effect::BackdropBlur(blur))
+	}
+
+	/// draw the background of the card with an effect, see [`Ui::paint_with_effects`]
+	pub fn add_effect(self, effect: Effect) -> Self {
+		let mut effects = self.effects;
+		effects.push(effect);
+		Self {
+			effects,
+			..self
+		}
+	}
+
 	/// where have we scrolled to?
 	pub fn scroll(&self, ui: &mut Ui) -> Vec2 {
 		let id = ui.container_id(self);
@@ -151,8 +151,13 @@ impl Container for MessageProvider {
 				if duration > t {
 					msg.message.should_delete = true;
 					msg.delete_time = Some(Instant::now());
+				}else if !msg.message.should_delete {
+					ui.request_repaint_after(t - duration);
 				}
 			}
+			if duration <= animation_time || msg.message.should_delete {
+				ui.request_repaint();
+			}
 			painter.set_color(background_color.set_alpha(factor));
 			let text_area = msg.message.text_area(painter);
 			let position = Vec2::new((ui.window_area().width() - text_area.width()) / 2.0 / painter.style().scale_factor, available_y) + inner_response.response.area.left_top();
@@ -10,6 +10,7 @@ use time::Duration;
 use nablo_shape::prelude::Painter;
 use crate::prelude::Text;
 use crate::prelude::shape_elements::Color;
+use crate::widgets::Effect;
 use nablo_shape::math::Vec2;
 /// the most basic container
 #[derive(Default, Clone)]
@@ -26,6 +27,7 @@ pub struct Card {
 	resizable: bool,
 	stroke_width: f32,
 	stroke_color: Option<Color>,
+	effects: Vec<Effect>,
 }
 
 /// show a collapsing area.
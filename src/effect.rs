@@ -0,0 +1,154 @@
+//! vertexs of shapes drawn with [`Effect`]s
+
+use nablo_shape::prelude::shape_elements::Vertex;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use crate::integrator::Paint;
+use crate::widgets::Effect;
+use crate::widgets::Gradient;
+use crate::widgets::Shadow;
+use crate::feather::Feather;
+
+/// turns a tessellated shape into shapes drawn in order: drop shadows, backdrop blurs, the shape itself and inner shadows.
+/// shadows are made from the shape itself, effects are scaled by `scale_factor` and returned [`Paint`]s are in pixel.
+pub(crate) fn effect_vertexs(mut vertexs: Vec<Vertex>, indices: Vec<u32>, effects: &[Effect], scale_factor: f32, window_size: Vec2, feather: &mut Feather) -> Vec<(Vec<Vertex>, Vec<u32>, Paint)> {
+	let Some(bounds) = bounds(&vertexs, window_size) else {
+		return vec!();
+	};
+	let mut shadows = vec!();
+	let mut backdrops = vec!();
+	let mut inner_shadows = vec!();
+	let mut fill = Paint::Color;
+	for effect in effects {
+		match effect {
+			Effect::DropShadow(shadow) => {
+				let (offset, spread, blur) = shadow_sizes(shadow, scale_factor);
+				let mut shadow_vertexs = moved(&vertexs, shadow.color.normalized(), offset, window_size);
+				let mut shadow_indices = indices.clone();
+				// fading out around the grown outline, as a blur does
+				feather.grow(&mut shadow_vertexs, &shadow_indices, window_size, spread - blur / 2.0);
+				if blur > 0.0 {
+					feather.fade(&mut shadow_vertexs, &mut shadow_indices, window_size, blur);
+				}else {
+					feather.apply(&mut shadow_vertexs, &mut shadow_indices, window_size);
+				}
+				shadows.push((shadow_vertexs, shadow_indices, Paint::Color));
+			},
+			Effect::InnerShadow(shadow) => {
+				let (offset, spread, blur) = shadow_sizes(shadow, scale_factor);
+				let mut shadow_vertexs = moved(&vertexs, shadow.color.normalized(), Vec2::ZERO, window_size);
+				// normals point up in normalized positions, edges facing away from `offset` cast wider shadows
+				let width = |normal: Vec2| spread - (offset.x * normal.x - offset.y * normal.y);
+				let shadow_indices = feather.inner_band(&mut shadow_vertexs, &indices, window_size, width, |normal| width(normal) + blur);
+				inner_shadows.push((shadow_vertexs, shadow_indices, Paint::Color));
+			},
+			Effect::BackdropBlur(blur) => {
+				let mut backdrop_vertexs = moved(&vertexs, [1.0; 4], Vec2::ZERO, window_size);
+				let mut backdrop_indices = indices.clone();
+				feather.apply(&mut backdrop_vertexs, &mut backdrop_indices, window_size);
+				backdrops.push((backdrop_vertexs, backdrop_indices, Paint::Backdrop { blur: blur * scale_factor }));
+			},
+			Effect::Gradient(gradient) => {
+				fill = gradient_paint(gradient, bounds, scale_factor);
+			},
+		}
+	}
+	if fill != Paint::Color {
+		// so a transparent fill stays transparent under the gradient
+		for vertex in &mut vertexs {
+			vertex.color = [1.0, 1.0, 1.0, vertex.color[3]];
+		}
+	}
+	let mut indices = indices;
+	feather.apply(&mut vertexs, &mut indices, window_size);
+	let mut back = shadows;
+	back.append(&mut backdrops);
+	back.push((vertexs, indices, fill));
+	back.append(&mut inner_shadows);
+	back
+}
+
+/// in pixel from left top of the window
+pub(crate) fn bounds(vertexs: &[Vertex], window_size: Vec2) -> Option<Area> {
+	let mut points = vertexs.iter().map(|vertex| Vec2::new((vertex.position[0] + 1.0) / 2.0 * window_size.x, (1.0 - vertex.position[1]) / 2.0 * window_size.y));
+	let first = points.next()?;
+	let (min, max) = points.fold((first, first), |(min, max), point| {
+		(Vec2::new(min.x.min(point.x), min.y.min(point.y)), Vec2::new(max.x.max(point.x), max.y.max(point.y)))
+	});
+	Some(Area::new(min, max))
+}
+
+/// offset, spread and blur of a shadow in pixel
+fn shadow_sizes(shadow: &Shadow, scale_factor: f32) -> (Vec2, f32, f32) {
+	(shadow.offset * Vec2::same(scale_factor), shadow.spread * scale_factor, shadow.blur.max(0.0) * scale_factor)
+}
+
+/// `vertexs` in `color` moved by `offset` in pixel
+fn moved(vertexs: &[Vertex], color: [f32; 4], offset: Vec2, window_size: Vec2) -> Vec<Vertex> {
+	vertexs.iter().map(|vertex| {
+		let mut vertex = vertex.clone();
+		vertex.position[0] += offset.x / window_size.x * 2.0;
+		vertex.position[1] -= offset.y / window_size.y * 2.0;
+		vertex.color = color;
+		vertex
+	}).collect()
+}
+
+fn gradient_paint(gradient: &Gradient, bounds: Area, scale_factor: f32) -> Paint {
+	let at = |point: Vec2| bounds.left_top() + point * bounds.width_and_height();
+	let sorted = |stops: &Vec<(f32, _)>| {
+		let mut stops = stops.clone();
+		stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+		stops.truncate(crate::MAX_GRADIENT_STOPS);
+		stops
+	};
+	match gradient {
+		Gradient::Linear { from, to, stops } => Paint::LinearGradient {
+			start: at(*from),
+			end: at(*to),
+			stops: sorted(stops),
+		},
+		Gradient::Radial { center, radius, stops } => Paint::RadialGradient {
+			center: at(*center),
+			radius: radius * scale_factor,
+			stops: sorted(stops),
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// square of 10 pixels in a window of 20
+	fn square() -> (Vec<Vertex>, Vec<u32>) {
+		let vertexs = [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]].into_iter().map(|[x, y]| Vertex {
+			position: [x, y, 0.0],
+			color: [1.0; 4],
+		}).collect();
+		(vertexs, vec!(0, 1, 2, 0, 2, 3))
+	}
+
+	#[test]
+	fn shadows_go_around_the_shape() {
+		let (vertexs, indices) = square();
+		let shadow = Shadow {
+			offset: Vec2::new(2.0, 0.0),
+			blur: 0.0,
+			spread: 0.0,
+			color: [0, 0, 0, 255].into(),
+		};
+		let shapes = effect_vertexs(vertexs, indices, &[Effect::InnerShadow(shadow), Effect::DropShadow(shadow)], 1.0, Vec2::same(20.0), &mut Feather::new(0.0));
+		assert_eq!(shapes.len(), 3);
+		// drop shadow is the square moved right by 2 pixels
+		let (drop, _, _) = &shapes[0];
+		assert!(drop.iter().all(|vertex| vertex.color == [0.0, 0.0, 0.0, 1.0]));
+		assert!(drop.iter().any(|vertex| (vertex.position[0] - 0.7).abs() < 1e-5));
+		assert_eq!(shapes[1].0[0].color, [1.0; 4]);
+		// inner shadow grows from the left edge, which faces away from the offset. corners move along both edges
+		let (inner, _, _) = &shapes[2];
+		let solid: Vec<f32> = inner[4..].iter().filter(|vertex| vertex.color[3] > 0.0).map(|vertex| vertex.position[0]).collect();
+		assert_eq!(solid.len(), 2);
+		assert!(solid.iter().all(|x| (x - (-0.5 + 2.0 / 2.0_f32.sqrt() / 10.0)).abs() < 1e-5));
+	}
+}
@@ -2,6 +2,10 @@
 
 use crate::texture::Image;
 use nablo_shape::math::Vec2;
+use nablo_shape::shape::Shape;
+use crate::integrator::ShapeExtra;
+#[cfg(feature = "vertexs")]
+use crate::integrator::ParsedShape;
 cfg_if::cfg_if! {
 	if #[cfg(feature = "manager")] {
 		cfg_if::cfg_if! {
@@ -38,16 +42,61 @@ pub enum OutputEvent {
 	RemoveShader(String),
 	/// id and shader code
 	RegstrateShader(String, String),
+	/// id and bytes of the custom uniform block, see [`crate::Ui::set_shader_uniform`]
+	ShaderUniform(String, Vec<u8>),
+	/// id and textures bound, see [`crate::Ui::set_shader_textures`]
+	ShaderTextures(String, Vec<String>),
 	TextureCreate(Image),
 	TextureChange(Image),
 	TextureDelete(String),
+	/// draw shapes into a texture, see [`crate::Ui::offscreen`]
+	Offscreen(Offscreen),
 	ClipboardCopy(String),
 	/// true for open
 	RequireSoftKeyboard(bool),
+	/// open a url, [`crate::Manager`] opens it in system browser or a new tab on web
+	OpenUrl(String),
+	/// fonts changed by [`crate::Ui::register_font`] or [`crate::Ui::set_font_fallback`], glyphs cached by font index may be wrong now.
+	/// new fonts are in [`crate::integrator::Output::fonts`]
+	FontChange,
 	/// close the programe
 	Close
 }
 
+/// shapes drawn into texture `id` instead of the window, sent only when they changed
+#[derive(Clone, PartialEq)]
+pub struct Offscreen {
+	/// id of the texture in texture map
+	pub id: String,
+	/// in pixel
+	pub size: Vec2,
+	/// sorted by layer, positions are relative to the texture
+	pub shapes: Vec<Shape>,
+	/// shader and effects of each shape
+	pub extras: Vec<ShapeExtra>,
+	/// see [`crate::Ui::set_feather`]
+	pub feather: f32,
+}
+
+impl std::fmt::Debug for Offscreen {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Offscreen")
+			.field("id", &self.id)
+			.field("size", &self.size)
+			.field("shapes", &self.shapes.len())
+			.field("feather", &self.feather)
+			.finish()
+	}
+}
+
+#[cfg(feature = "vertexs")]
+impl Offscreen {
+	/// turn shapes into what the renderer draws
+	pub fn parsed_shapes(&self) -> Vec<ParsedShape> {
+		crate::integrator::parse_shapes(self.shapes.clone(), self.extras.clone(), self.size, self.feather)
+	}
+}
+
 /// storges events that we care
 #[non_exhaustive]
 #[derive(Clone, Debug, PartialEq, Default)]
@@ -74,9 +123,27 @@ pub enum Event {
 	TouchCancel(Touch),
 	/// contains scroll delta
 	Scroll(Vec2),
+	/// contains id of a shader from [`crate::Ui::registrate_shader`] which is compiled and in use
+	ShaderRegistrated(String),
+	/// contains id of a shader from [`crate::Ui::registrate_shader`] which failed to compile and problems found, last working version of this shader keeps being used
+	ShaderError(String, Vec<ShaderDiagnostic>),
 	#[default] NotSupported,
 }
 
+/// a problem found when compiling a shader, see [`Event::ShaderError`]
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ShaderDiagnostic {
+	/// line in the code you registrated, starts from 1. 0 if we dont know where the problem is
+	pub line: usize,
+	/// column in charaters, starts from 1. 0 if we dont know where the problem is
+	pub column: usize,
+	/// how many charaters the problem spans
+	pub length: usize,
+	pub message: String,
+	/// the problem is in structs nablo puts before your code, such as `VertexOutput` defined again. `line` and `column` are 0 then
+	pub is_in_prelude: bool,
+}
+
 #[non_exhaustive]
 #[derive(Clone, Debug, PartialEq, Default)]
 /// a stuct for touch
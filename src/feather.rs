@@ -0,0 +1,277 @@
+//! smooth edges of tessellated shapes without msaa, and bands along their outline for shadows
+
+use nablo_shape::prelude::shape_elements::Vertex;
+use nablo_shape::math::Vec2;
+use std::collections::HashMap;
+
+/// a edge used by only one triangle, `c` is the third vertex of the triangle
+struct Edge {
+	a: u32,
+	b: u32,
+	c: u32,
+}
+
+/// a position on the outline
+#[derive(Clone, Copy)]
+struct Corner {
+	/// welded vertex
+	key: u32,
+	/// a vertex at this position
+	index: u32,
+	/// outward, averaged from edges using it
+	normal: Vec2,
+	/// moves one pixel away from both edges, longer than `normal` at corners
+	miter: Vec2,
+}
+
+/// adds a strip `width` pixels wide around the outline of triangles, fading to transparent.
+///
+/// made once for all shapes of a frame, so its buffers are allocated only once.
+pub(crate) struct Feather {
+	width: f32,
+	/// first vertex at each position
+	welded: HashMap<(u32, u32), u32>,
+	/// welded vertex of each vertex
+	weld: Vec<u32>,
+	/// `None` once a edge is shared by two triangles
+	edges: HashMap<(u32, u32), Option<Edge>>,
+	outline: Vec<Edge>,
+	/// welded vertex, a vertex at its position and outward normal of a outline edge using it
+	edge_normals: Vec<(u32, u32, Vec2)>,
+	/// sorted by `key`
+	corners: Vec<Corner>,
+	/// vertexs made for each welded vertex on the outline, where the band starts and ends
+	band: HashMap<u32, (u32, u32)>,
+}
+
+impl Feather {
+	pub(crate) fn new(width: f32) -> Self {
+		Self {
+			width,
+			welded: HashMap::new(),
+			weld: vec!(),
+			edges: HashMap::new(),
+			outline: vec!(),
+			edge_normals: vec!(),
+			corners: vec!(),
+			band: HashMap::new(),
+		}
+	}
+
+	/// feather triangles in `indices`, positions are normalized in a window of `window_size`, vertexs at same position are treated as one.
+	pub(crate) fn apply(&mut self, vertexs: &mut Vec<Vertex>, indices: &mut Vec<u32>, window_size: Vec2) {
+		self.fade(vertexs, indices, window_size, self.width);
+	}
+
+	/// same as [`Self::apply`] with strip of `width` pixels
+	pub(crate) fn fade(&mut self, vertexs: &mut Vec<Vertex>, indices: &mut Vec<u32>, window_size: Vec2, width: f32) {
+		if width > 0.0 && self.find_outline(vertexs, indices, window_size) {
+			self.band(vertexs, indices, window_size, |_| 0.0, |_| width);
+		}
+	}
+
+	/// move the outline of triangles in `indices` outward by `distance` pixels, inward if negative
+	pub(crate) fn grow(&mut self, vertexs: &mut [Vertex], indices: &[u32], window_size: Vec2, distance: f32) {
+		if distance == 0.0 || !self.find_outline(vertexs, indices, window_size) {
+			return;
+		}
+		let half = window_size / 2.0;
+		for (index, vertex) in vertexs.iter_mut().enumerate() {
+			let key = self.weld[index];
+			if let Ok(found) = self.corners.binary_search_by_key(&key, |corner| corner.key) {
+				let offset = self.corners[found].miter * Vec2::same(distance);
+				vertex.position[0] += offset.x / half.x;
+				vertex.position[1] += offset.y / half.y;
+			}
+		}
+	}
+
+	/// shadow inside the outline of triangles in `indices`, solid from the outline to `start` pixels inside, then fading to transparent at `end`.
+	/// both depend on the outward normal, returns triangles of the band using `vertexs`, which are extended
+	pub(crate) fn inner_band(&mut self, vertexs: &mut Vec<Vertex>, indices: &[u32], window_size: Vec2, start: impl Fn(Vec2) -> f32, end: impl Fn(Vec2) -> f32) -> Vec<u32> {
+		let mut band = vec!();
+		if self.find_outline(vertexs, indices, window_size) {
+			self.band(vertexs, &mut band, window_size, |normal| -start(normal).max(0.0), |normal| -end(normal).max(start(normal)).max(0.0));
+		}
+		band
+	}
+
+	/// fills `self.outline` and `self.corners` from triangles in `indices`, false if there is no outline
+	fn find_outline(&mut self, vertexs: &[Vertex], indices: &[u32], window_size: Vec2) -> bool {
+		self.outline.clear();
+		self.corners.clear();
+		if indices.len() < 3 {
+			return false;
+		}
+		let half = window_size / 2.0;
+		let pixel = |index: u32| {
+			let position = vertexs[index as usize].position;
+			Vec2::new(position[0] * half.x, position[1] * half.y)
+		};
+		// same position, same vertex
+		self.welded.clear();
+		self.weld.clear();
+		for (index, vertex) in vertexs.iter().enumerate() {
+			let key = (vertex.position[0].to_bits(), vertex.position[1].to_bits());
+			self.weld.push(*self.welded.entry(key).or_insert(index as u32));
+		}
+
+		self.edges.clear();
+		for triangle in indices.chunks_exact(3) {
+			for (a, b, c) in [(triangle[0], triangle[1], triangle[2]), (triangle[1], triangle[2], triangle[0]), (triangle[2], triangle[0], triangle[1])] {
+				let (wa, wb) = (self.weld[a as usize], self.weld[b as usize]);
+				if wa == wb {
+					continue;
+				}
+				let key = (wa.min(wb), wa.max(wb));
+				self.edges.entry(key).and_modify(|edge| *edge = None).or_insert(Some(Edge { a, b, c }));
+			}
+		}
+
+		// outward normal of each outline edge, for both of its vertexs
+		self.edge_normals.clear();
+		for edge in self.edges.drain().filter_map(|(_, edge)| edge) {
+			let (a, b, c) = (pixel(edge.a), pixel(edge.b), pixel(edge.c));
+			let direction = b - a;
+			let length = (direction.x * direction.x + direction.y * direction.y).sqrt();
+			if length <= f32::EPSILON {
+				continue;
+			}
+			let mut normal = Vec2::new(direction.y, -direction.x) / length;
+			let to_inside = c - a;
+			let side = normal.x * to_inside.x + normal.y * to_inside.y;
+			if side.abs() <= f32::EPSILON {
+				continue;
+			}
+			if side > 0.0 {
+				normal = Vec2::ZERO - normal;
+			}
+			for index in [edge.a, edge.b] {
+				self.edge_normals.push((self.weld[index as usize], index, normal));
+			}
+			self.outline.push(edge);
+		}
+
+		// every welded vertex on the outline gets one direction to move
+		self.edge_normals.sort_unstable_by_key(|(key, _, _)| *key);
+		let mut start = 0;
+		while start < self.edge_normals.len() {
+			let (key, index, _) = self.edge_normals[start];
+			let end = start + self.edge_normals[start..].iter().take_while(|(other, _, _)| *other == key).count();
+			let edge_normals = &self.edge_normals[start..end];
+			start = end;
+			let sum = edge_normals.iter().fold(Vec2::ZERO, |sum, (_, _, normal)| sum + *normal);
+			let length = (sum.x * sum.x + sum.y * sum.y).sqrt();
+			let normal = if length <= f32::EPSILON { edge_normals[0].2 } else { sum / length };
+			// keep the band as wide as asked at corners, but not too long at sharp ones
+			let cos = edge_normals.iter().map(|(_, _, edge_normal)| edge_normal.x * normal.x + edge_normal.y * normal.y).fold(1.0_f32, f32::min);
+			self.corners.push(Corner {
+				key,
+				index,
+				normal,
+				miter: normal / cos.max(0.5),
+			});
+		}
+		!self.outline.is_empty()
+	}
+
+	/// band along the outline found by [`Self::find_outline`], solid from the outline to `start` pixels away, fading to transparent at `end`.
+	/// both depend on the outward normal, the band goes inside for negative distances
+	fn band(&mut self, vertexs: &mut Vec<Vertex>, indices: &mut Vec<u32>, window_size: Vec2, start: impl Fn(Vec2) -> f32, end: impl Fn(Vec2) -> f32) {
+		let half = window_size / 2.0;
+		self.band.clear();
+		for corner in &self.corners {
+			let mut moved = |distance: f32, is_transparent: bool| {
+				let mut vertex = vertexs[corner.index as usize].clone();
+				let offset = corner.miter * Vec2::same(distance);
+				vertex.position[0] += offset.x / half.x;
+				vertex.position[1] += offset.y / half.y;
+				if is_transparent {
+					vertex.color[3] = 0.0;
+				}
+				vertexs.push(vertex);
+				vertexs.len() as u32 - 1
+			};
+			let start_distance = start(corner.normal);
+			let start_index = if start_distance == 0.0 { corner.index } else { moved(start_distance, false) };
+			let end_index = moved(end(corner.normal), true);
+			self.band.insert(corner.key, (start_index, end_index));
+		}
+
+		// quads keep winding of the triangle they are next to
+		let quad = |indices: &mut Vec<u32>, (a, b): (u32, u32), (next_a, next_b): (u32, u32), is_inward: bool| {
+			if a == next_a && b == next_b {
+				return;
+			}
+			if is_inward {
+				indices.extend([a, b, next_b, a, next_b, next_a]);
+			}else {
+				indices.extend([b, a, next_a, b, next_a, next_b]);
+			}
+		};
+		for edge in self.outline.drain(..) {
+			let (start_a, end_a) = self.band[&self.weld[edge.a as usize]];
+			let (start_b, end_b) = self.band[&self.weld[edge.b as usize]];
+			let normal = self.corners[self.corners.binary_search_by_key(&self.weld[edge.a as usize], |corner| corner.key).unwrap_or(0)].normal;
+			let is_inward = end(normal) < 0.0;
+			quad(indices, (edge.a, edge.b), (start_a, start_b), is_inward);
+			quad(indices, (start_a, start_b), (end_a, end_b), is_inward);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// square of 10 pixels in a window of 20, two counter clockwise triangles
+	fn square() -> (Vec<Vertex>, Vec<u32>) {
+		let vertexs = [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]].into_iter().map(|[x, y]| Vertex {
+			position: [x, y, 0.0],
+			color: [1.0; 4],
+		}).collect();
+		(vertexs, vec!(0, 1, 2, 0, 2, 3))
+	}
+
+	fn signed_area(vertexs: &[Vertex], triangle: &[u32]) -> f32 {
+		let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|index| vertexs[index as usize].position);
+		(b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+	}
+
+	#[test]
+	fn feather_adds_transparent_strip_with_same_winding() {
+		let (mut vertexs, mut indices) = square();
+		Feather::new(1.0).apply(&mut vertexs, &mut indices, Vec2::same(20.0));
+		assert_eq!(vertexs.len(), 8);
+		assert_eq!(indices.len(), 6 + 4 * 6);
+		assert!(vertexs[4..].iter().all(|vertex| vertex.color[3] == 0.0));
+		// corners move one pixel away from both edges
+		let corner = vertexs[4..].iter().find(|vertex| vertex.position[0] > 0.0 && vertex.position[1] > 0.0).unwrap();
+		assert!((corner.position[0] - 0.6).abs() < 1e-5 && (corner.position[1] - 0.6).abs() < 1e-5);
+		assert!(indices.chunks_exact(3).all(|triangle| signed_area(&vertexs, triangle) >= 0.0));
+	}
+
+	#[test]
+	fn feather_of_zero_width_does_nothing() {
+		let (mut vertexs, mut indices) = square();
+		Feather::new(0.0).apply(&mut vertexs, &mut indices, Vec2::same(20.0));
+		assert_eq!((vertexs.len(), indices.len()), (4, 6));
+	}
+
+	#[test]
+	fn grow_moves_outline() {
+		let (mut vertexs, indices) = square();
+		Feather::new(0.0).grow(&mut vertexs, &indices, Vec2::same(20.0), -1.0);
+		assert!(vertexs.iter().all(|vertex| (vertex.position[0].abs() - 0.4).abs() < 1e-5 && (vertex.position[1].abs() - 0.4).abs() < 1e-5));
+	}
+
+	#[test]
+	fn inner_band_stays_inside_with_same_winding() {
+		let (mut vertexs, indices) = square();
+		let band = Feather::new(0.0).inner_band(&mut vertexs, &indices, Vec2::same(20.0), |_| 1.0, |_| 2.0);
+		// a solid quad and a fading quad for each edge
+		assert_eq!(band.len(), 4 * 12);
+		assert!(vertexs[4..].iter().all(|vertex| vertex.position[0].abs() < 0.5 && vertex.position[1].abs() < 0.5));
+		assert!(band.chunks_exact(3).all(|triangle| signed_area(&vertexs, triangle) >= 0.0));
+	}
+}
@@ -0,0 +1,231 @@
+//! fonts registered at runtime, see [`crate::Ui::register_font`] and [`crate::Ui::set_font_fallback`]
+//!
+//! every [`crate::Ui`] has its own [`FontBook`], texts are laid out with the one of the ui running a frame. renderers get it by [`crate::integrator::Output::fonts`].
+//!
+//! font family of a text shape is [`crate::integrator::ShapeExtra::font_family`], which is given by [`family_id`].
+
+use std::sync::RwLock;
+use std::sync::Mutex;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::Range;
+use once_cell::sync::Lazy;
+use ab_glyph::Font;
+use ab_glyph::FontArc;
+use nablo_shape::prelude::shape_elements::DEFAULT_FONT;
+use anyhow::Result;
+use crate::widgets::TruncationCache;
+
+/// DejaVu Sans Mono, see `src/fonts/LICENSE-DejaVu`
+const MONOSPACE_FONT: &[u8] = include_bytes!("fonts/DejaVuSansMono.ttf");
+
+/// fonts of a [`crate::Ui`], shared with its renderer
+pub type SharedFontBook = Arc<RwLock<FontBook>>;
+
+/// used outside frames, it only has build-in fonts
+static BUILD_IN: Lazy<SharedFontBook> = Lazy::new(SharedFontBook::default);
+
+/// every font book gets a new generation from here when it changes, so generations of different books never meet
+static GENERATIONS: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+	/// fonts of the ui running a frame on this thread, see [`with_font_book`]
+	static CURRENT: RefCell<Option<SharedFontBook>> = RefCell::new(None);
+}
+
+/// fonts of the [`crate::Ui`] running a frame on this thread, [`Text`](crate::widgets::Text)s are laid out with them.
+/// outside frames there are only build-in fonts, use [`crate::Ui::layout_text`] there
+pub fn font_book() -> SharedFontBook {
+	CURRENT.with(|current| current.borrow().clone()).unwrap_or_else(|| BUILD_IN.clone())
+}
+
+/// run `f` with `book` as [`font_book`]
+pub(crate) fn with_font_book<R>(book: &SharedFontBook, f: impl FnOnce() -> R) -> R {
+	let previous = CURRENT.with(|current| current.replace(Some(book.clone())));
+	let back = f();
+	CURRENT.with(|current| *current.borrow_mut() = previous);
+	back
+}
+
+fn next_generation() -> u64 {
+	GENERATIONS.fetch_add(1, Ordering::Relaxed)
+}
+
+/// all fonts we have and how to choose between them
+pub struct FontBook {
+	/// first one is the build-in font, second one is the build-in monospace font
+	fonts: Vec<FontArc>,
+	/// family id to index of `fonts`
+	families: HashMap<u32, usize>,
+	/// family ids
+	fallbacks: HashMap<Script, Vec<u32>>,
+	/// changes every time fonts or fallbacks change, never same as the one of another book
+	generation: u64,
+	/// see [`crate::widgets::Text::galley`], dropped when fonts change
+	pub(crate) truncated: Mutex<TruncationCache>,
+}
+
+impl Default for FontBook {
+	fn default() -> Self {
+		Self {
+			fonts: vec!(FontArc::try_from_slice(DEFAULT_FONT).unwrap(), FontArc::try_from_slice(MONOSPACE_FONT).unwrap()),
+			families: HashMap::from([(DEFAULT_FAMILY, 0), (family_id(MONOSPACE), 1)]),
+			fallbacks: HashMap::new(),
+			generation: next_generation(),
+			truncated: Mutex::default(),
+		}
+	}
+}
+
+impl FontBook {
+	/// all fonts, index of this is what [`Self::font_for`] returns
+	pub fn fonts(&self) -> &Vec<FontArc> {
+		&self.fonts
+	}
+
+	/// changes every time fonts or fallbacks change, so results made by old fonts can be dropped
+	pub fn generation(&self) -> u64 {
+		self.generation
+	}
+
+	pub(crate) fn register(&mut self, name: &str, bytes: Vec<u8>) -> Result<()> {
+		let font = FontArc::try_from_vec(bytes)?;
+		self.changed();
+		let id = family_id(name);
+		if let Some(index) = self.families.get(&id) {
+			// keep index so glyphs already laid out stay valid
+			self.fonts[*index] = font;
+		}else {
+			self.families.insert(id, self.fonts.len());
+			self.fonts.push(font);
+		}
+		Ok(())
+	}
+
+	pub(crate) fn set_fallback(&mut self, script: Script, families: &[String]) {
+		self.fallbacks.insert(script, families.iter().map(|name| family_id(name)).collect());
+		self.changed();
+	}
+
+	fn changed(&mut self) {
+		self.generation = next_generation();
+		*self.truncated.get_mut().unwrap() = TruncationCache::default();
+	}
+
+	/// pick a font for a charater: the family itself, then fallbacks of charater's script, then build-in font
+	pub fn font_for(&self, family: u32, chr: char) -> usize {
+		let own = self.families.get(&family).copied().unwrap_or(0);
+		let fallbacks = self.fallbacks.get(&Script::of(chr)).into_iter().flatten().filter_map(|id| self.families.get(id).copied());
+		std::iter::once(own).chain(fallbacks).chain(std::iter::once(0))
+			.find(|index| self.fonts[*index].glyph_id(chr).0 != 0)
+			.unwrap_or(own)
+	}
+
+	/// split text into byte ranges drawn by same font
+	pub fn font_runs(&self, family: u32, text: &str) -> Vec<(Range<usize>, usize)> {
+		let mut back: Vec<(Range<usize>, usize)> = vec!();
+		for (index, chr) in text.char_indices() {
+			let font = self.font_for(family, chr);
+			match back.last_mut() {
+				Some((range, last)) if *last == font => range.end = index + chr.len_utf8(),
+				_ => back.push((index..index + chr.len_utf8(), font)),
+			}
+		}
+		back
+	}
+}
+
+/// family id of the build-in font
+pub const DEFAULT_FAMILY: u32 = 0;
+
+/// family name of the build-in monospace font, registering a font with this name replaces it
+pub const MONOSPACE: &str = "monospace";
+
+/// get the family id of a font family name, same name always gets same id.
+pub fn family_id(name: &str) -> u32 {
+	// FNV-1a
+	let mut hash: u32 = 0x811c9dc5;
+	for byte in name.bytes() {
+		hash ^= byte as u32;
+		hash = hash.wrapping_mul(0x01000193);
+	}
+	if hash == DEFAULT_FAMILY {
+		1
+	}else {
+		hash
+	}
+}
+
+/// scripts which can have their own fallback chain
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Script {
+	Latin,
+	Greek,
+	Cyrillic,
+	Arabic,
+	Hebrew,
+	/// chinese, japanese and korean
+	Cjk,
+	Emoji,
+	Other,
+}
+
+impl Script {
+	/// get which script a charater belongs to
+	pub fn of(chr: char) -> Self {
+		match chr as u32 {
+			0x0000..=0x024F | 0x1E00..=0x1EFF => Self::Latin,
+			0x0370..=0x03FF | 0x1F00..=0x1FFF => Self::Greek,
+			0x0400..=0x052F => Self::Cyrillic,
+			0x0590..=0x05FF | 0xFB1D..=0xFB4F => Self::Hebrew,
+			0x0600..=0x06FF | 0x0750..=0x077F | 0x08A0..=0x08FF | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => Self::Arabic,
+			0x1100..=0x11FF | 0x2E80..=0x2FDF | 0x3000..=0x30FF | 0x3130..=0x318F | 0x31F0..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF | 0x20000..=0x2FA1F => Self::Cjk,
+			0x2600..=0x27BF | 0x1F000..=0x1FAFF => Self::Emoji,
+			_ => Self::Other,
+		}
+	}
+}
+
+/// how the renderer rasterizes glyphs
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TextRendering {
+	/// glyphs are rasterized for every size they are drawn in, sharpest for text which is not scaled.
+	/// texts with outlines, shadows or rotation are still drawn from distance fields
+	#[default]
+	Bitmap,
+	/// glyphs are rasterized once as signed distance fields, stays sharp at any scale
+	Sdf,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn font_book_follows_frames() {
+		let book = SharedFontBook::default();
+		let other = SharedFontBook::default();
+		assert!(!Arc::ptr_eq(&font_book(), &book));
+		with_font_book(&book, || {
+			assert!(Arc::ptr_eq(&font_book(), &book));
+			with_font_book(&other, || assert!(Arc::ptr_eq(&font_book(), &other)));
+			assert!(Arc::ptr_eq(&font_book(), &book));
+		});
+		assert!(Arc::ptr_eq(&font_book(), &BUILD_IN));
+	}
+
+	#[test]
+	fn generations_never_meet() {
+		let book = SharedFontBook::default();
+		let other = SharedFontBook::default();
+		assert_ne!(book.read().unwrap().generation(), other.read().unwrap().generation());
+		let before = book.read().unwrap().generation();
+		book.write().unwrap().set_fallback(Script::Cjk, &["none".into()]);
+		let after = book.read().unwrap().generation();
+		assert_ne!(before, after);
+		assert_ne!(after, other.read().unwrap().generation());
+	}
+}
@@ -0,0 +1,621 @@
+//! glyph atlas of the wgpu renderer.
+//!
+//! every glyph is rasterized once into one shared atlas. texts are drawn as textured quads where their [`TextRow`]s laid out by [`crate::Ui`] say,
+//! so a whole batch of texts only needs one draw call.
+//!
+//! with [`TextRendering::Sdf`] glyphs are rasterized once in [`SDF_SIZE`] as signed distance fields and scaled to any size when drawing.
+//! texts with outlines, shadows or rotation always use distance fields, `text.wgsl` draws the effects from them.
+
+use ab_glyph::point;
+use ab_glyph::Font;
+use ab_glyph::GlyphId;
+use ab_glyph::PxScale;
+use nablo_shape::prelude::shape_elements::Style;
+use nablo_shape::prelude::shape_elements::Text;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::sync::Arc;
+use wgpu::TextureView;
+use wgpu::include_wgsl;
+use crate::font::FontBook;
+use crate::font::SharedFontBook;
+use crate::font::TextRendering;
+use crate::integrator::RenderStats;
+use crate::state::WTexture;
+use crate::text_layout::text_scale;
+use crate::text_layout::TextRow;
+use crate::texture::create_texture;
+use crate::widgets::TextOutline;
+use crate::widgets::TextShadow;
+
+/// glyphs are rasterized at this many horizontal positions inside a pixel
+const SUBPIXEL: u32 = 4;
+/// font sizes are rounded to `1 / SCALE_STEP` pixel
+const SCALE_STEP: f32 = 4.0;
+/// empty pixels between glyphs in the atlas, avoid sampling neighbours
+const PADDING: u32 = 1;
+const ATLAS_START_SIZE: u32 = 512;
+const ATLAS_MAX_SIZE: u32 = 4096;
+/// pixel height sdf glyphs are rasterized in
+const SDF_SIZE: f32 = 48.0;
+/// how far in pixel a sdf reaches from the edge of a glyph, outlines and blurs of shadows can not reach further
+const SDF_SPREAD: u32 = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphKey {
+	font: usize,
+	id: GlyphId,
+	/// px scale multiplied with [`SCALE_STEP`]
+	scale: u32,
+	/// horizontal offset in [`SUBPIXEL`]
+	offset: u32,
+	/// rasterized as signed distance field
+	sdf: bool,
+}
+
+#[derive(Clone, Copy)]
+struct AtlasGlyph {
+	/// left top in atlas, in pixel
+	position: [u32; 2],
+	size: [u32; 2],
+	/// left top of the bitmap relative to the pen position on baseline
+	offset: Vec2,
+}
+
+struct Shelf {
+	y: u32,
+	height: u32,
+	x: u32,
+}
+
+/// the atlas is full, everything in it should be thrown away
+struct AtlasFull;
+
+struct Atlas {
+	texture: WTexture,
+	/// sampled linearly and clamped, sdf needs it when scaling
+	bind_group: wgpu::BindGroup,
+	size: u32,
+	max_size: u32,
+	shelves: Vec<Shelf>,
+	/// `None` for glyphs with nothing to draw like spaces
+	glyphs: HashMap<GlyphKey, Option<AtlasGlyph>>,
+}
+
+/// a text shape to draw, see [`crate::integrator::ParsedShape::Text`]
+pub(crate) struct GlyphText {
+	pub text: Text,
+	pub style: Style,
+	pub row: Arc<TextRow>,
+	pub outline: Option<TextOutline>,
+	pub shadow: Option<TextShadow>,
+}
+
+/// corner of a glyph quad, `VertexInput` in `text.wgsl`
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct GlyphVertex {
+	/// in clip space
+	position: [f32; 2],
+	/// in atlas, reaches out of `uv_rect` where only the shadow is drawn
+	uv: [f32; 2],
+	/// left top and right bottom of the glyph in atlas
+	uv_rect: [f32; 4],
+	/// left top and right bottom in pixel, nothing is drawn outside
+	clip: [f32; 4],
+	fill: [f32; 4],
+	outline_color: [f32; 4],
+	shadow_color: [f32; 4],
+	/// where the shadow is sampled relative to `uv`
+	shadow_offset: [f32; 2],
+	/// 1.0 for sdf glyphs, outline width and shadow softness in distance of the sdf, last one is unused
+	params: [f32; 4],
+}
+
+const GLYPH_ATTRIBUTES: [wgpu::VertexAttribute; 9] = wgpu::vertex_attr_array![
+	0 => Float32x2,
+	1 => Float32x2,
+	2 => Float32x4,
+	3 => Float32x4,
+	4 => Float32x4,
+	5 => Float32x4,
+	6 => Float32x4,
+	7 => Float32x2,
+	8 => Float32x4,
+];
+
+/// shared glyph atlas and layout cache, see module level docs
+pub(crate) struct GlyphCache {
+	atlas: Atlas,
+	/// how texts without effects are drawn
+	rendering: TextRendering,
+	/// glyphs in atlas come from here, see [`Self::set_fonts`]
+	fonts: SharedFontBook,
+	/// of `fonts` when glyphs in atlas were rasterized
+	generation: u64,
+	pipeline: wgpu::RenderPipeline,
+	vertexs: Vec<GlyphVertex>,
+	indices: Vec<u32>,
+	vertex_buffer: wgpu::Buffer,
+	index_buffer: wgpu::Buffer,
+}
+
+impl Atlas {
+	fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+		let max_size = device.limits().max_texture_dimension_2d.min(ATLAS_MAX_SIZE);
+		let size = ATLAS_START_SIZE.min(max_size);
+		let texture = create_texture(Vec2::same(size as f32), device, queue);
+		Self {
+			bind_group: atlas_bind_group(device, &texture),
+			texture,
+			size,
+			max_size,
+			shelves: vec!(),
+			glyphs: HashMap::new(),
+		}
+	}
+
+	/// throw away all glyphs, the atlas grows if it can
+	fn reset(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+		if self.size < self.max_size {
+			self.size = (self.size * 2).min(self.max_size);
+			self.texture = create_texture(Vec2::same(self.size as f32), device, queue);
+			self.bind_group = atlas_bind_group(device, &self.texture);
+		}
+		self.shelves.clear();
+		self.glyphs.clear();
+	}
+
+	/// find a place for a bitmap on shelves
+	fn allocate(&mut self, width: u32, height: u32) -> Option<[u32; 2]> {
+		let width = width + PADDING;
+		let height = height + PADDING;
+		let size = self.size;
+		if let Some(shelf) = self.shelves.iter_mut().find(|shelf| shelf.height >= height && shelf.height <= height * 2 && shelf.x + width <= size) {
+			let position = [shelf.x, shelf.y];
+			shelf.x += width;
+			return Some(position);
+		}
+		let y = self.shelves.last().map(|shelf| shelf.y + shelf.height).unwrap_or(0);
+		if y + height > size || width > size {
+			return None;
+		}
+		self.shelves.push(Shelf { y, height, x: width });
+		Some([0, y])
+	}
+
+	fn get(&mut self, book: &FontBook, queue: &wgpu::Queue, key: GlyphKey) -> Result<Option<AtlasGlyph>, AtlasFull> {
+		if let Some(glyph) = self.glyphs.get(&key) {
+			return Ok(*glyph);
+		}
+		let glyph = self.rasterize(book, queue, key)?;
+		self.glyphs.insert(key, glyph);
+		Ok(glyph)
+	}
+
+	fn rasterize(&mut self, book: &FontBook, queue: &wgpu::Queue, key: GlyphKey) -> Result<Option<AtlasGlyph>, AtlasFull> {
+		let Some(font) = book.fonts().get(key.font) else {
+			return Ok(None);
+		};
+		let offset = key.offset as f32 / SUBPIXEL as f32;
+		let glyph = key.id.with_scale_and_position(PxScale::from(key.scale as f32 / SCALE_STEP), point(offset, 0.0));
+		let Some(outlined) = font.outline_glyph(glyph) else {
+			return Ok(None);
+		};
+		let bounds = outlined.px_bounds();
+		let glyph_width = bounds.width().ceil() as u32;
+		let glyph_height = bounds.height().ceil() as u32;
+		if glyph_width == 0 || glyph_height == 0 {
+			return Ok(None);
+		}
+		// sdf needs room outside the glyph for distances
+		let spread = if key.sdf { SDF_SPREAD } else { 0 };
+		let width = glyph_width + spread * 2;
+		let height = glyph_height + spread * 2;
+		let position = self.allocate(width, height).ok_or(AtlasFull)?;
+		let mut coverages = vec![0.0; (width * height) as usize];
+		outlined.draw(|x, y, coverage| {
+			if x < glyph_width && y < glyph_height {
+				coverages[((y + spread) * width + x + spread) as usize] = coverage.clamp(0.0, 1.0);
+			}
+		});
+		let alphas = if key.sdf { signed_distance_field(&coverages, width, height) } else { coverages };
+		// white with coverage or distance as alpha, the color comes from tint
+		let mut rgba = vec![255u8; (width * height * 4) as usize];
+		for (index, alpha) in alphas.iter().enumerate() {
+			rgba[index * 4 + 3] = (alpha * 255.0).round() as u8;
+		}
+		queue.write_texture(wgpu::ImageCopyTexture {
+			texture: &self.texture.texture,
+			mip_level: 0,
+			origin: wgpu::Origin3d { x: position[0], y: position[1], z: 0 },
+			aspect: wgpu::TextureAspect::All,
+		}, &rgba, wgpu::ImageDataLayout {
+			offset: 0,
+			bytes_per_row: Some(width * 4),
+			rows_per_image: Some(height),
+		}, wgpu::Extent3d {
+			width,
+			height,
+			depth_or_array_layers: 1,
+		});
+		Ok(Some(AtlasGlyph {
+			position,
+			size: [width, height],
+			offset: Vec2::new(bounds.min.x - offset - spread as f32, bounds.min.y - spread as f32),
+		}))
+	}
+}
+
+impl GlyphCache {
+	pub(crate) fn new(device: &wgpu::Device, queue: &wgpu::Queue, format: wgpu::TextureFormat, rendering: TextRendering, sample_count: u32) -> Self {
+		let atlas = Atlas::new(device, queue);
+		let shader = device.create_shader_module(include_wgsl!("text.wgsl"));
+		let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+			label: Some("Text Pipeline Layout"),
+			bind_group_layouts: &[&atlas.texture.layout],
+			push_constant_ranges: &[],
+		});
+		// custom shaders are for shapes, never for texts
+		let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+			label: Some("Text Pipeline"),
+			layout: Some(&layout),
+			vertex: wgpu::VertexState {
+				module: &shader,
+				entry_point: "vs_main",
+				buffers: &[wgpu::VertexBufferLayout {
+					array_stride: size_of::<GlyphVertex>() as u64,
+					step_mode: wgpu::VertexStepMode::Vertex,
+					attributes: &GLYPH_ATTRIBUTES,
+				}],
+			},
+			fragment: Some(wgpu::FragmentState {
+				module: &shader,
+				entry_point: "fs_main",
+				targets: &[Some(wgpu::ColorTargetState {
+					format,
+					blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+					write_mask: wgpu::ColorWrites::ALL,
+				})],
+			}),
+			primitive: wgpu::PrimitiveState {
+				topology: wgpu::PrimitiveTopology::TriangleList,
+				strip_index_format: None,
+				front_face: wgpu::FrontFace::Ccw,
+				cull_mode: None,
+				polygon_mode: wgpu::PolygonMode::Fill,
+				unclipped_depth: false,
+				conservative: false,
+			},
+			depth_stencil: None,
+			multisample: wgpu::MultisampleState {
+				count: sample_count,
+				mask: !0,
+				alpha_to_coverage_enabled: false,
+			},
+			multiview: None,
+		});
+		Self {
+			atlas,
+			rendering,
+			fonts: SharedFontBook::default(),
+			generation: 0,
+			pipeline,
+			vertexs: vec!(),
+			indices: vec!(),
+			vertex_buffer: text_buffer(device, "Text Vertex Buffer", 2_u64.pow(16), wgpu::BufferUsages::VERTEX),
+			index_buffer: text_buffer(device, "Text Index Buffer", 2_u64.pow(16), wgpu::BufferUsages::INDEX),
+		}
+	}
+
+	/// draw all `texts` in one draw call
+	pub(crate) fn draw(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, view: &TextureView, resolve_target: Option<&TextureView>, texts: &[GlyphText], window_size: Vec2) -> RenderStats {
+		if texts.is_empty() {
+			return RenderStats::default();
+		}
+		let fonts = self.fonts.clone();
+		let book = fonts.read().unwrap();
+		if book.generation() != self.generation {
+			// same font index may be another font now
+			self.atlas.shelves.clear();
+			self.atlas.glyphs.clear();
+			self.generation = book.generation();
+		}
+		if self.build(&book, queue, texts, window_size).is_err() {
+			// glyphs already in this batch are thrown away too, so build the whole batch again
+			self.atlas.reset(device, queue);
+			// still full means the batch itself is larger than the atlas, draw what fits
+			let _ = self.build(&book, queue, texts, window_size);
+		}
+		drop(book);
+		if self.indices.is_empty() {
+			return RenderStats::default();
+		}
+
+		let vertex_bytes: &[u8] = bytemuck::cast_slice(&self.vertexs);
+		let index_bytes: &[u8] = bytemuck::cast_slice(&self.indices);
+		if vertex_bytes.len() as u64 > self.vertex_buffer.size() {
+			self.vertex_buffer = text_buffer(device, "Text Vertex Buffer", (vertex_bytes.len() as u64).next_power_of_two(), wgpu::BufferUsages::VERTEX);
+		}
+		if index_bytes.len() as u64 > self.index_buffer.size() {
+			self.index_buffer = text_buffer(device, "Text Index Buffer", (index_bytes.len() as u64).next_power_of_two(), wgpu::BufferUsages::INDEX);
+		}
+		queue.write_buffer(&self.vertex_buffer, 0, vertex_bytes);
+		queue.write_buffer(&self.index_buffer, 0, index_bytes);
+
+		let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+			label: Some("Text Encoder"),
+		});
+		let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+			label: Some("Text Pass"),
+			color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+				view,
+				resolve_target,
+				ops: wgpu::Operations {
+					load: wgpu::LoadOp::Load,
+					store: wgpu::StoreOp::Store,
+				},
+			})],
+			..Default::default()
+		});
+		render_pass.set_pipeline(&self.pipeline);
+		render_pass.set_bind_group(0, &self.atlas.bind_group, &[]);
+		render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..vertex_bytes.len() as u64));
+		render_pass.set_index_buffer(self.index_buffer.slice(..index_bytes.len() as u64), wgpu::IndexFormat::Uint32);
+		render_pass.draw_indexed(0..self.indices.len() as u32, 0, 0..1);
+		drop(render_pass);
+		queue.submit(Some(encoder.finish()));
+		RenderStats {
+			draw_calls: 1,
+			vertices: self.vertexs.len(),
+			upload_bytes: vertex_bytes.len() + index_bytes.len(),
+		}
+	}
+
+	/// fill `self.vertexs` and `self.indices` with quads of all `texts`
+	fn build(&mut self, book: &FontBook, queue: &wgpu::Queue, texts: &[GlyphText], window_size: Vec2) -> Result<(), AtlasFull> {
+		self.vertexs.clear();
+		self.indices.clear();
+		let atlas_size = self.atlas.size as f32;
+		for GlyphText { text, style, row, outline, shadow } in texts {
+			let scale_factor = style.scale_factor;
+			let clip = Area::new_with_origin(window_size).cross_part(&Area::new(style.clip.area[0] * scale_factor, style.clip.area[1] * scale_factor));
+			if clip.width() <= 0.0 || clip.height() <= 0.0 || text.text.is_empty() {
+				continue;
+			}
+			let scale = ((text_scale(style.size) * scale_factor * SCALE_STEP).round() as u32).max(1);
+			let angle = style.rotate.to_radians();
+			// bitmaps can not make effects and look blurry when rotated
+			let is_sdf = self.rendering == TextRendering::Sdf || outline.is_some() || shadow.is_some() || angle != 0.0;
+			// how much a glyph in atlas should be scaled
+			let ratio = if is_sdf { scale as f32 / SCALE_STEP / SDF_SIZE } else { 1.0 };
+
+			let origin = style.position * scale_factor;
+			let transform_origin = style.transform_origin * scale_factor;
+			let baseline = origin.y + row.ascent * scale_factor;
+			let baseline = if is_sdf { baseline } else { baseline.round() };
+			let shadow_offset = shadow.map_or(Vec2::ZERO, |shadow| shadow.offset * scale_factor);
+			let uv_per_pixel = 1.0 / ratio / atlas_size;
+			let template = GlyphVertex {
+				position: [0.0; 2],
+				uv: [0.0; 2],
+				uv_rect: [0.0; 4],
+				clip: [clip.left_top().x, clip.left_top().y, clip.right_bottom().x, clip.right_bottom().y],
+				fill: style.fill.normalized(),
+				outline_color: outline.map_or([0.0; 4], |outline| outline.color.normalized()),
+				shadow_color: shadow.map_or([0.0; 4], |shadow| shadow.color.normalized()),
+				shadow_offset: [shadow_offset.x * uv_per_pixel, shadow_offset.y * uv_per_pixel],
+				params: [
+					if is_sdf { 1.0 } else { 0.0 },
+					outline.map_or(0.0, |outline| sdf_distance(outline.width * scale_factor, ratio)),
+					shadow.map_or(0.0, |shadow| sdf_distance(shadow.blur * scale_factor, ratio)),
+					0.0,
+				],
+			};
+			for laid in &row.glyphs {
+				let Some(font) = book.fonts().get(laid.font) else {
+					continue;
+				};
+				let id = font.glyph_id(laid.display);
+				let pen = origin.x + laid.x * scale_factor;
+				let (left, key) = if is_sdf {
+					(pen, GlyphKey { font: laid.font, id, scale: (SDF_SIZE * SCALE_STEP) as u32, offset: 0, sdf: true })
+				}else {
+					let mut left = pen.floor();
+					let mut offset = ((pen - left) * SUBPIXEL as f32).round() as u32;
+					if offset >= SUBPIXEL {
+						left += 1.0;
+						offset = 0;
+					}
+					(left, GlyphKey { font: laid.font, id, scale, offset, sdf: false })
+				};
+				let Some(glyph) = self.atlas.get(book, queue, key)? else {
+					continue;
+				};
+				let left_top = Vec2::new(left, baseline) + glyph.offset * ratio;
+				let right_bottom = left_top + Vec2::new(glyph.size[0] as f32, glyph.size[1] as f32) * ratio;
+				let uv_left_top = Vec2::new(glyph.position[0] as f32, glyph.position[1] as f32) / atlas_size;
+				let uv_right_bottom = uv_left_top + Vec2::new(glyph.size[0] as f32, glyph.size[1] as f32) / atlas_size;
+				// the shadow reaches out of the glyph
+				let area = Area::new(
+					left_top + Vec2::new(shadow_offset.x.min(0.0), shadow_offset.y.min(0.0)),
+					right_bottom + Vec2::new(shadow_offset.x.max(0.0), shadow_offset.y.max(0.0)),
+				);
+				let uv = Area::new(uv_left_top + (area.left_top() - left_top) * uv_per_pixel, uv_right_bottom + (area.right_bottom() - right_bottom) * uv_per_pixel);
+				let corners = [area.left_top(), area.right_top(), area.right_bottom(), area.left_bottom()].map(|point| rotate_around(point, transform_origin, angle));
+				let visible = bounds(&corners).cross_part(&clip);
+				if visible.width() <= 0.0 || visible.height() <= 0.0 {
+					continue;
+				}
+				let glyph_vertex = GlyphVertex {
+					uv_rect: [uv_left_top.x, uv_left_top.y, uv_right_bottom.x, uv_right_bottom.y],
+					..template
+				};
+				push_quad(&mut self.vertexs, &mut self.indices, corners, uv, glyph_vertex, window_size);
+			}
+		}
+		Ok(())
+	}
+
+	/// glyphs of texts drawn next come from `fonts`, see [`crate::integrator::Output::fonts`]
+	pub(crate) fn set_fonts(&mut self, fonts: SharedFontBook) {
+		self.fonts = fonts;
+	}
+}
+
+fn atlas_bind_group(device: &wgpu::Device, texture: &WTexture) -> wgpu::BindGroup {
+	let view = texture.texture.create_view(&wgpu::TextureViewDescriptor::default());
+	let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+		address_mode_u: wgpu::AddressMode::ClampToEdge,
+		address_mode_v: wgpu::AddressMode::ClampToEdge,
+		address_mode_w: wgpu::AddressMode::ClampToEdge,
+		mag_filter: wgpu::FilterMode::Linear,
+		min_filter: wgpu::FilterMode::Linear,
+		mipmap_filter: wgpu::FilterMode::Nearest,
+		..Default::default()
+	});
+	device.create_bind_group(&wgpu::BindGroupDescriptor {
+		layout: &texture.layout,
+		entries: &[
+			wgpu::BindGroupEntry {
+				binding: 0,
+				resource: wgpu::BindingResource::TextureView(&view),
+			},
+			wgpu::BindGroupEntry {
+				binding: 1,
+				resource: wgpu::BindingResource::Sampler(&sampler),
+			}
+		],
+		label: Some("glyph_atlas_bind_group"),
+	})
+}
+
+/// turn coverages into distances to the edge, 0.5 is right on the edge and 1.0 is [`SDF_SPREAD`] inside
+fn signed_distance_field(coverages: &[f32], width: u32, height: u32) -> Vec<f32> {
+	let spread = SDF_SPREAD as i32;
+	let (width, height) = (width as i32, height as i32);
+	let is_inside = |x: i32, y: i32| coverages[(y * width + x) as usize] >= 0.5;
+	let mut back = Vec::with_capacity(coverages.len());
+	for y in 0..height {
+		for x in 0..width {
+			let inside = is_inside(x, y);
+			// nearest pixel on the other side of the edge
+			let mut nearest = (spread * spread) as f32;
+			for dy in -spread..=spread {
+				for dx in -spread..=spread {
+					let (other_x, other_y) = (x + dx, y + dy);
+					if other_x < 0 || other_y < 0 || other_x >= width || other_y >= height {
+						continue;
+					}
+					if is_inside(other_x, other_y) != inside {
+						nearest = nearest.min((dx * dx + dy * dy) as f32);
+					}
+				}
+			}
+			let distance = nearest.sqrt() - 0.5;
+			let signed = if inside { distance } else { -distance };
+			back.push((0.5 + signed / (2.0 * SDF_SPREAD as f32)).clamp(0.0, 1.0));
+		}
+	}
+	back
+}
+
+fn text_buffer(device: &wgpu::Device, label: &str, size: u64, usage: wgpu::BufferUsages) -> wgpu::Buffer {
+	device.create_buffer(&wgpu::BufferDescriptor {
+		label: Some(label),
+		size,
+		usage: usage | wgpu::BufferUsages::COPY_DST,
+		mapped_at_creation: false,
+	})
+}
+
+/// push a quad of `corners` in pixel clockwise from left top, showing `uv` of the atlas. the rest comes from `template`
+fn push_quad(vertexs: &mut Vec<GlyphVertex>, indices: &mut Vec<u32>, corners: [Vec2; 4], uv: Area, template: GlyphVertex, window_size: Vec2) {
+	let start = vertexs.len() as u32;
+	for (point, tex_coord) in corners.into_iter().zip([uv.left_top(), uv.right_top(), uv.right_bottom(), uv.left_bottom()]) {
+		vertexs.push(GlyphVertex {
+			position: [point.x / window_size.x * 2.0 - 1.0, 1.0 - point.y / window_size.y * 2.0],
+			uv: [tex_coord.x, tex_coord.y],
+			..template
+		});
+	}
+	indices.extend_from_slice(&[start, start + 1, start + 2, start, start + 2, start + 3]);
+}
+
+/// turn `point` around `origin` by `angle` in radians, clockwise on screen
+fn rotate_around(point: Vec2, origin: Vec2, angle: f32) -> Vec2 {
+	if angle == 0.0 {
+		return point;
+	}
+	let (sin, cos) = angle.sin_cos();
+	let offset = point - origin;
+	origin + Vec2::new(offset.x * cos - offset.y * sin, offset.x * sin + offset.y * cos)
+}
+
+/// smallest area containing all `points`
+fn bounds(points: &[Vec2]) -> Area {
+	let mut left_top = Vec2::same(f32::INFINITY);
+	let mut right_bottom = Vec2::same(f32::NEG_INFINITY);
+	for point in points {
+		left_top = Vec2::new(left_top.x.min(point.x), left_top.y.min(point.y));
+		right_bottom = Vec2::new(right_bottom.x.max(point.x), right_bottom.y.max(point.y));
+	}
+	Area::new(left_top, right_bottom)
+}
+
+/// `pixel` on screen in distance of a sdf scaled by `ratio`, a sdf changes `1 / (2 * SDF_SPREAD)` every pixel of the atlas
+fn sdf_distance(pixel: f32, ratio: f32) -> f32 {
+	(pixel / ratio / (2.0 * SDF_SPREAD as f32)).clamp(0.0, 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bytemuck::Zeroable;
+
+	fn assert_near(a: Vec2, b: Vec2) {
+		assert!((a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4, "{:?} != {:?}", (a.x, a.y), (b.x, b.y));
+	}
+
+	#[test]
+	fn rotation_turns_clockwise_around_origin() {
+		let origin = Vec2::new(10.0, 10.0);
+		assert_near(rotate_around(Vec2::new(20.0, 10.0), origin, 90_f32.to_radians()), Vec2::new(10.0, 20.0));
+		assert_near(rotate_around(Vec2::new(20.0, 10.0), origin, 180_f32.to_radians()), Vec2::new(0.0, 10.0));
+		assert_near(rotate_around(Vec2::new(3.0, 4.0), origin, 0.0), Vec2::new(3.0, 4.0));
+	}
+
+	#[test]
+	fn bounds_of_rotated_quad() {
+		let area = Area::new(Vec2::ZERO, Vec2::new(4.0, 2.0));
+		let corners = [area.left_top(), area.right_top(), area.right_bottom(), area.left_bottom()].map(|point| rotate_around(point, Vec2::ZERO, 90_f32.to_radians()));
+		let bounds = bounds(&corners);
+		assert_near(bounds.left_top(), Vec2::new(-2.0, 0.0));
+		assert_near(bounds.right_bottom(), Vec2::new(0.0, 4.0));
+	}
+
+	#[test]
+	fn effects_in_sdf_distance() {
+		// a glyph drawn in half of its sdf size, one pixel on screen is two in the atlas
+		assert!((sdf_distance(1.0, 0.5) - 2.0 / (2.0 * SDF_SPREAD as f32)).abs() < 1e-6);
+		assert_eq!(sdf_distance(0.0, 0.5), 0.0);
+		// can not reach out of the spread
+		assert_eq!(sdf_distance(1000.0, 1.0), 0.5);
+	}
+
+	#[test]
+	fn quads_are_two_triangles() {
+		let mut vertexs = vec!();
+		let mut indices = vec!();
+		let template = GlyphVertex::zeroed();
+		let corners = [Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0), Vec2::new(0.0, 10.0)];
+		push_quad(&mut vertexs, &mut indices, corners, Area::new(Vec2::ZERO, Vec2::same(0.5)), template, Vec2::same(20.0));
+		push_quad(&mut vertexs, &mut indices, corners, Area::new(Vec2::ZERO, Vec2::same(0.5)), template, Vec2::same(20.0));
+		assert_eq!(indices, vec!(0, 1, 2, 0, 2, 3, 4, 5, 6, 4, 6, 7));
+		assert_eq!(vertexs[0].position, [-1.0, 1.0]);
+		assert_eq!(vertexs[2].position, [0.0, 0.0]);
+		assert_eq!(vertexs[2].uv, [0.5, 0.5]);
+	}
+}
@@ -19,7 +19,6 @@
  * ```
 */
 
-#[cfg(feature = "vertexs")]
 use nablo_shape::prelude::Area;
 #[cfg(feature = "vertexs")]
 use nablo_shape::prelude::shape_elements::Image;
@@ -31,9 +30,25 @@ use nablo_shape::prelude::shape_elements::Vertex;
 use nablo_shape::prelude::shape_elements::Style;
 #[cfg(feature = "vertexs")]
 use nablo_shape::prelude::shape_elements::Text;
+#[cfg(feature = "vertexs")]
+use nablo_shape::shape::ShapeElement;
+#[cfg(feature = "vertexs")]
+use nablo_shape::math::Vec2;
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+use crate::widgets::PaintCallback;
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+use std::collections::HashMap;
+use crate::widgets::Effect;
+use crate::widgets::TextOutline;
+use crate::widgets::TextShadow;
+use crate::text_layout::TextRow;
+use crate::font::SharedFontBook;
+use crate::font::with_font_book;
+use std::sync::Arc;
 use crate::Event;
 use crate::Shape;
 use crate::Ui;
+use time::Duration;
 
 /// the shape prased
 #[cfg(feature = "vertexs")]
@@ -44,17 +59,175 @@ pub enum ParsedShape {
 		indices: Vec<u32>,
 		clip_area: Area,
 		scale_factor: f32,
-		info: u32
+		info: u32,
+		/// `None` for current shader
+		shader: Option<String>,
+		paint: Paint,
+	},
+	/// glyphs are drawn as `row` says, outline and shadow are drawn from distance fields of glyphs
+	Text {
+		text: Text,
+		style: Style,
+		row: Arc<TextRow>,
+		outline: Option<TextOutline>,
+		shadow: Option<TextShadow>,
 	},
-	Text(Text, Style),
-	Image(Image, Style),
+	Image {
+		image: Image,
+		style: Style,
+		/// `None` for current shader
+		shader: Option<String>,
+		/// multiplied with the texture
+		tint: Color,
+		/// part of the texture shown, normalized
+		uv: Area,
+	},
+	/// where [`crate::widgets::PaintCallback`] `id` draws, in pixel. `clip` is not crossed with `area` or the target yet
+	PaintCallback {
+		id: String,
+		area: Area,
+		clip: Area,
+	},
+}
+
+/// turn shapes into what the renderer draws in a window of `window_size`, shapes should be sorted by layer
+#[cfg(feature = "vertexs")]
+pub(crate) fn parse_shapes(shapes: Vec<Shape>, extras: Vec<ShapeExtra>, window_size: Vec2, feather: f32) -> Vec<ParsedShape> {
+	let mut parsed_shapes = vec!();
+	let mut feather = crate::feather::Feather::new(feather);
+	for (shape, extra) in shapes.into_iter().zip(extras) {
+		let shader = extra.shader;
+		if let Some(id) = extra.paint_callback {
+			// the shape only tells where it is
+			let size = if let ShapeElement::Rect(rect) = &shape.shape { rect.width_and_height } else { Vec2::ZERO };
+			let scale_factor = Vec2::same(shape.style.scale_factor);
+			parsed_shapes.push(ParsedShape::PaintCallback {
+				id,
+				area: Area::new(shape.style.position * scale_factor, (shape.style.position + size) * scale_factor),
+				clip: Area::new(shape.style.clip.area[0] * scale_factor, shape.style.clip.area[1] * scale_factor),
+			});
+		}else if let ShapeElement::Text(inner) = shape.shape {
+			parsed_shapes.push(ParsedShape::Text {
+				text: inner,
+				style: shape.style,
+				row: extra.text_row.unwrap_or_default(),
+				outline: extra.outline,
+				shadow: extra.text_shadow,
+			});
+		}else if let ShapeElement::Image(inner) = shape.shape {
+			parsed_shapes.push(ParsedShape::Image {
+				image: inner,
+				style: shape.style,
+				shader,
+				tint: extra.tint.unwrap_or(Color::from(1.0)),
+				uv: extra.uv.unwrap_or(Area::new(Vec2::ZERO, Vec2::same(1.0))),
+			});
+		}else {
+			let (scale_factor, info) = (shape.style.scale_factor, shape.style.info);
+			let (mut vertexs, mut indices, clip_area) = shape.into_vertexs(window_size);
+			if extra.effects.is_empty() {
+				feather.apply(&mut vertexs, &mut indices, window_size);
+				parsed_shapes.push(ParsedShape::Vertexs { vertexs, indices, clip_area, scale_factor, info, shader, paint: Paint::Color });
+			}else {
+				let effects = crate::effect::effect_vertexs(vertexs, indices, &extra.effects, scale_factor, window_size, &mut feather);
+				parsed_shapes.extend(effects.into_iter().map(|(vertexs, indices, paint)| {
+					// effects only work with default shader
+					ParsedShape::Vertexs { vertexs, indices, clip_area, scale_factor, info, shader: None, paint }
+				}));
+			}
+		}
+	}
+	parsed_shapes
+}
+
+/// what is drawn with a shape beyond [`Shape`] itself
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ShapeExtra {
+	/// `None` for the shader set by [`Ui::change_current_shader`]
+	pub shader: Option<String>,
+	/// see [`Ui::paint_with_effects`]
+	pub effects: Vec<Effect>,
+	/// multiplied with images, `None` for white. see [`Ui::paint_image`]
+	pub tint: Option<Color>,
+	/// part of the texture an image shows, normalized. `None` for the whole texture
+	pub uv: Option<Area>,
+	/// a rect shape standing for [`crate::widgets::PaintCallback`] of this id, the shape itself is not drawn
+	pub paint_callback: Option<String>,
+	/// around text shapes, see [`crate::widgets::Text::outline`]
+	pub outline: Option<TextOutline>,
+	/// under text shapes, see [`crate::widgets::Text::shadow`]
+	pub text_shadow: Option<TextShadow>,
+	/// glyphs of a text shape laid out by [`Ui`], filled for every text shape when a frame ends
+	pub text_row: Option<Arc<TextRow>>,
+	/// font family of a text shape given by [`crate::font::family_id`], build-in font by default
+	pub font_family: u32,
+}
+
+impl ShapeExtra {
+	/// take what `text` has for text shapes, see [`crate::widgets::Text`]
+	pub(crate) fn set_text_extra(&mut self, text: ShapeExtra) {
+		self.outline = text.outline;
+		self.text_shadow = text.text_shadow;
+		self.text_row = text.text_row;
+		self.font_family = text.font_family;
+	}
+}
+
+/// how vertexs of a [`ParsedShape::Vertexs`] are colored, positions are in pixel from left top of the window
+#[cfg(feature = "vertexs")]
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum Paint {
+	/// color of vertexs
+	#[default]
+	Color,
+	/// multiplied with color of vertexs, stops are sorted by offset
+	LinearGradient {
+		start: Vec2,
+		end: Vec2,
+		stops: Vec<(f32, Color)>,
+	},
+	/// multiplied with color of vertexs, stops are sorted by offset
+	RadialGradient {
+		center: Vec2,
+		radius: f32,
+		stops: Vec<(f32, Color)>,
+	},
+	/// what is drawn behind blurred by `blur`, alpha from vertexs
+	Backdrop {
+		blur: f32,
+	},
+}
+
+/// what the renderer did in a frame, see [`crate::Ui::render_stats`]
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct RenderStats {
+	pub draw_calls: usize,
+	pub vertices: usize,
+	/// bytes of vertexs, indices and uniforms written to gpu
+	pub upload_bytes: usize,
+}
+
+impl std::ops::AddAssign for RenderStats {
+	fn add_assign(&mut self, other: Self) {
+		self.draw_calls += other.draw_calls;
+		self.vertices += other.vertices;
+		self.upload_bytes += other.upload_bytes;
+	}
 }
 
 /// helper of integrating
 #[derive(Default)]
 pub struct Integrator {
 	/// you may want using this when dealing with some events.
-	pub ui: Ui
+	pub ui: Ui,
+	/// shapes of last frame, for finding out what changed
+	last_shapes: Vec<Shape>,
+	/// extras of `last_shapes`
+	last_extras: Vec<ShapeExtra>,
+	/// window area of last frame, `None` to repaint the whole window next frame
+	last_window: Option<Area>,
+	/// last frame asked for next one by [`Ui::request_repaint_after`]
+	repaint_requested: bool,
 }
 
 /// after running ui code, here's things you should handle
@@ -63,8 +236,20 @@ pub struct Output<S> {
 	pub background_color: Color,
 	/// shapes you should draw. type of this value depends on what function you call. see more in [`Integrator`]
 	pub shapes: S,
+	/// what [`Shape`]s of [`Integrator::frame`] need besides themselves, such as tints, shaders and glyphs of texts. parallel to `shapes`.
+	/// parsed shapes of `Integrator::frame_vertexs` have them applied already, there these are of the shapes before parsing
+	pub extras: Vec<ShapeExtra>,
 	/// the events you should handle, such as creating a texture
-	pub output_events: Vec<OutputEvent>
+	pub output_events: Vec<OutputEvent>,
+	/// false if this frame looks the same as last one and no repaint was requested, you can skip drawing it and wait for next input.
+	pub is_changed: bool,
+	/// run next frame after this even if there's no input, see [`Ui::request_repaint_after`]
+	pub repaint_after: Option<Duration>,
+	/// fonts glyphs of [`ShapeExtra::text_row`] come from, same as [`Ui::font_book`]
+	pub fonts: SharedFontBook,
+	/// [`crate::widgets::PaintCallback`]s shown this frame by id, hand them to the renderer before drawing `shapes`
+	#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+	pub paint_callbacks: HashMap<String, PaintCallback>,
 }
 
 impl Integrator {
@@ -74,13 +259,26 @@ impl Integrator {
 			self.ui.event(&event)
 		}
 		self.ui.update();
-		ui_code(&mut self.ui);
+		self.ui.take_loaded_textures();
+		self.ui.update_animated_textures();
+		let fonts = self.ui.font_book();
+		with_font_book(&fonts, || ui_code(&mut self.ui));
 		self.ui.raw_shape();
+		self.ui.remove_unused_offscreens();
+		let is_damaged = self.is_damaged();
 		let output = Output {
 			background_color: self.ui.style().background_color,
 			shapes: self.ui.shape.raw_shape.clone(),
+			extras: self.ui.shape.extras.clone(),
 			output_events: self.ui.output_events.clone(),
+			is_changed: is_damaged || !self.ui.output_events.is_empty() || self.repaint_requested,
+			repaint_after: self.ui.repaint_after,
+			fonts,
+			#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+			paint_callbacks: std::mem::take(&mut *self.ui.paint_callbacks.lock().unwrap()),
 		};
+		self.repaint_requested = self.ui.repaint_after.is_some();
+		self.keep_shapes(is_damaged);
 		self.ui.clear();
 		output
 	}
@@ -92,13 +290,32 @@ impl Integrator {
 			self.ui.event(&event)
 		}
 		self.ui.update();
-		ui_code(&mut self.ui);
+		self.ui.take_loaded_textures();
+		self.ui.update_animated_textures();
+		let fonts = self.ui.font_book();
+		with_font_book(&fonts, || ui_code(&mut self.ui));
+		self.ui.raw_shape();
+		self.ui.remove_unused_offscreens();
+		let is_damaged = self.is_damaged();
+		if is_damaged {
+			// parsing takes shapes away
+			self.last_shapes = self.ui.shape.raw_shape.clone();
+			self.last_extras = self.ui.shape.extras.clone();
+		}
+		let extras = self.ui.shape.extras.clone();
 		self.ui.handle_raw_shape();
 		let output = Output {
 			background_color: self.ui.style().background_color,
 			shapes: self.ui.shape.parsed_shapes.clone(),
+			extras,
 			output_events: self.ui.output_events.clone(),
+			is_changed: is_damaged || !self.ui.output_events.is_empty() || self.repaint_requested,
+			repaint_after: self.ui.repaint_after,
+			fonts,
+			#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+			paint_callbacks: std::mem::take(&mut *self.ui.paint_callbacks.lock().unwrap()),
 		};
+		self.repaint_requested = self.ui.repaint_after.is_some();
 		self.ui.clear();
 		output
 	}
@@ -107,4 +324,79 @@ impl Integrator {
 	pub fn event(&mut self, input_event: &Event) {
 		self.ui.event(input_event)
 	}
-}
\ No newline at end of file
+
+	/// next frame will be reported as changed, use this when what you drawn is lost, such as a lost surface
+	pub fn request_full_repaint(&mut self) {
+		self.last_window = None;
+	}
+
+	/// compare sorted shapes with last frame, returns true if any of them changed. shapes are kept by [`Self::keep_shapes`]
+	fn is_damaged(&mut self) -> bool {
+		let window = self.ui.window_area();
+		self.last_window.replace(window) != Some(window) || self.ui.shape.raw_shape != self.last_shapes || self.ui.shape.extras != self.last_extras
+	}
+
+	/// keep shapes of this frame for next [`Self::is_damaged`], nothing to do if they did not change
+	fn keep_shapes(&mut self, is_damaged: bool) {
+		if is_damaged {
+			// shapes of the ui are cleared after this anyway
+			std::mem::swap(&mut self.last_shapes, &mut self.ui.shape.raw_shape);
+			std::mem::swap(&mut self.last_extras, &mut self.ui.shape.extras);
+		}
+	}
+
+	/// `waker` is called from another thread when a texture loaded by [`Ui::load_texture`] finished decoding,
+	/// use it to wake your event loop up. without it [`Ui`] polls every 50ms while loading
+	pub fn set_texture_waker(&mut self, waker: impl Fn() + Send + 'static) {
+		self.ui.texture_loader.lock().unwrap().set_waker(Box::new(waker));
+	}
+
+	/// tell [`Ui`] what the renderer did for last frame, you can get it by [`Ui::render_stats`]
+	pub fn report_render_stats(&mut self, stats: RenderStats) {
+		self.ui.render_stats = stats;
+	}
+}
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::animated_texture::AnimatedTexture;
+	use crate::texture::TextureOptions;
+
+	#[test]
+	fn animations_move_on_once_a_frame() {
+		let mut integrator = Integrator::default();
+		for id in ["inside", "outside"] {
+			integrator.ui.texture_id.lock().unwrap().insert(id.to_string(), (Vec2::same(1.0), TextureOptions::default()));
+			integrator.ui.animated_textures.lock().unwrap().insert(id.to_string(), AnimatedTexture::from_delays(&[1.0, 1.0, 1.0]));
+		}
+		let ui_code = |ui: &mut Ui| {
+			ui.offscreen("screen", Vec2::same(8.0), |ui| {
+				ui.add(crate::widgets::Image::new("inside", Vec2::same(8.0)));
+			});
+			ui.add(crate::widgets::Image::new("outside", Vec2::same(8.0)));
+		};
+		integrator.frame(vec!(), ui_code);
+		for animation in integrator.ui.animated_textures.lock().unwrap().values_mut() {
+			animation.pass_time(1.5);
+		}
+		let output = integrator.frame(vec!(), ui_code);
+		for id in ["inside", "outside"] {
+			assert_eq!(integrator.ui.animated_textures.lock().unwrap()[id].frame(), 1, "{id}");
+			let changes = output.output_events.iter().filter(|event| matches!(event, OutputEvent::TextureChange(image) if image.id == id)).count();
+			assert_eq!(changes, 1, "{id}");
+		}
+		// the offscreen showing a changed animation is drawn again
+		assert!(output.output_events.iter().any(|event| matches!(event, OutputEvent::Offscreen(offscreen) if offscreen.id == "screen")));
+	}
+
+	#[test]
+	fn same_frames_are_not_changed() {
+		let mut integrator = Integrator::default();
+		assert!(integrator.frame(vec!(), |ui| { ui.label("a"); }).is_changed);
+		assert!(!integrator.frame(vec!(), |ui| { ui.label("a"); }).is_changed);
+		assert!(integrator.frame(vec!(), |ui| { ui.label("b"); }).is_changed);
+		assert!(!integrator.frame(vec!(), |ui| { ui.label("b"); }).is_changed);
+		integrator.request_full_repaint();
+		assert!(integrator.frame(vec!(), |ui| { ui.label("b"); }).is_changed);
+	}
+}
@@ -44,12 +44,16 @@ cfg_if::cfg_if! {
 	if #[cfg(feature = "manager")] {
 		mod manager;
 		mod state;
+		mod glyph_cache;
+		mod texture_atlas;
 		use clipboard::ClipboardContext;
 		use crate::integrator::Integrator;
 		use winit::event_loop::ControlFlow;
 	}else if #[cfg(feature = "baseview_manager")] {
 		mod baseview_manager;
 		mod state;
+		mod glyph_cache;
+		mod texture_atlas;
 		use clipboard::ClipboardContext;
 		use crate::integrator::Integrator;
 	}
@@ -57,13 +61,19 @@ cfg_if::cfg_if! {
 
 cfg_if::cfg_if! {
 	if #[cfg(feature = "vertexs")] {
+		mod feather;
+		mod effect;
 		use crate::integrator::ParsedShape;
 	}
 }
 
 mod ui;
+mod texture_loader;
+mod animated_texture;
 mod response;
 pub mod texture;
+pub mod font;
+pub mod text_layout;
 pub mod event;
 pub mod widgets;
 pub mod container;
@@ -75,6 +85,15 @@ pub mod presets;
 
 use crate::event::Touch;
 use crate::event::OutputEvent;
+use crate::integrator::RenderStats;
+use crate::texture_loader::TextureLoader;
+use crate::texture::TextureOptions;
+use crate::animated_texture::AnimatedTexture;
+use crate::integrator::ShapeExtra;
+use crate::font::SharedFontBook;
+use crate::text_layout::TextRow;
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+use crate::widgets::PaintCallback;
 use std::ops::Sub;
 use time::Duration;
 use nablo_shape::shape::shape_elements::Layer;
@@ -85,16 +104,25 @@ use crate::event::MouseButton;
 use crate::event::Key;
 use crate::event::Event;
 use nablo_shape::shape::Shape;
+use nablo_shape::shape::ShapeElement;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use nablo_shape::math::Area;
 use nablo_shape::math::Vec2;
 use time::OffsetDateTime;
 use std::sync::Mutex;
 use std::sync::Arc;
+use rayon::prelude::*;
 
 /// will replace when typing in a input setted `is_password = true`
 pub const PASSWORD: char = '●';
 
+/// how many textures can be bound to a shader by [`Ui::set_shader_textures`]
+pub const MAX_SHADER_TEXTURES: usize = 4;
+
+/// how many stops of a [`crate::widgets::Gradient`] are drawn
+pub const MAX_GRADIENT_STOPS: usize = 4;
+
 #[derive(Clone, serde::Serialize, serde::Deserialize, Copy, Debug)]
 pub(crate) struct Instant {
 	offset: OffsetDateTime
@@ -133,6 +161,14 @@ cfg_if::cfg_if!{
 			pub fullscreen: bool,
 			pub icon: Option<(Vec<u8>,Vec2)>,
 			pub control_flow: ControlFlow,
+			/// how glyphs of texts are rasterized
+			pub text_rendering: font::TextRendering,
+			/// if true, frames looking the same as last one are not drawn, and nablo waits for input or [`Ui::request_repaint_after`] after them
+			pub skip_idle_frames: bool,
+			/// samples per pixel of msaa, usually 1 or 4. 1 sample is used if your device does not support it
+			pub msaa_samples: u32,
+			/// see [`Ui::set_feather`]
+			pub feather: f32,
 			// pub soft_rendering: bool,
 		}
 
@@ -175,6 +211,12 @@ cfg_if::cfg_if!{
 			/// how large is our window? if window is resizeable, this would be min size
 			pub size: Vec2,
 			pub title: String,
+			/// how glyphs of texts are rasterized
+			pub text_rendering: font::TextRendering,
+			/// samples per pixel of msaa, usually 1 or 4. 1 sample is used if your device does not support it
+			pub msaa_samples: u32,
+			/// see [`Ui::set_feather`]
+			pub feather: f32,
 		}
 
 		/// a trait for your app
@@ -206,7 +248,9 @@ cfg_if::cfg_if!{
 			integrator: Integrator,
 			/// your app
 			pub app: T,
-			state: State
+			state: State,
+			/// time since start, given to shaders
+			timer: Instant,
 		}
 	}
 }
@@ -218,26 +262,97 @@ pub(crate) struct MemoryTemp {
 	pub access_time: usize
 }
 
+/// ui drawn into a texture, see [`Ui::offscreen`]
+#[derive(Default)]
+pub(crate) struct OffscreenTemp {
+	pub ui: Ui,
+	/// shapes, extras and size last sent to the renderer
+	pub shapes: Vec<Shape>,
+	pub extras: Vec<ShapeExtra>,
+	pub size: Option<Vec2>,
+	/// shown this frame
+	pub is_used: bool,
+}
+
 #[derive(Default)]
 pub(crate) struct Shapes {
 	pub raw_shape: Vec<Shape>,
+	/// shader and effects of each shape in `raw_shape`
+	pub extras: Vec<ShapeExtra>,
+	/// shader of shapes appended, see [`Ui::with_shader`]
+	pub shader: Option<String>,
+	/// texts and extras of text shapes drawn by [`crate::widgets::Text`] but not appended yet, in the order they are drawn.
+	/// texts keep them whatever happens to their painters in between, see [`Self::append_painter`]
+	pub text_extras: Vec<(String, ShapeExtra)>,
 	#[cfg(feature = "vertexs")]
 	pub parsed_shapes: Vec<ParsedShape>
 }
 
 impl Shapes {
 	pub fn append(&mut self, shape: impl Into<Vec<Shape>>) {
-		self.raw_shape.append(&mut shape.into())
+		self.append_with_extras(shape, vec!())
+	}
+
+	/// `extras` are for the first shapes, rest of them use current shader without effects
+	pub fn append_with_extras(&mut self, shape: impl Into<Vec<Shape>>, mut extras: Vec<ShapeExtra>) {
+		let mut shape = shape.into();
+		extras.resize(shape.len(), ShapeExtra {
+			shader: self.shader.clone(),
+			..Default::default()
+		});
+		self.raw_shape.append(&mut shape);
+		self.extras.append(&mut extras);
+	}
+
+	/// append a painter drawn by a widget or container, same as [`Self::append_with_extras`].
+	/// text shapes drawn after the first `extras.len()` shapes take [`Self::text_extras`] pushed since it had `text_extras` items, in order.
+	/// text shapes not drawn by [`crate::widgets::Text`] are skipped by their texts, extras left are thrown away
+	pub fn append_painter(&mut self, painter: Painter, extras: Vec<ShapeExtra>, text_extras: usize) {
+		let start = self.raw_shape.len() + extras.len();
+		self.append_with_extras(painter, extras);
+		let mut pending = self.text_extras.drain(text_extras.min(self.text_extras.len())..).peekable();
+		for (shape, extra) in self.raw_shape[start.min(self.raw_shape.len())..].iter().zip(self.extras[start.min(self.extras.len())..].iter_mut()) {
+			let ShapeElement::Text(text) = &shape.shape else {
+				continue;
+			};
+			if pending.peek().is_some_and(|(pending, _)| *pending == text.text) {
+				extra.set_text_extra(pending.next().unwrap().1);
+			}
+		}
+	}
+
+	/// move all shapes of `other` into `self`, keeping their extras
+	pub fn append_shapes(&mut self, other: &mut Shapes) {
+		self.raw_shape.append(&mut other.raw_shape);
+		self.extras.append(&mut other.extras);
+	}
+
+	/// shapes and their extras after `at`
+	pub fn split_off(&mut self, at: usize) -> (Vec<Shape>, Vec<ShapeExtra>) {
+		(self.raw_shape.split_off(at), self.extras.split_off(at))
+	}
+
+	/// sort shapes by layer, extras go with their shapes
+	pub fn sort(&mut self) {
+		let mut shapes: Vec<(Shape, ShapeExtra)> = self.raw_shape.drain(..).zip(self.extras.drain(..)).collect();
+		shapes.par_sort_by(|a, b| a.0.style.layer.cmp(&b.0.style.layer));
+		(self.raw_shape, self.extras) = shapes.into_iter().unzip();
 	}
 
 	#[cfg(not(feature = "vertexs"))]
 	pub fn clear(&mut self) {
 		self.raw_shape.clear();
+		self.extras.clear();
+		self.shader = None;
+		self.text_extras.clear();
 	}
 
 	#[cfg(feature = "vertexs")]
 	pub fn clear(&mut self) {
 		self.raw_shape.clear();
+		self.extras.clear();
+		self.shader = None;
+		self.text_extras.clear();
 		self.parsed_shapes.clear();
 	}
 }
@@ -259,13 +374,31 @@ pub struct Ui {
 	paint_style: PaintStyle,
 	layout: Layout,
 	output_events: Vec<OutputEvent>,
-	texture_id: Arc<Mutex<Vec<String>>>,
+	/// size and options of textures added
+	texture_id: Arc<Mutex<HashMap<String, (Vec2, TextureOptions)>>>,
+	texture_loader: Arc<Mutex<TextureLoader>>,
+	animated_textures: Arc<Mutex<HashMap<String, AnimatedTexture>>>,
+	/// ids of images drawn last frame including offscreens, only these animated textures move on. see [`Ui::update_animated_textures`]
+	shown_images: Arc<Mutex<HashSet<String>>>,
+	/// ids of animated textures and offscreens changed this frame, offscreens showing them are drawn again
+	changed_textures: Arc<Mutex<HashSet<String>>>,
 	offset: Vec2,
 	parent_area: Option<Area>,
 	start_position: Vec2,
 	window_crossed: Area,
 	// scale_factor: f32,
-	collapse_times: usize
+	collapse_times: usize,
+	render_stats: RenderStats,
+	repaint_after: Option<Duration>,
+	feather: f32,
+	offscreens: Arc<Mutex<HashMap<String, OffscreenTemp>>>,
+	/// see [`Ui::register_font`]
+	fonts: SharedFontBook,
+	/// rows of text shapes not drawn by [`crate::widgets::Text`] by text, family, size and font generation, see [`Ui::raw_shape`]
+	text_rows: HashMap<(String, u32, [u32; 2], u64), Arc<TextRow>>,
+	/// shown this frame, by id
+	#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+	paint_callbacks: Arc<Mutex<HashMap<String, PaintCallback>>>,
 }
 
 #[derive(Default, Clone)]
@@ -277,6 +410,8 @@ pub struct Layout {
 	is_inverse: bool,
 	/// if true, this will put the content horizentally.
 	is_horizental: bool,
+	/// if true, horizental content will be put right to left when [`Ui::language`] is written right to left.
+	mirror_for_rtl: bool,
 	// /// if true, [`Ui`] will put the content in central position.
 	// is_centered: bool
 	// /// this will affect the position [`Ui`] put.
@@ -452,4 +587,66 @@ pub(crate) fn to_json<T: serde::Serialize>(input: &T) -> String {
 			String::new()
 		}
 	}
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::widgets::TextOutline;
+	use nablo_shape::prelude::shape_elements::Color;
+
+	#[test]
+	fn text_extras_follow_texts() {
+		let mut shapes = Shapes::default();
+		let outline = Some(TextOutline { width: 1.0, color: Color::from(1.0) });
+		shapes.text_extras.push(("b".to_string(), ShapeExtra { outline, font_family: 7, ..Default::default() }));
+		let mut painter = Painter::default();
+		painter.text("a".to_string());
+		painter.rect(Vec2::same(4.0), Vec2::ZERO);
+		painter.text("b".to_string());
+		// changing the painter after drawing does not lose them
+		painter.brighter(0.1);
+		shapes.append_painter(painter, vec!(), 0);
+		assert_eq!(shapes.extras.iter().map(|extra| extra.outline).collect::<Vec<_>>(), vec!(None, None, outline));
+		assert_eq!(shapes.extras.iter().map(|extra| extra.font_family).collect::<Vec<_>>(), vec!(0, 0, 7));
+		assert!(shapes.text_extras.is_empty());
+	}
+
+	#[test]
+	fn same_texts_take_their_own_extras() {
+		let mut shapes = Shapes::default();
+		let first = ShapeExtra { font_family: 1, ..Default::default() };
+		let second = ShapeExtra { font_family: 2, ..Default::default() };
+		shapes.text_extras.push(("a".to_string(), first));
+		shapes.text_extras.push(("a".to_string(), second));
+		let mut painter = Painter::default();
+		painter.text("a".to_string());
+		painter.text("a".to_string());
+		shapes.append_painter(painter, vec!(), 0);
+		assert_eq!(shapes.extras.iter().map(|extra| extra.font_family).collect::<Vec<_>>(), vec!(1, 2));
+	}
+
+	#[test]
+	fn text_extras_stay_in_their_painter() {
+		let mut shapes = Shapes::default();
+		// pushed before this painter, not for it
+		shapes.text_extras.push(("a".to_string(), ShapeExtra { font_family: 1, ..Default::default() }));
+		let start = shapes.text_extras.len();
+		shapes.text_extras.push(("a".to_string(), ShapeExtra { font_family: 2, ..Default::default() }));
+		// pushed for a text never drawn
+		shapes.text_extras.push(("b".to_string(), ShapeExtra { font_family: 3, ..Default::default() }));
+		let mut painter = Painter::default();
+		// not drawn by a text widget
+		painter.text("c".to_string());
+		painter.text("a".to_string());
+		shapes.append_painter(painter, vec!(), start);
+		assert_eq!(shapes.extras.iter().map(|extra| extra.font_family).collect::<Vec<_>>(), vec!(0, 2));
+		assert_eq!(shapes.text_extras.len(), start);
+		// shapes appended before keep their extras, such as ones in a container
+		shapes.text_extras.push(("a".to_string(), ShapeExtra { font_family: 4, ..Default::default() }));
+		let (split, extras) = shapes.split_off(0);
+		let mut end = Painter::new(&Area::ZERO, split, PaintStyle::default());
+		end.text("a".to_string());
+		shapes.append_painter(end, extras, start);
+		assert_eq!(shapes.extras.iter().map(|extra| extra.font_family).collect::<Vec<_>>(), vec!(0, 2, 4));
+	}
+}
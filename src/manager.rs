@@ -2,6 +2,7 @@ use crate::Instant;
 use winit::event_loop::ControlFlow;
 use nablo_shape::prelude::Vec2;
 use crate::state::State;
+use crate::state::shader_event;
 use crate::Key;
 use clipboard::ClipboardProvider;
 use crate::OutputEvent;
@@ -10,6 +11,7 @@ use crate::Integrator;
 use crate::Manager;
 use crate::App;
 use crate::Settings;
+use crate::font::TextRendering;
 use winit::event::Event;
 use crate::event::Event as NabloEvent;
 use winit::window::Icon;
@@ -18,6 +20,7 @@ use winit::dpi::LogicalSize;
 use winit::event_loop::EventLoop;
 use winit::window::WindowBuilder;
 use winit::event::WindowEvent;
+use winit::event::StartCause;
 use anyhow::*;
 
 #[cfg(target_os = "android")]
@@ -34,9 +37,10 @@ impl<T: App + 'static> Manager<T> {
 		self.run_app(Some(frame))
 	}
 
-	fn handle_event_loop<E>(mut self, frame: Option<usize>, event_loop: EventLoop<E>) -> Result<()> {
+	fn handle_event_loop(mut self, frame: Option<usize>, event_loop: EventLoop<()>) -> Result<()> {
 		#[cfg(target_os = "android")]
 		self.app.android_app(self.android_app.clone());
+		self.integrator.ui.set_feather(self.settings.feather);
 		self.app.on_open(&mut self.integrator.ui);
 
 		// TODO: make this changable
@@ -90,7 +94,7 @@ impl<T: App + 'static> Manager<T> {
 						};
 					}).expect("cant run");
 
-				let mut state = State::new(&window, Vec2::new(window.inner_size().width as f32, window.inner_size().height as f32));
+				let mut state = State::new(&window, Vec2::new(window.inner_size().width as f32, window.inner_size().height as f32), self.settings.text_rendering, self.settings.msaa_samples);
 				
 				event_loop.run(move |winit_event, elwt| {
 					match winit_event {
@@ -119,16 +123,28 @@ impl<T: App + 'static> Manager<T> {
 								match event {
 									WindowEvent::RedrawRequested => {
 										let output = self.integrator.frame_vertexs(vec!(), |ui| self.app.app(ui));
+										state.set_paint_callbacks(output.paint_callbacks.clone());
+										state.set_fonts(output.fonts.clone());
 										for event in &output.output_events {
 											self.handle_event(event.clone(), &mut state)
 										}
-										let cursor_position = self.integrator.ui.input().cursor_position().unwrap_or(Vec2::ZERO) / self.integrator.ui.window_area().width_and_height() * Vec2::same(2.0) - Vec2::same(1.0);
-										match state.render(output, cursor_position, self.timer.elapsed().as_seconds_f32()) {
-											Ok(_) => {}
-											Err(wgpu::SurfaceError::Lost) => state.resize(state.size),
-											Err(wgpu::SurfaceError::OutOfMemory) => elwt.exit(),
-											Err(e) => eprintln!("{:?}", e),
-										};
+										for (id, result) in state.poll_shaders() {
+											self.integrator.event(&shader_event(id, result));
+										}
+										let is_changed = output.is_changed || !self.settings.skip_idle_frames;
+										let repaint_after = output.repaint_after;
+										if is_changed {
+											let cursor_position = self.integrator.ui.input().cursor_position().unwrap_or(Vec2::ZERO) / self.integrator.ui.window_area().width_and_height() * Vec2::same(2.0) - Vec2::same(1.0);
+											match state.render(output, cursor_position, self.timer.elapsed().as_seconds_f32()) {
+												Ok(stats) => self.integrator.report_render_stats(stats),
+												Err(wgpu::SurfaceError::Lost) => {
+													state.resize(state.size);
+													self.integrator.request_full_repaint();
+												},
+												Err(wgpu::SurfaceError::OutOfMemory) => elwt.exit(),
+												Err(e) => eprintln!("{:?}", e),
+											};
+										}
 										
 										if let Some(inner) = frame {
 											if id > inner {
@@ -136,16 +152,28 @@ impl<T: App + 'static> Manager<T> {
 											}
 										}
 										id = id + 1;
-										window.request_redraw();
+										// an idle frame waits for input or `repaint_after`, so the browser stops calling us back every frame.
+										// things may still be moving after a changed frame, so run one more. so do shaders still checked by the device
+										if is_changed || state.has_pending_shaders() {
+											elwt.set_control_flow(self.settings.control_flow);
+											window.request_redraw();
+										}else if let Some(after) = repaint_after {
+											elwt.set_control_flow(ControlFlow::wait_duration(std::time::Duration::try_from(after).unwrap_or_default()));
+										}else {
+											elwt.set_control_flow(ControlFlow::Wait);
+										}
 									},
 									WindowEvent::CloseRequested => { elwt.exit() },
 									WindowEvent::Resized(physical_size) => {
-										state.resize(Vec2::new(physical_size.width as f32, physical_size.height as f32))
+										state.resize(Vec2::new(physical_size.width as f32, physical_size.height as f32));
+										window.request_redraw();
 									},
-									_ => {}
+									_ => window.request_redraw(),
 								}
 							}
 						},
+						// time for `repaint_after`
+						Event::NewEvents(StartCause::ResumeTimeReached { .. }) => window.request_redraw(),
 						_ => {}
 					}
 				}).unwrap();
@@ -154,8 +182,19 @@ impl<T: App + 'static> Manager<T> {
 			}else {
 				let mut window: Option<winit::window::Window> = None;
 				let mut state: Option<State> = None;
+				// nothing changed in last frame, wait for input or `repaint_at`
+				let mut is_idle = false;
+				let mut repaint_at: Option<std::time::Instant> = None;
+				let proxy = event_loop.create_proxy();
+				self.integrator.set_texture_waker(move || {
+					let _ = proxy.send_event(());
+				});
 				event_loop.run(move |winit_event, elwt, control_flow| {
-					*control_flow = self.settings.control_flow;
+					*control_flow = match (is_idle, repaint_at) {
+						(false, _) => self.settings.control_flow,
+						(true, Some(repaint_at)) => ControlFlow::WaitUntil(repaint_at),
+						(true, None) => ControlFlow::Wait,
+					};
 					if self.need_close {
 						self.app.on_close(&mut self.integrator.ui);
 						*control_flow = ControlFlow::Exit;
@@ -181,7 +220,9 @@ impl<T: App + 'static> Manager<T> {
 								w_bind.set_window_icon(Some(Icon::from_rgba(color.clone(), size.x as u32, size.y as u32).unwrap()))
 							}
 							window = Some(w_bind);
-							state = Some(State::new(window.as_ref().unwrap(), Vec2::new(window.as_ref().unwrap().inner_size().width as f32, window.as_ref().unwrap().inner_size().height as f32)));
+							is_idle = false;
+							self.integrator.request_full_repaint();
+							state = Some(State::new(window.as_ref().unwrap(), Vec2::new(window.as_ref().unwrap().inner_size().width as f32, window.as_ref().unwrap().inner_size().height as f32), self.settings.text_rendering, self.settings.msaa_samples));
 						},
 						Event::Suspended => {
 							window = None;
@@ -219,20 +260,43 @@ impl<T: App + 'static> Manager<T> {
 								_ => {}
 							}
 							self.integrator.event(&event.into());
+							is_idle = false;
+							if let Some(t) = &window {
+								t.request_redraw();
+							}
+						},
+						// time for `repaint_at`, or a texture finished loading
+						Event::NewEvents(StartCause::ResumeTimeReached { .. }) | Event::UserEvent(()) => {
+							is_idle = false;
+							if let Some(t) = &window {
+								t.request_redraw();
+							}
 						},
 						Event::RedrawRequested(_) => {
 							if let Some(state) = &mut state {
 								let output = self.integrator.frame_vertexs(vec!(), |ui| self.app.app(ui));
+								state.set_paint_callbacks(output.paint_callbacks.clone());
+								state.set_fonts(output.fonts.clone());
 								for event in &output.output_events {
 									self.handle_event(event.clone(), state)
 								}
-								let cursor_position = self.integrator.ui.input().cursor_position().unwrap_or(Vec2::ZERO) / self.integrator.ui.window_area().width_and_height() * Vec2::same(2.0) - Vec2::same(1.0);
-								match state.render(output, cursor_position, self.timer.elapsed().as_seconds_f32()) {
-									Ok(_) => {}
-									Err(wgpu::SurfaceError::Lost) => state.resize(state.size),
-									Err(wgpu::SurfaceError::OutOfMemory) => *control_flow = ControlFlow::Exit,
-									Err(e) => eprintln!("{:?}", e),
-								};
+								for (id, result) in state.poll_shaders() {
+									self.integrator.event(&shader_event(id, result));
+								}
+								let is_changed = output.is_changed || !self.settings.skip_idle_frames;
+								repaint_at = output.repaint_after.map(|after| std::time::Instant::now() + std::time::Duration::try_from(after).unwrap_or_default());
+								if is_changed {
+									let cursor_position = self.integrator.ui.input().cursor_position().unwrap_or(Vec2::ZERO) / self.integrator.ui.window_area().width_and_height() * Vec2::same(2.0) - Vec2::same(1.0);
+									match state.render(output, cursor_position, self.timer.elapsed().as_seconds_f32()) {
+										Ok(stats) => self.integrator.report_render_stats(stats),
+										Err(wgpu::SurfaceError::Lost) => {
+											state.resize(state.size);
+											self.integrator.request_full_repaint();
+										},
+										Err(wgpu::SurfaceError::OutOfMemory) => *control_flow = ControlFlow::Exit,
+										Err(e) => eprintln!("{:?}", e),
+									};
+								}
 								
 								if let Some(inner) = frame {
 									if id > inner {
@@ -240,14 +304,20 @@ impl<T: App + 'static> Manager<T> {
 									}
 								}
 								id += 1;
+								// things may still be moving after a changed frame, so run one more. so do shaders still checked by the device
+								is_idle = !is_changed && !state.has_pending_shaders();
 								if let Some(t) = &window {
-									t.request_redraw();
+									if !is_idle {
+										t.request_redraw();
+									}
 								}
 							}
 						},
 						Event::RedrawEventsCleared => {
 							if let Some(t) = &window {
-								t.request_redraw();
+								if !is_idle {
+									t.request_redraw();
+								}
 							}
 						},
 						_ => {}
@@ -333,14 +403,27 @@ impl<T: App + 'static> Manager<T> {
 	fn handle_event(&mut self, event: OutputEvent, state: &mut State) {
 		match event {
 			OutputEvent::TextureCreate(texture) => {
-				state.insert_texture(texture.id.clone(), texture);
+				if let Err(e) = state.insert_texture(texture.id.clone(), texture) {
+					#[cfg(feature = "info")]
+					println!("uploading texture failed, info: {}", e);
+					#[cfg(feature = "log")]
+					log::error!("uploading texture failed, info: {}", e);
+				}
 			},
 			OutputEvent::TextureChange(texture) => {
-				state.insert_texture(texture.id.clone(), texture);
+				if let Err(e) = state.insert_texture(texture.id.clone(), texture) {
+					#[cfg(feature = "info")]
+					println!("uploading texture failed, info: {}", e);
+					#[cfg(feature = "log")]
+					log::error!("uploading texture failed, info: {}", e);
+				}
 			},
 			OutputEvent::TextureDelete(id) => {
 				state.remove_texture(&id);
 			},
+			OutputEvent::Offscreen(offscreen) => {
+				state.render_offscreen(offscreen, Vec2::ZERO, self.timer.elapsed().as_seconds_f32());
+			},
 			OutputEvent::ClipboardCopy(text) => {
 				if let Some(clipboard) = &mut self.clipboard {
 					let data = clipboard.set_contents(text);
@@ -353,10 +436,20 @@ impl<T: App + 'static> Manager<T> {
 				}
 			},
 			OutputEvent::RequireSoftKeyboard(_) => {},
+			OutputEvent::OpenUrl(url) => open_url(&url),
+			// glyph cache finds it out by font generation
+			OutputEvent::FontChange => {},
 			OutputEvent::Close => self.need_close = true,
 			OutputEvent::ChangeShader(id) => state.change_shader(id),
 			OutputEvent::RemoveShader(id) => state.remove_shader(id),
-			OutputEvent::RegstrateShader(id, code) => state.registrate_shader(id, code),
+			OutputEvent::ShaderUniform(id, bytes) => state.set_shader_uniform(id, bytes),
+			OutputEvent::ShaderTextures(id, textures) => state.set_shader_textures(id, textures),
+			OutputEvent::RegstrateShader(id, code) => {
+				// working ones are told by `poll_shaders` later
+				if let Err(diagnostics) = state.registrate_shader(id.clone(), code) {
+					self.integrator.event(&shader_event(id, Err(diagnostics)));
+				}
+			},
 		}
 	}
 
@@ -364,14 +457,27 @@ impl<T: App + 'static> Manager<T> {
 	fn handle_event(&mut self, event: OutputEvent, state: &mut State) {
 		match event {
 			OutputEvent::TextureCreate(texture) => {
-				state.insert_texture(texture.id.clone(), texture);
+				if let Err(e) = state.insert_texture(texture.id.clone(), texture) {
+					#[cfg(feature = "info")]
+					println!("uploading texture failed, info: {}", e);
+					#[cfg(feature = "log")]
+					log::error!("uploading texture failed, info: {}", e);
+				}
 			},
 			OutputEvent::TextureChange(texture) => {
-				state.insert_texture(texture.id.clone(), texture);
+				if let Err(e) = state.insert_texture(texture.id.clone(), texture) {
+					#[cfg(feature = "info")]
+					println!("uploading texture failed, info: {}", e);
+					#[cfg(feature = "log")]
+					log::error!("uploading texture failed, info: {}", e);
+				}
 			},
 			OutputEvent::TextureDelete(id) => {
 				state.remove_texture(&id);
 			},
+			OutputEvent::Offscreen(offscreen) => {
+				state.render_offscreen(offscreen, Vec2::ZERO, self.timer.elapsed().as_seconds_f32());
+			},
 			OutputEvent::ClipboardCopy(text) => {
 				if let Some(clipboard) = &mut self.clipboard {
 					let data = clipboard.set_contents(text);
@@ -390,6 +496,31 @@ impl<T: App + 'static> Manager<T> {
 					self.android_app.hide_soft_input(true);
 				}
 			},
+			OutputEvent::OpenUrl(url) => open_url(&url),
+			// glyph cache finds it out by font generation
+			OutputEvent::FontChange => {},
+		}
+	}
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn open_url(url: &str) {
+	if let Err(e) = webbrowser::open(url) {
+		#[cfg(feature = "info")]
+		println!("open url failed, info: {}", e);
+		#[cfg(feature = "log")]
+		log::error!("open url failed, info: {}", e);
+	}
+}
+
+#[cfg(target_arch = "wasm32")]
+fn open_url(url: &str) {
+	if let Some(window) = web_sys::window() {
+		if let Err(e) = window.open_with_url_and_target(url, "_blank") {
+			#[cfg(feature = "info")]
+			println!("open url failed, info: {:?}", e);
+			#[cfg(feature = "log")]
+			log::error!("open url failed, info: {:?}", e);
 		}
 	}
 }
@@ -404,6 +535,10 @@ impl Default for Settings {
 			fullscreen: false,
 			icon: None,
 			control_flow: ControlFlow::Poll,
+			text_rendering: TextRendering::default(),
+			skip_idle_frames: true,
+			msaa_samples: 1,
+			feather: 0.0,
 		}
 	}
 }
\ No newline at end of file
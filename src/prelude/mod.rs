@@ -7,6 +7,16 @@ pub use crate::widgets::*;
 pub use crate::presets::*;
 pub use crate::*;
 pub use crate::event::*;
+pub use crate::texture::TextureOptions;
+pub use crate::texture::FilterMode;
+pub use crate::texture::AddressMode;
+pub use crate::texture::TextureStatus;
+pub use crate::texture::Playback;
+pub use time::Date;
+pub use time::Time;
+pub use time::OffsetDateTime;
+pub use time::Month;
+pub use time::Weekday;
 
 #[cfg(feature = "nablo_data")]
 pub use nablo_data::*;
@@ -17,12 +17,15 @@ struct VertexInput {
 	@location(1) color: vec4f,
 	/// 0 = false, other = true
 	@location(2) is_texture: u32,
+	/// multiplied with the sampled color when drawing a texture
+	@location(3) tint: vec4f,
 }
 
 struct VertexOutput {
 	@builtin(position) clip_position: vec4f,
 	@location(0) color: vec4f,
 	@location(1) is_texture: u32,
+	@location(2) tint: vec4f,
 }
 
 @vertex
@@ -34,6 +37,7 @@ fn vs_main(
 	out.color = model.color;
 	out.clip_position = vec4f(model.position, 1.0);
 	out.is_texture = model.is_texture;
+	out.tint = model.tint;
 	return out;
 }
 
@@ -47,6 +51,41 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
 	if in.is_texture == 0u {
 		return in.color;
 	}else {
-		return textureSample(t_diffuse, s_diffuse, in.color.xy);
+		return textureSample(t_diffuse, s_diffuse, in.color.xy) * in.tint;
 	}
-}
\ No newline at end of file
+}
+
+/// a gradient of a batch, see `Paint` in `src/state.rs`
+struct Paint {
+	/// 1 = linear gradient, 2 = radial gradient
+	kind: u32,
+	/// of radial gradient
+	radius: f32,
+	/// in pixel, start and end of linear gradient, or center of radial gradient
+	points: vec4f,
+	stop_colors: array<vec4f, 4>,
+	stop_offsets: vec4f,
+}
+
+@group(2) @binding(0)
+var<uniform> paint: Paint;
+
+/// color at `t` of a gradient with 4 stops
+fn gradient(t: f32) -> vec4f {
+	let offsets = paint.stop_offsets;
+	var color = mix(paint.stop_colors[0], paint.stop_colors[1], clamp((t - offsets.x) / max(offsets.y - offsets.x, 0.00001), 0.0, 1.0));
+	color = mix(color, paint.stop_colors[2], clamp((t - offsets.y) / max(offsets.z - offsets.y, 0.00001), 0.0, 1.0));
+	color = mix(color, paint.stop_colors[3], clamp((t - offsets.z) / max(offsets.w - offsets.z, 0.00001), 0.0, 1.0));
+	return color;
+}
+
+/// shapes filled by a gradient, multiplied with their color
+@fragment
+fn fs_paint(in: VertexOutput) -> @location(0) vec4f {
+	let point = in.clip_position.xy;
+	if paint.kind == 2u {
+		return gradient(length(point - paint.points.xy) / max(paint.radius, 0.00001)) * in.color;
+	}
+	let direction = paint.points.zw - paint.points.xy;
+	return gradient(dot(point - paint.points.xy, direction) / max(dot(direction, direction), 0.00001)) * in.color;
+}
@@ -6,23 +6,38 @@
 // use crate::prelude::Shape;
 use wgpu::TextureView;
 use nablo_shape::prelude::shape_elements::Style;
-use wgpu_text::BrushBuilder;
-use nablo_shape::prelude::shape_elements::DEFAULT_FONT;
-use crate::texture::create_texture_with_data;
+use crate::texture::create_texture_with_options;
+use crate::texture::write_texture_levels;
+use crate::texture::check_texture_data;
 use crate::prelude::shape_elements::Rect;
 use nablo_shape::prelude::ShapeMask;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Wake;
+use std::task::Waker;
+use crate::widgets::PaintCallback;
+use crate::widgets::PaintCallbackInfo;
+use crate::widgets::TypeMap;
 use nablo_shape::prelude::Area;
 use crate::ParsedShape;
-use nablo_shape::prelude::shape_elements::CORRECTION;
-use nablo_shape::prelude::shape_elements::EM;
-use wgpu_text::TextBrush;
-use wgpu_text::glyph_brush::Section as TextSection;
-use wgpu_text::glyph_brush::Text as WText;
-use wgpu_text::glyph_brush::ab_glyph::FontArc;
+use crate::glyph_cache::GlyphCache;
+use crate::font::SharedFontBook;
+use crate::glyph_cache::GlyphText;
+use crate::texture_atlas::TextureAtlas;
+use crate::font::TextRendering;
+use crate::event::ShaderDiagnostic;
+use crate::event::Event;
 use crate::texture::create_texture;
 use std::result::Result::Ok;
 use crate::integrator::Output;
+use crate::integrator::RenderStats;
+use crate::integrator::Paint;
+use crate::event::Offscreen;
 use wgpu::include_wgsl;
 use nablo_shape::math::Vec2;
 use pollster::FutureExt as _;
@@ -47,28 +62,123 @@ pub(crate) struct State {
 	pub uniform_bind_group_layout: wgpu::BindGroupLayout,
 	pub shader_default: wgpu::ShaderModule,
 	pub fragment_shaders: HashMap<String, wgpu::ShaderModule>,
+	/// shaders passed naga, waiting for the device to check them
+	pub pending_shaders: Vec<PendingShader>,
+	/// pipelines of registrated shaders
+	pub pipelines: HashMap<String, wgpu::RenderPipeline>,
+	/// custom uniforms and textures of each shader, bound at group 2
+	pub shader_inputs: HashMap<String, ShaderInputs>,
+	pub shader_inputs_layout: wgpu::BindGroupLayout,
+	pub shader_inputs_sampler: wgpu::Sampler,
+	/// used when a texture bound to shader is missing
+	pub blank_texture: WTexture,
 	/// None for default
 	pub current_shader: Option<String>,
+	/// samples of msaa, 1 for no msaa
+	pub sample_count: u32,
+	/// drawn into this and resolved to surface when using msaa
+	pub msaa_view: Option<wgpu::TextureView>,
+	/// same for each offscreen by id, with its size
+	pub offscreen_msaa_views: HashMap<String, (Vec2, wgpu::TextureView)>,
 	// contains original image size
 	pub texture_map: HashMap<String, WTexture>,
-	pub brushes: Vec<TextBrush<FontArc>>,
-	pub font: FontArc,
+	/// small textures not in `texture_map`
+	pub atlas: TextureAtlas,
+	pub glyph_cache: GlyphCache,
+	/// [`PaintCallback`]s of current frame by id
+	pub paint_callbacks: HashMap<String, PaintCallback>,
+	/// ids of [`PaintCallback`]s already set up
+	pub paint_callbacks_set_up: HashSet<String>,
+	/// resources used by [`PaintCallback`]s
+	pub callback_resources: TypeMap,
+	/// one direction of gaussian blur for [`Paint::Backdrop`], drawing `src/blur.wgsl`
+	pub blur_pipeline: wgpu::RenderPipeline,
+	pub blur_buffer: wgpu::Buffer,
+	pub blur_bind_group: wgpu::BindGroup,
+	/// half blurred backdrop
+	pub backdrop_scratch: Option<WTexture>,
+	/// shapes filled by gradients, drawing `fs_paint` of `src/shader.wgsl`
+	pub paint_pipeline: wgpu::RenderPipeline,
+	/// [`PaintUniform`] of each batch with a gradient, choose which one by dynamic offset
+	pub paint_buffer: wgpu::Buffer,
+	pub paint_bind_group: wgpu::BindGroup,
+	pub paint_bind_group_layout: wgpu::BindGroupLayout,
 }
 
 pub(crate) struct WTexture {
-	#[allow(dead_code)]
 	pub texture: wgpu::Texture,
 	pub bind_group: wgpu::BindGroup,
 	pub layout: wgpu::BindGroupLayout,
+	/// options it was created with
+	pub options: crate::texture::TextureOptions,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type ErrorFuture = Pin<Box<dyn Future<Output = Option<wgpu::Error>> + Send>>;
+#[cfg(target_arch = "wasm32")]
+type ErrorFuture = Pin<Box<dyn Future<Output = Option<wgpu::Error>>>>;
+
+/// a shader used once the device found no error in it, see [`State::poll_shaders`]
+pub(crate) struct PendingShader {
+	id: String,
+	module: wgpu::ShaderModule,
+	pipeline: wgpu::RenderPipeline,
+	error: ErrorFuture,
+}
+
+/// futures of wgpu are polled every frame rather than waited, nothing needs to be woken up
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+	fn wake(self: Arc<Self>) {}
+}
+
+/// what user gives to a shader except [`Uniform`]
+pub(crate) struct ShaderInputs {
+	pub uniform_buffer: wgpu::Buffer,
+	/// texture ids
+	pub textures: Vec<String>,
+	pub bind_group: wgpu::BindGroup,
 }
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct Vertex {
-	position: [f32; 3],
-	color: [f32; 4],
+pub(crate) struct Vertex {
+	pub position: [f32; 3],
+	pub color: [f32; 4],
 	/// 0 = false, other = true, do not find bool in wgpu VertexFormat :(.
-	is_texture: u32
+	pub is_texture: u32,
+	/// multiplied with the sampled color when drawing a texture
+	pub tint: [f32; 4],
+}
+
+/// gradient of a batch, `Paint` in `src/shader.wgsl`
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+struct PaintUniform {
+	/// one of `PAINT_*`
+	kind: u32,
+	/// of radial gradient, in pixel
+	radius: f32,
+	_padding: [f32; 2],
+	/// in pixel, start and end of linear gradient, or center of radial gradient
+	points: [f32; 4],
+	stop_colors: [[f32; 4]; crate::MAX_GRADIENT_STOPS],
+	stop_offsets: [f32; crate::MAX_GRADIENT_STOPS],
+}
+
+const PAINT_LINEAR_GRADIENT: u32 = 1;
+const PAINT_RADIAL_GRADIENT: u32 = 2;
+
+/// texture id of what is behind a [`Paint::Backdrop`] shape, blurred
+const BACKDROP_TEXTURE: &str = "\u{0}backdrop";
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct BlurUniform {
+	direction: [f32; 2],
+	radius: f32,
+	_padding: f32,
 }
 
 #[repr(C)]
@@ -90,6 +200,7 @@ struct VertexOutput {
 	@builtin(position) clip_position: vec4f,
 	@location(0) color: vec4f,
 	@location(1) is_texture: u32,
+	@location(2) tint: vec4f,
 }
 
 @group(0)@binding(0)
@@ -109,8 +220,73 @@ struct Uniform {
 
 @group(1) @binding(0)
 var<uniform> uniforms: Uniform;
+
+@group(2)@binding(1)
+var s_custom: sampler;
+@group(2)@binding(2)
+var t_custom_0: texture_2d<f32>;
+@group(2)@binding(3)
+var t_custom_1: texture_2d<f32>;
+@group(2)@binding(4)
+var t_custom_2: texture_2d<f32>;
+@group(2)@binding(5)
+var t_custom_3: texture_2d<f32>;
 "#;
 
+/// size of a custom uniform block never setted
+const DEFAULT_CUSTOM_UNIFORM_SIZE: u64 = 256;
+
+/// size of [`Uniform`] in wgsl, which rounds up to 16 bytes
+const UNIFORM_SIZE: u64 = 48;
+
+/// size of [`PaintUniform`]
+const PAINT_UNIFORM_SIZE: u64 = std::mem::size_of::<PaintUniform>() as u64;
+
+/// where a frame is drawn
+struct Target<'a> {
+	/// what `view` is made from
+	texture: &'a wgpu::Texture,
+	view: &'a TextureView,
+	/// drawn into this and resolved to `view` when using msaa
+	msaa_view: Option<&'a TextureView>,
+	/// in pixel
+	size: Vec2,
+}
+
+impl<'a> Target<'a> {
+	fn attachment(&self, load: wgpu::LoadOp<wgpu::Color>) -> wgpu::RenderPassColorAttachment<'a> {
+		let (view, resolve_target) = match self.msaa_view {
+			Some(msaa_view) => (msaa_view, Some(self.view)),
+			None => (self.view, None),
+		};
+		wgpu::RenderPassColorAttachment {
+			view,
+			resolve_target,
+			ops: wgpu::Operations {
+				load,
+				store: wgpu::StoreOp::Store,
+			},
+		}
+	}
+}
+
+/// shapes next to each other sharing texture, shader, clip and info are drawn by one draw call
+struct Batch {
+	/// `None` for no texture
+	texture: Option<String>,
+	/// shader really used, `None` for default one
+	shader: Option<String>,
+	/// drawn by `State::paint_pipeline` instead of the shader
+	paint: Option<PaintUniform>,
+	/// x, y, width, height in pixel
+	scissor: [u32; 4],
+	info: u32,
+	/// normalized, for [`Uniform::position`] and [`Uniform::width_and_height`]
+	clip_area: Area,
+	indices: std::ops::Range<u32>,
+	vertexs_len: u32,
+}
+
 
 // const VERTICES: &[Vertex] = &[
 // 	Vertex { position: [-1.0, 1.0, 0.0], color: [0.0, 0.0, 0.0, 0.0], is_texture: 1 },
@@ -125,7 +301,7 @@ var<uniform> uniforms: Uniform;
 // ];
 
 impl State {
-	pub(crate) fn new<Window: raw_window_handle::HasRawDisplayHandle + raw_window_handle::HasRawWindowHandle>(window: &Window, size: Vec2) -> Self {
+	pub(crate) fn new<Window: raw_window_handle::HasRawDisplayHandle + raw_window_handle::HasRawWindowHandle>(window: &Window, size: Vec2, text_rendering: TextRendering, sample_count: u32) -> Self {
 		let mut size = size;
 		if size.x == 0.0{
 			size.x = 640.0
@@ -159,7 +335,8 @@ impl State {
 
 		let caps = surface.get_capabilities(&adapter);
 		let config = wgpu::SurfaceConfiguration {
-			usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+			// copied for backdrop blur when possible
+			usage: wgpu::TextureUsages::RENDER_ATTACHMENT | (caps.usages & wgpu::TextureUsages::COPY_SRC),
 			format: wgpu::TextureFormat::Rgba8UnormSrgb,
 			width: size.x as u32,
 			height: size.y as u32,
@@ -169,19 +346,23 @@ impl State {
 		};
 		surface.configure(&device, &config);
 
+		let sample_count = if adapter.get_texture_format_features(config.format).flags.sample_count_supported(sample_count) {
+			sample_count
+		}else {
+			#[cfg(feature = "info")]
+			println!("msaa with {} samples is not supported, using 1 sample", sample_count);
+			#[cfg(feature = "log")]
+			log::warn!("msaa with {} samples is not supported, using 1 sample", sample_count);
+			1
+		};
+		let msaa_view = create_msaa_view(&device, config.format, size, sample_count);
+
 		let shader_default = device.create_shader_module(include_wgsl!("shader.wgsl"));
 
 		let empty_texture = create_texture([size.x, size.y].into(), &device, &queue);
 
 
-		let uniform_buffer = device.create_buffer(
-			&wgpu::BufferDescriptor {
-				label: Some("uniform Buffer Render"),
-				size: 64,
-				usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-				mapped_at_creation: false,
-			}
-		);
+		let uniform_buffer = render_buffer(&device, "uniform Buffer Render", 2_u64.pow(12), wgpu::BufferUsages::UNIFORM);
 
 		let uniform_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
 			entries: &[wgpu::BindGroupLayoutEntry {
@@ -189,23 +370,64 @@ impl State {
 				visibility: wgpu::ShaderStages::all(),
 				ty: wgpu::BindingType::Buffer {
 					ty: wgpu::BufferBindingType::Uniform,
-					has_dynamic_offset: false,
-					min_binding_size: None,
+					// each batch has its uniform in the same buffer
+					has_dynamic_offset: true,
+					min_binding_size: wgpu::BufferSize::new(UNIFORM_SIZE),
 				},
 				count: None,
 			}],
 			label: Some("uniform bind group layout"),
 		});
 
-		let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-			layout: &uniform_bind_group_layout,
-			entries: &[wgpu::BindGroupEntry {
+		let uniform_bind_group = create_uniform_bind_group(&device, &uniform_bind_group_layout, &uniform_buffer);
+
+		let mut shader_inputs_entries = vec!(
+			wgpu::BindGroupLayoutEntry {
 				binding: 0,
-				resource: uniform_buffer.as_entire_binding(),
-			}],
-			label: Some("uniform bind group layout"),
+				visibility: wgpu::ShaderStages::FRAGMENT,
+				ty: wgpu::BindingType::Buffer {
+					ty: wgpu::BufferBindingType::Uniform,
+					has_dynamic_offset: false,
+					min_binding_size: None,
+				},
+				count: None,
+			},
+			wgpu::BindGroupLayoutEntry {
+				binding: 1,
+				visibility: wgpu::ShaderStages::FRAGMENT,
+				ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+				count: None,
+			},
+		);
+		for index in 0..crate::MAX_SHADER_TEXTURES {
+			shader_inputs_entries.push(wgpu::BindGroupLayoutEntry {
+				binding: 2 + index as u32,
+				visibility: wgpu::ShaderStages::FRAGMENT,
+				ty: wgpu::BindingType::Texture {
+					multisampled: false,
+					view_dimension: wgpu::TextureViewDimension::D2,
+					sample_type: wgpu::TextureSampleType::Float { filterable: true },
+				},
+				count: None,
+			});
+		}
+		let shader_inputs_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+			entries: &shader_inputs_entries,
+			label: Some("shader inputs bind group layout"),
 		});
 
+		let shader_inputs_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+			address_mode_u: wgpu::AddressMode::ClampToEdge,
+			address_mode_v: wgpu::AddressMode::ClampToEdge,
+			address_mode_w: wgpu::AddressMode::ClampToEdge,
+			mag_filter: wgpu::FilterMode::Linear,
+			min_filter: wgpu::FilterMode::Linear,
+			mipmap_filter: wgpu::FilterMode::Nearest,
+			..Default::default()
+		});
+
+		let blank_texture = create_texture(Vec2::same(1.0), &device, &queue);
+
 		let render_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
 			label: Some("Render Pipeline Layout"),
 			bind_group_layouts: &[&empty_texture.layout, &uniform_bind_group_layout],
@@ -240,33 +462,124 @@ impl State {
 			},
 			depth_stencil: None,
 			multisample: wgpu::MultisampleState {
-				count: 1, 
+				count: sample_count, 
 				mask: !0, 
 				alpha_to_coverage_enabled: false, 
 			},
 			multiview: None,
 		});
 
-		let vertex_buffer = device.create_buffer(
-			&wgpu::BufferDescriptor {
-				label: Some("Vertex Buffer Render"),
-				size: 2_u64.pow(16),
-				usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-				mapped_at_creation: false,
-			}
-		);
+		let vertex_buffer = render_buffer(&device, "Vertex Buffer Render", 2_u64.pow(16), wgpu::BufferUsages::VERTEX);
+		let index_buffer = render_buffer(&device, "Index Buffer Render", 2_u64.pow(16), wgpu::BufferUsages::INDEX);
 
-		let index_buffer = device.create_buffer(
-			&wgpu::BufferDescriptor {
-				label: Some("Index Buffer Render"),
-				size: 2_u64.pow(16),
-				usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
-				mapped_at_creation: false,
-			}
-		);
+		let blur_shader = device.create_shader_module(include_wgsl!("blur.wgsl"));
+		let blur_buffer = render_buffer(&device, "Blur Buffer", std::mem::size_of::<BlurUniform>() as u64, wgpu::BufferUsages::UNIFORM);
+		let blur_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+			entries: &[wgpu::BindGroupLayoutEntry {
+				binding: 0,
+				visibility: wgpu::ShaderStages::FRAGMENT,
+				ty: wgpu::BindingType::Buffer {
+					ty: wgpu::BufferBindingType::Uniform,
+					has_dynamic_offset: false,
+					min_binding_size: None,
+				},
+				count: None,
+			}],
+			label: Some("blur bind group layout"),
+		});
+		let blur_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+			layout: &blur_layout,
+			entries: &[wgpu::BindGroupEntry {
+				binding: 0,
+				resource: blur_buffer.as_entire_binding(),
+			}],
+			label: Some("blur bind group"),
+		});
+		let blur_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+			label: Some("Blur Pipeline Layout"),
+			bind_group_layouts: &[&empty_texture.layout, &blur_layout],
+			push_constant_ranges: &[],
+		});
+		let blur_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+			label: Some("Blur Pipeline"),
+			layout: Some(&blur_pipeline_layout),
+			vertex: wgpu::VertexState {
+				module: &blur_shader,
+				entry_point: "vs_main",
+				buffers: &[],
+			},
+			fragment: Some(wgpu::FragmentState {
+				module: &blur_shader,
+				entry_point: "fs_main",
+				targets: &[Some(wgpu::ColorTargetState {
+					format: wgpu::TextureFormat::Rgba8UnormSrgb,
+					blend: None,
+					write_mask: wgpu::ColorWrites::ALL,
+				})],
+			}),
+			primitive: wgpu::PrimitiveState::default(),
+			depth_stencil: None,
+			multisample: wgpu::MultisampleState::default(),
+			multiview: None,
+		});
 
-		let font = FontArc::try_from_slice(DEFAULT_FONT).unwrap();
-		let brush = BrushBuilder::using_font(font.clone()).build(&device, config.width, config.height, config.format);
+		let paint_buffer = render_buffer(&device, "Paint Buffer", 2_u64.pow(12), wgpu::BufferUsages::UNIFORM);
+		let paint_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+			entries: &[wgpu::BindGroupLayoutEntry {
+				binding: 0,
+				visibility: wgpu::ShaderStages::FRAGMENT,
+				ty: wgpu::BindingType::Buffer {
+					ty: wgpu::BufferBindingType::Uniform,
+					// each batch with a gradient has its paint in the same buffer
+					has_dynamic_offset: true,
+					min_binding_size: wgpu::BufferSize::new(PAINT_UNIFORM_SIZE),
+				},
+				count: None,
+			}],
+			label: Some("paint bind group layout"),
+		});
+		let paint_bind_group = create_paint_bind_group(&device, &paint_bind_group_layout, &paint_buffer);
+		let paint_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+			label: Some("Paint Pipeline Layout"),
+			bind_group_layouts: &[&empty_texture.layout, &uniform_bind_group_layout, &paint_bind_group_layout],
+			push_constant_ranges: &[],
+		});
+		let paint_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+			label: Some("Paint Pipeline"),
+			layout: Some(&paint_pipeline_layout),
+			vertex: wgpu::VertexState {
+				module: &shader_default,
+				entry_point: "vs_main",
+				buffers: &[desc()],
+			},
+			fragment: Some(wgpu::FragmentState {
+				module: &shader_default,
+				entry_point: "fs_paint",
+				targets: &[Some(wgpu::ColorTargetState {
+					format: config.format,
+					blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+					write_mask: wgpu::ColorWrites::ALL,
+				})],
+			}),
+			primitive: wgpu::PrimitiveState {
+				topology: wgpu::PrimitiveTopology::TriangleList,
+				strip_index_format: None,
+				front_face: wgpu::FrontFace::Ccw,
+				cull_mode: Some(wgpu::Face::Front),
+				polygon_mode: wgpu::PolygonMode::Fill,
+				unclipped_depth: false,
+				conservative: false,
+			},
+			depth_stencil: None,
+			multisample: wgpu::MultisampleState {
+				count: sample_count,
+				mask: !0,
+				alpha_to_coverage_enabled: false,
+			},
+			multiview: None,
+		});
+
+		let glyph_cache = GlyphCache::new(&device, &queue, config.format, text_rendering, sample_count);
 
 		Self {
 			surface,
@@ -283,10 +596,30 @@ impl State {
 			uniform_bind_group_layout,
 			shader_default,
 			texture_map: HashMap::new(),
-			brushes: vec!(brush),
-			font,
+			atlas: TextureAtlas::new(&device),
+			glyph_cache,
 			fragment_shaders: HashMap::new(),
-			current_shader: None
+			pending_shaders: vec!(),
+			pipelines: HashMap::new(),
+			shader_inputs: HashMap::new(),
+			shader_inputs_layout,
+			shader_inputs_sampler,
+			blank_texture,
+			current_shader: None,
+			sample_count,
+			msaa_view,
+			offscreen_msaa_views: HashMap::new(),
+			paint_callbacks: HashMap::new(),
+			paint_callbacks_set_up: HashSet::new(),
+			callback_resources: TypeMap::default(),
+			blur_pipeline,
+			blur_buffer,
+			blur_bind_group,
+			backdrop_scratch: None,
+			paint_pipeline,
+			paint_buffer,
+			paint_bind_group,
+			paint_bind_group_layout,
 		}
 	}
 
@@ -295,136 +628,68 @@ impl State {
 			self.size = new_size;
 			self.config.width = new_size.x as u32;
 			self.config.height = new_size.y as u32;
-			for brush in &self.brushes {
-				brush.resize_view(self.config.width as f32, self.config.height as f32, &self.queue);
-			}
 			self.empty_texture = create_texture([new_size.x, new_size.y].into() , &self.device, &self.queue);
-			let render_pipeline_layout = self.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-				label: Some("Render Pipeline Layout"),
-				bind_group_layouts: &[&self.empty_texture.layout, &self.uniform_bind_group_layout],
-				push_constant_ranges: &[],
-			});
-			let module = if let Some(shader_id) = &self.current_shader {
-				if let Some(shader) = self.fragment_shaders.get(shader_id) {
-					shader
-				}else {
-					&self.shader_default
-				}
-			}else {
-				&self.shader_default
-			};
-				
-			let render_pipeline = self.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-				label: Some("Render Pipeline"),
-				layout: Some(&render_pipeline_layout),
-				vertex: wgpu::VertexState {
-					module: &self.shader_default,
-					entry_point: "vs_main",
-					buffers: &[desc()],
-				},
-				fragment: Some(wgpu::FragmentState {
-					module,
-					entry_point: "fs_main",
-					targets: &[Some(wgpu::ColorTargetState {
-						format: self.config.format,
-						blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-						write_mask: wgpu::ColorWrites::ALL,
-					})],
-				}),
-				primitive: wgpu::PrimitiveState {
-					topology: wgpu::PrimitiveTopology::TriangleList, 
-					strip_index_format: None,
-					front_face: wgpu::FrontFace::Ccw,
-					cull_mode: Some(wgpu::Face::Back),
-					polygon_mode: wgpu::PolygonMode::Fill,
-					unclipped_depth: false,
-					conservative: false,
-				},
-				depth_stencil: None,
-				multisample: wgpu::MultisampleState {
-					count: 1, 
-					mask: !0, 
-					alpha_to_coverage_enabled: false, 
-				},
-				multiview: None,
-			});
-			self.render_pipeline = render_pipeline;
+			self.render_pipeline = self.create_pipeline(&self.shader_default, false);
+			self.msaa_view = create_msaa_view(&self.device, self.config.format, new_size, self.sample_count);
 			self.surface.configure(&self.device, &self.config);
 		}
 	}
 
-	pub(crate) fn draw_single_shape(&mut self, shape: ParsedShape, mouse_position: Vec2, time: f32, text_count: &mut usize, view: &TextureView) -> Result<(), wgpu::SurfaceError> {
-		let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-			label: Some("Render Texture Encoder"),
-		});
-		let window_size = Vec2::new(self.config.width as f32, self.config.height as f32);
-		let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-			label: Some("Render Pass Texture"),
-			color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-				view,
-				resolve_target: None,
-				ops: wgpu::Operations {
-				load: wgpu::LoadOp::Load,
-				store: wgpu::StoreOp::Store,
-			},
-			})],
-			..Default::default()
-		});
-		render_pass.set_pipeline(&self.render_pipeline);
-
-		match shape {
-			ParsedShape::Vertexs { vertexs, indices, clip_area, scale_factor, info } => {
-				self.queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[Uniform {
-					mouse_position: [mouse_position.x, mouse_position.y],
-					position: [clip_area.left_top().x, clip_area.left_top().y],
-					width_and_height: [clip_area.width_and_height().x, clip_area.width_and_height().y],
-					time,
-					info,
-					window_xy: [window_size.x, window_size.y],
-					indices_len: vertexs.len() as u32,
-				}]));
-
-				render_pass.set_bind_group(0, &self.empty_texture.bind_group, &[]);
-				render_pass.set_bind_group(1, &self.uniform_bind_group, &[]);
-				let mut vertexs_process = vec!();
-				for vertex in vertexs {
-					vertexs_process.push(Vertex {
-						position: vertex.position,
-						color: vertex.color,
-						is_texture: 0,
-					})
-				}
-				self.queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertexs_process));
-				self.queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&indices));
-				let clip_area = Area::new((clip_area.area[0] + Vec2::same(1.0)) / 2.0 * window_size * scale_factor, (clip_area.area[1] + Vec2::same(1.0)) / 2.0 * window_size * scale_factor);
-				let clip_area = Area::new_with_origin(window_size).cross_part(&clip_area);
-				render_pass.set_scissor_rect(clip_area.area[0].x as u32, clip_area.area[0].y as u32, clip_area.width_and_height().x as u32, clip_area.width_and_height().y as u32);
-				render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-				render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-				render_pass.draw_indexed(0..indices.len() as u32, 0, 0..1);
-			},
-			ParsedShape::Text(text, style) => {
-				if *text_count >= self.brushes.len() {
-					self.brushes.push(BrushBuilder::using_font(self.font.clone()).build(&self.device, self.config.width, self.config.height, self.config.format))
-				}
-				render_pass.set_bind_group(0, &self.empty_texture.bind_group, &[]);
-				let section = TextSection::default()
-					.with_screen_position((style.position.x * style.scale_factor, style.position.y * style.scale_factor))
-					.add_text(WText::new(text.text.leak()).with_color(style.fill.normalized()).with_scale(EM * CORRECTION * style.size.len() / 2_f32.sqrt() * style.scale_factor));
-				let clip_area = Area::new_with_origin(window_size).cross_part(&Area::new(style.clip.area[0] * style.scale_factor, style.clip.area[1] * style.scale_factor));
-				render_pass.set_scissor_rect(
-					clip_area.area[0].x as u32, 
-					clip_area.area[0].y as u32, 
-					clip_area.width_and_height().x as u32, 
-					clip_area.width_and_height().y as u32
-				);
-				self.brushes[*text_count].queue(&self.device, &self.queue, vec!(section)).unwrap();
-				self.brushes[*text_count].draw(&mut render_pass);
-				*text_count += 1;
-			},
-			ParsedShape::Image(image, style) => {
-				if let Some(t) = self.texture_map.get(&image.id) {
-					render_pass.set_bind_group(0, &t.bind_group, &[]);
+	/// draw shapes without texts in one render pass, see [`Batch`]
+	fn draw_shapes(&mut self, shapes: &mut Vec<ParsedShape>, mouse_position: Vec2, time: f32, target: &Target) -> RenderStats {
+		if shapes.is_empty() {
+			return RenderStats::default();
+		}
+		let window_size = target.size;
+		let window = Area::new_with_origin(window_size);
+		let mut vertexs_process: Vec<Vertex> = vec!();
+		let mut indices_process: Vec<u32> = vec!();
+		let mut batches: Vec<Batch> = vec!();
+		// shader really used, `None` for default one
+		let resolve_shader = |shader: Option<String>| shader.or(self.current_shader.clone()).filter(|id| self.pipelines.contains_key(id) && self.shader_inputs.contains_key(id));
+		for shape in shapes.drain(..) {
+			let (vertexs, indices, texture, shader, paint, clip_area, scissor, info) = match shape {
+				ParsedShape::Vertexs { vertexs, indices, clip_area, scale_factor, info, shader, paint } => {
+					let scissor = Area::new((clip_area.area[0] + Vec2::same(1.0)) / 2.0 * window_size * scale_factor, (clip_area.area[1] + Vec2::same(1.0)) / 2.0 * window_size * scale_factor);
+					if let Paint::Backdrop { .. } = paint {
+						// blurred copy of what is behind, see `Self::capture_backdrop`
+						let vertexs: Vec<Vertex> = vertexs.into_iter().map(|vertex| Vertex {
+							position: vertex.position,
+							color: [(vertex.position[0] + 1.0) / 2.0, (1.0 - vertex.position[1]) / 2.0, 0.0, 0.0],
+							is_texture: 1,
+							tint: [1.0, 1.0, 1.0, vertex.color[3]],
+						}).collect();
+						(vertexs, indices, Some(BACKDROP_TEXTURE.to_string()), None, None, clip_area, window.cross_part(&scissor), info)
+					}else {
+						// gradients are drawn by `self.paint_pipeline` rather than the shader
+						let paint = paint_uniform(&paint);
+						let shader = if paint.is_none() { resolve_shader(shader) } else { None };
+						let vertexs: Vec<Vertex> = vertexs.into_iter().map(|vertex| Vertex {
+							position: vertex.position,
+							color: vertex.color,
+							is_texture: 0,
+							tint: [1.0; 4],
+						}).collect();
+						(vertexs, indices, None, shader, paint, clip_area, window.cross_part(&scissor), info)
+					}
+				},
+				// drawn in batches by `self.glyph_cache`, see `Self::render`
+				ParsedShape::Text { .. } => continue,
+				// drawn in their own render pass, see `Self::draw_callback`
+				ParsedShape::PaintCallback { .. } => continue,
+				ParsedShape::Image { image, style, shader, tint, uv } => {
+					// where the texture is in its atlas page, normalized
+					let (texture, region) = if self.texture_map.contains_key(&image.id) {
+						(image.id, Area::new(Vec2::ZERO, Vec2::same(1.0)))
+					}else if let Some(packed) = self.atlas.region(&image.id) {
+						packed
+					}else {
+						continue;
+					};
+					let region = Area::new(
+						region.left_top() + uv.left_top() * region.width_and_height(),
+						region.left_top() + uv.right_bottom() * region.width_and_height(),
+					);
 					let mask = image.mask.unwrap_or(ShapeMask::Rect(Rect {
 						width_and_height: image.size,
 						..Default::default()
@@ -435,44 +700,207 @@ impl State {
 						position: Vec2::ZERO,
 						..style.clone()
 					});
-					self.queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[Uniform {
-						mouse_position: [mouse_position.x, mouse_position.y],
-						position: [clip_area.left_top().x, clip_area.left_top().y],
-						width_and_height: [clip_area.width_and_height().x, clip_area.width_and_height().y],
-						time,
-						info: style.info,
-						window_xy: [window_size.x, window_size.y],
-						indices_len: vertexs.len() as u32,
-					}]));
-					let mut vertexs_process = vec!();
-					for i in 0..vertexs.len() {
-						vertexs_process.push(Vertex {
-							position: vertexs[i].position,
-							color: [(texture_cords[i].position[0] + 1.0) / 2.0, 1.0 - (texture_cords[i].position[1] + 1.0) / 2.0, 0.0, 0.0],
-							is_texture: 1,
-						})
-					}
-					self.queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertexs_process));
-					self.queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&indices));
-					let clip_area = Area::new((clip_area.area[0] + Vec2::same(1.0)) / 2.0 * window_size, (clip_area.area[1] + Vec2::same(1.0)) / 2.0 * window_size);
-					let clip_area = Area::new_with_origin(window_size).cross_part(&clip_area);
-					render_pass.set_scissor_rect(clip_area.area[0].x as u32, clip_area.area[0].y as u32, clip_area.width_and_height().x as u32, clip_area.width_and_height().y as u32);
-					render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-					render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-					render_pass.draw_indexed(0..indices.len() as u32, 0, 0..1);
-				}
-			},
-		};
+					let vertexs: Vec<Vertex> = vertexs.iter().zip(texture_cords.iter()).map(|(vertex, texture_cord)| Vertex {
+						position: vertex.position,
+						color: [
+							region.left_top().x + (texture_cord.position[0] + 1.0) / 2.0 * region.width(),
+							region.left_top().y + (1.0 - (texture_cord.position[1] + 1.0) / 2.0) * region.height(),
+							0.0,
+							0.0
+						],
+						is_texture: 1,
+						tint: tint.normalized(),
+					}).collect();
+					let scissor = Area::new((clip_area.area[0] + Vec2::same(1.0)) / 2.0 * window_size, (clip_area.area[1] + Vec2::same(1.0)) / 2.0 * window_size);
+					(vertexs, indices, Some(texture), resolve_shader(shader), None, clip_area, window.cross_part(&scissor), style.info)
+				},
+			};
+			if scissor.width() <= 0.0 || scissor.height() <= 0.0 || indices.is_empty() {
+				continue;
+			}
+			let scissor = [scissor.area[0].x as u32, scissor.area[0].y as u32, scissor.width_and_height().x as u32, scissor.width_and_height().y as u32];
+			let base = vertexs_process.len() as u32;
+			let vertexs_len = vertexs.len() as u32;
+			let start = indices_process.len() as u32;
+			vertexs_process.extend(vertexs);
+			indices_process.extend(indices.into_iter().map(|index| index + base));
+			let end = indices_process.len() as u32;
+			match batches.last_mut() {
+				Some(batch) if batch.texture == texture && batch.shader == shader && batch.paint == paint && batch.scissor == scissor && batch.info == info => {
+					batch.indices.end = end;
+					batch.vertexs_len += vertexs_len;
+				},
+				_ => {
+					batches.push(Batch {
+						texture,
+						shader,
+						paint,
+						scissor,
+						info,
+						clip_area,
+						indices: start..end,
+						vertexs_len,
+					});
+				},
+			}
+		}
+		if batches.is_empty() {
+			return RenderStats::default();
+		}
 
-		drop(render_pass);
+		let stride = wgpu::util::align_to(UNIFORM_SIZE, self.device.limits().min_uniform_buffer_offset_alignment as u64);
+		let mut uniforms = vec!(0u8; stride as usize * batches.len());
+		for (index, batch) in batches.iter().enumerate() {
+			let uniform = Uniform {
+				mouse_position: [mouse_position.x, mouse_position.y],
+				position: [batch.clip_area.left_top().x, batch.clip_area.left_top().y],
+				width_and_height: [batch.clip_area.width_and_height().x, batch.clip_area.width_and_height().y],
+				time,
+				info: batch.info,
+				window_xy: [window_size.x, window_size.y],
+				indices_len: batch.vertexs_len,
+			};
+			let offset = index * stride as usize;
+			let bytes = bytemuck::bytes_of(&uniform);
+			uniforms[offset..offset + bytes.len()].copy_from_slice(bytes);
+		}
+		// only batches with gradients have one
+		let paint_stride = wgpu::util::align_to(PAINT_UNIFORM_SIZE, self.device.limits().min_uniform_buffer_offset_alignment as u64);
+		let mut paints = vec!();
+		let mut paint_offsets = vec!();
+		for batch in &batches {
+			paint_offsets.push(batch.paint.map(|paint| {
+				let offset = paints.len();
+				paints.extend_from_slice(bytemuck::bytes_of(&paint));
+				paints.resize(offset + paint_stride as usize, 0);
+				offset as u32
+			}));
+		}
+		let vertex_bytes: &[u8] = bytemuck::cast_slice(&vertexs_process);
+		let index_bytes: &[u8] = bytemuck::cast_slice(&indices_process);
+		if vertex_bytes.len() as u64 > self.vertex_buffer.size() {
+			self.vertex_buffer = render_buffer(&self.device, "Vertex Buffer Render", (vertex_bytes.len() as u64).next_power_of_two(), wgpu::BufferUsages::VERTEX);
+		}
+		if index_bytes.len() as u64 > self.index_buffer.size() {
+			self.index_buffer = render_buffer(&self.device, "Index Buffer Render", (index_bytes.len() as u64).next_power_of_two(), wgpu::BufferUsages::INDEX);
+		}
+		if uniforms.len() as u64 > self.uniform_buffer.size() {
+			self.uniform_buffer = render_buffer(&self.device, "uniform Buffer Render", (uniforms.len() as u64).next_power_of_two(), wgpu::BufferUsages::UNIFORM);
+			self.uniform_bind_group = create_uniform_bind_group(&self.device, &self.uniform_bind_group_layout, &self.uniform_buffer);
+		}
+		self.queue.write_buffer(&self.vertex_buffer, 0, vertex_bytes);
+		self.queue.write_buffer(&self.index_buffer, 0, index_bytes);
+		self.queue.write_buffer(&self.uniform_buffer, 0, &uniforms);
+		if paints.len() as u64 > self.paint_buffer.size() {
+			self.paint_buffer = render_buffer(&self.device, "Paint Buffer", (paints.len() as u64).next_power_of_two(), wgpu::BufferUsages::UNIFORM);
+			self.paint_bind_group = create_paint_bind_group(&self.device, &self.paint_bind_group_layout, &self.paint_buffer);
+		}
+		if !paints.is_empty() {
+			self.queue.write_buffer(&self.paint_buffer, 0, &paints);
+		}
 
+		let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+			label: Some("Render Texture Encoder"),
+		});
+		let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+			label: Some("Render Pass Texture"),
+			color_attachments: &[Some(target.attachment(wgpu::LoadOp::Load))],
+			..Default::default()
+		});
+		render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..vertex_bytes.len() as u64));
+		render_pass.set_index_buffer(self.index_buffer.slice(..index_bytes.len() as u64), wgpu::IndexFormat::Uint32);
+		// images packed in the same atlas page do not switch bind groups
+		let mut bound_texture = None;
+		for (index, batch) in batches.iter().enumerate() {
+			if let Some(offset) = paint_offsets[index] {
+				render_pass.set_pipeline(&self.paint_pipeline);
+				render_pass.set_bind_group(2, &self.paint_bind_group, &[offset]);
+			}else if let Some(id) = &batch.shader {
+				render_pass.set_pipeline(&self.pipelines[id]);
+				render_pass.set_bind_group(2, &self.shader_inputs[id].bind_group, &[]);
+			}else {
+				render_pass.set_pipeline(&self.render_pipeline);
+			}
+			if bound_texture != Some(&batch.texture) {
+				let texture = batch.texture.as_ref().and_then(|id| self.texture(id)).unwrap_or(&self.empty_texture);
+				render_pass.set_bind_group(0, &texture.bind_group, &[]);
+				bound_texture = Some(&batch.texture);
+			}
+			render_pass.set_bind_group(1, &self.uniform_bind_group, &[(index as u64 * stride) as u32]);
+			render_pass.set_scissor_rect(batch.scissor[0], batch.scissor[1], batch.scissor[2], batch.scissor[3]);
+			render_pass.draw_indexed(batch.indices.clone(), 0, 0..1);
+		}
+		drop(render_pass);
 		self.queue.submit(Some(encoder.finish()));
-		Ok(())
+
+		RenderStats {
+			draw_calls: batches.len(),
+			vertices: vertexs_process.len(),
+			upload_bytes: vertex_bytes.len() + index_bytes.len() + uniforms.len() + paints.len(),
+		}
 	}
 
-	pub(crate) fn render(&mut self, input: Output<Vec<ParsedShape>>, mouse_position: Vec2, time: f32) -> Result<(), wgpu::SurfaceError> {
+	pub(crate) fn render(&mut self, input: Output<Vec<ParsedShape>>, mouse_position: Vec2, time: f32) -> Result<RenderStats, wgpu::SurfaceError> {
 		let output = self.surface.get_current_texture()?;
 		let view = output.texture.create_view(&Default::default());
+		// taken out for borrow checker
+		let msaa_view = self.msaa_view.take();
+		let target = Target {
+			texture: &output.texture,
+			view: &view,
+			msaa_view: msaa_view.as_ref(),
+			size: Vec2::new(self.config.width as f32, self.config.height as f32),
+		};
+		let background = wgpu::Color {
+			r: input.background_color[0].powf(2.2) as f64,
+			g: input.background_color[1].powf(2.2) as f64,
+			b: input.background_color[2].powf(2.2) as f64,
+			a: input.background_color[3].powf(2.2) as f64,
+		};
+		let stats = self.draw_frame(input.shapes, background, mouse_position, time, &target);
+		self.msaa_view = msaa_view;
+
+		output.present();
+		Ok(stats)
+	}
+
+	/// draw shapes into texture `offscreen.id` instead of the window, texture is created or resized when needed
+	pub(crate) fn render_offscreen(&mut self, offscreen: Offscreen, mouse_position: Vec2, time: f32) -> RenderStats {
+		let size = Vec2::new(offscreen.size.x.max(1.0).round(), offscreen.size.y.max(1.0).round());
+		let is_same_size = self.texture_map.get(&offscreen.id).is_some_and(|texture| {
+			texture.texture.width() == size.x as u32 && texture.texture.height() == size.y as u32
+		});
+		if !is_same_size {
+			self.atlas.remove(&offscreen.id);
+			self.texture_map.insert(offscreen.id.clone(), create_texture(size, &self.device, &self.queue));
+			self.rebind_texture(&offscreen.id);
+		}
+		// taken out for borrow checker, an offscreen can not draw itself anyway
+		let Some(texture) = self.texture_map.remove(&offscreen.id) else {
+			return RenderStats::default();
+		};
+		let view = texture.texture.create_view(&wgpu::TextureViewDescriptor::default());
+		// taken out as well, made again only when resized
+		let msaa_view = match self.offscreen_msaa_views.remove(&offscreen.id) {
+			Some((msaa_size, msaa_view)) if msaa_size == size => Some(msaa_view),
+			_ => create_msaa_view(&self.device, texture.texture.format(), size, self.sample_count),
+		};
+		let target = Target {
+			texture: &texture.texture,
+			view: &view,
+			msaa_view: msaa_view.as_ref(),
+			size,
+		};
+		let stats = self.draw_frame(offscreen.parsed_shapes(), wgpu::Color::TRANSPARENT, mouse_position, time, &target);
+		if let Some(msaa_view) = msaa_view {
+			self.offscreen_msaa_views.insert(offscreen.id.clone(), (size, msaa_view));
+		}
+		self.texture_map.insert(offscreen.id, texture);
+		stats
+	}
+
+	/// clear `target` by `background` and draw `shapes` on it
+	fn draw_frame(&mut self, shapes: Vec<ParsedShape>, background: wgpu::Color, mouse_position: Vec2, time: f32, target: &Target) -> RenderStats {
 		// clear sections
 		let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
 			label: Some("Render Texture Encoder"),
@@ -480,19 +908,7 @@ impl State {
 
 		let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
 			label: Some("Render Pass"),
-			color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-				view: &view,
-				resolve_target: None,
-				ops: wgpu::Operations {
-					load: wgpu::LoadOp::Clear(wgpu::Color {
-						r: input.background_color[0].powf(2.2) as f64,
-						g: input.background_color[1].powf(2.2) as f64,
-						b: input.background_color[2].powf(2.2) as f64,
-						a: input.background_color[3].powf(2.2) as f64,
-					}),
-					store: wgpu::StoreOp::Store,
-				},
-			})],
+			color_attachments: &[Some(target.attachment(wgpu::LoadOp::Clear(background)))],
 			..Default::default()
 		});
 		render_pass.set_pipeline(&self.render_pipeline);
@@ -501,62 +917,411 @@ impl State {
 
 		self.queue.submit(Some(encoder.finish()));
 
-		// draw process
-		let mut text_count = 0;
-		for shape in input.shapes {
-
-			self.draw_single_shape(shape, mouse_position, time, &mut text_count, &view)?;
+		// draw process, texts next to each other are drawn together, so as other shapes
+		let mut stats = RenderStats::default();
+		let mut texts = vec!();
+		let mut shapes_batch = vec!();
+		for shape in shapes {
+			match shape {
+				ParsedShape::Text { text, style, row, outline, shadow } => {
+					stats += self.draw_shapes(&mut shapes_batch, mouse_position, time, target);
+					texts.push(GlyphText { text, style, row, outline, shadow });
+				},
+				ParsedShape::PaintCallback { id, area, clip } => {
+					stats += self.draw_shapes(&mut shapes_batch, mouse_position, time, target);
+					stats += self.draw_texts(&mut texts, target);
+					stats += self.draw_callback(&id, area, clip, target);
+				},
+				ParsedShape::Vertexs { vertexs, indices, clip_area, scale_factor, info, shader, paint: Paint::Backdrop { blur } } => {
+					stats += self.draw_shapes(&mut shapes_batch, mouse_position, time, target);
+					stats += self.draw_texts(&mut texts, target);
+					if let Some(bounds) = crate::effect::bounds(&vertexs, target.size) {
+						stats += self.capture_backdrop(blur, bounds, target);
+					}
+					shapes_batch.push(ParsedShape::Vertexs { vertexs, indices, clip_area, scale_factor, info, shader, paint: Paint::Backdrop { blur } });
+				},
+				shape => {
+					stats += self.draw_texts(&mut texts, target);
+					shapes_batch.push(shape);
+				},
+			}
 		}
+		stats += self.draw_shapes(&mut shapes_batch, mouse_position, time, target);
+		stats += self.draw_texts(&mut texts, target);
+		stats
+	}
 
+	/// copy what is drawn around `bounds` of `target` into [`BACKDROP_TEXTURE`] and blur it by `blur` pixels.
+	/// nothing is behind backdrops if `target` can not be copied
+	fn capture_backdrop(&mut self, blur: f32, bounds: Area, target: &Target) -> RenderStats {
+		if !target.texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+			self.texture_map.remove(BACKDROP_TEXTURE);
+			return RenderStats::default();
+		}
+		let size = Vec2::new(target.texture.width() as f32, target.texture.height() as f32);
+		let fits = |texture: Option<&WTexture>| texture.is_some_and(|texture| texture.texture.width() == size.x as u32 && texture.texture.height() == size.y as u32);
+		if !fits(self.texture_map.get(BACKDROP_TEXTURE)) {
+			self.texture_map.insert(BACKDROP_TEXTURE.to_string(), create_texture(size, &self.device, &self.queue));
+		}
+		if !fits(self.backdrop_scratch.as_ref()) {
+			self.backdrop_scratch = Some(create_texture(size, &self.device, &self.queue));
+		}
+		let window = Area::new_with_origin(size);
+		// x, y, width, height in pixel
+		let region = |grow: f32| {
+			let area = window.cross_part(&Area::new(bounds.left_top() - Vec2::same(grow), bounds.right_bottom() + Vec2::same(grow)));
+			[area.left_top().x as u32, area.left_top().y as u32, area.width().max(0.0) as u32, area.height().max(0.0) as u32]
+		};
+		let blur = blur.max(0.0);
+		let copied = region(blur * 2.0);
+		if copied[2] == 0 || copied[3] == 0 {
+			return RenderStats::default();
+		}
+		let backdrop = &self.texture_map[BACKDROP_TEXTURE];
+		let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+			label: Some("Backdrop Encoder"),
+		});
+		let origin = wgpu::Origin3d { x: copied[0], y: copied[1], z: 0 };
+		encoder.copy_texture_to_texture(wgpu::ImageCopyTexture {
+			origin,
+			..target.texture.as_image_copy()
+		}, wgpu::ImageCopyTexture {
+			origin,
+			..backdrop.texture.as_image_copy()
+		}, wgpu::Extent3d {
+			width: copied[2],
+			height: copied[3],
+			depth_or_array_layers: 1,
+		});
+		self.queue.submit(Some(encoder.finish()));
+		if blur <= 0.0 {
+			return RenderStats::default();
+		}
 
-		if text_count <= self.brushes.len() {
-			let len = self.brushes.len();
-			for _ in 0..(len - text_count) {
-				self.brushes.pop();
+		// horizontal into scratch, then vertical back
+		let Some(scratch) = &self.backdrop_scratch else {
+			return RenderStats::default();
+		};
+		let passes = [([1.0, 0.0], backdrop, scratch, region(blur)), ([0.0, 1.0], scratch, backdrop, region(0.0))];
+		for (direction, source, destination, scissor) in passes {
+			if scissor[2] == 0 || scissor[3] == 0 {
+				continue;
 			}
+			self.queue.write_buffer(&self.blur_buffer, 0, bytemuck::bytes_of(&BlurUniform {
+				direction,
+				radius: blur,
+				_padding: 0.0,
+			}));
+			let view = destination.texture.create_view(&wgpu::TextureViewDescriptor::default());
+			let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+				label: Some("Blur Encoder"),
+			});
+			let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+				label: Some("Blur Pass"),
+				color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+					view: &view,
+					resolve_target: None,
+					ops: wgpu::Operations {
+						load: wgpu::LoadOp::Load,
+						store: wgpu::StoreOp::Store,
+					},
+				})],
+				..Default::default()
+			});
+			render_pass.set_pipeline(&self.blur_pipeline);
+			render_pass.set_bind_group(0, &source.bind_group, &[]);
+			render_pass.set_bind_group(1, &self.blur_bind_group, &[]);
+			render_pass.set_scissor_rect(scissor[0], scissor[1], scissor[2], scissor[3]);
+			render_pass.draw(0..3, 0..1);
+			drop(render_pass);
+			self.queue.submit(Some(encoder.finish()));
 		}
+		RenderStats {
+			draw_calls: 2,
+			upload_bytes: std::mem::size_of::<BlurUniform>() * 2,
+			..Default::default()
+		}
+	}
 
-		output.present();
-		Ok(())
+	/// draw [`PaintCallback`] `id` at `area` in its own render pass
+	fn draw_callback(&mut self, id: &str, area: Area, clip: Area, target: &Target) -> RenderStats {
+		let Some(callback) = self.paint_callbacks.get(id).cloned() else {
+			return RenderStats::default();
+		};
+		// wgpu wants the viewport inside of the target
+		let viewport = area.cross_part(&Area::new_with_origin(target.size));
+		let clip = clip.cross_part(&viewport);
+		if clip.width() <= 0.0 || clip.height() <= 0.0 || viewport.width() <= 0.0 || viewport.height() <= 0.0 {
+			return RenderStats::default();
+		}
+		let info = PaintCallbackInfo {
+			area,
+			viewport,
+			clip,
+			target_size: target.size,
+			format: self.config.format,
+			sample_count: self.sample_count,
+		};
+		if self.paint_callbacks_set_up.insert(id.to_string()) {
+			callback.run_setup(&self.device, &self.queue, &info, &mut self.callback_resources);
+		}
+		callback.run_prepare(&self.device, &self.queue, &info, &mut self.callback_resources);
+
+		let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+			label: Some("Paint Callback Encoder"),
+		});
+		let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+			label: Some("Paint Callback Pass"),
+			color_attachments: &[Some(target.attachment(wgpu::LoadOp::Load))],
+			..Default::default()
+		});
+		render_pass.set_viewport(viewport.left_top().x, viewport.left_top().y, viewport.width(), viewport.height(), 0.0, 1.0);
+		render_pass.set_scissor_rect(clip.left_top().x as u32, clip.left_top().y as u32, clip.width() as u32, clip.height() as u32);
+		callback.run_paint(&info, &mut render_pass, &self.callback_resources);
+		drop(render_pass);
+		self.queue.submit(Some(encoder.finish()));
+		RenderStats {
+			draw_calls: 1,
+			..Default::default()
+		}
+	}
+
+	/// hand over [`PaintCallback`]s of current frame, see [`crate::integrator::Output::paint_callbacks`]
+	pub(crate) fn set_paint_callbacks(&mut self, paint_callbacks: HashMap<String, PaintCallback>) {
+		self.paint_callbacks = paint_callbacks;
 	}
 
-	pub(crate) fn insert_texture(&mut self, id: String, image: crate::texture::Image) {
-		self.texture_map.insert(id, create_texture_with_data(image.size, &self.device, &self.queue, image.rgba));
+	fn draw_texts(&mut self, texts: &mut Vec<GlyphText>, target: &Target) -> RenderStats {
+		let (view, resolve_target) = match target.msaa_view {
+			Some(msaa_view) => (msaa_view, Some(target.view)),
+			None => (target.view, None),
+		};
+		let stats = self.glyph_cache.draw(&self.device, &self.queue, view, resolve_target, texts, target.size);
+		texts.clear();
+		stats
+	}
+
+	/// fonts texts of next frames are laid out with, see [`crate::integrator::Output::fonts`]
+	pub(crate) fn set_fonts(&mut self, fonts: SharedFontBook) {
+		self.glyph_cache.set_fonts(fonts);
+	}
+
+	/// small textures are packed into [`TextureAtlas`] unless a shader binds them.
+	/// fails if `image` has wrong amount of bytes, the texture is left as it was
+	pub(crate) fn insert_texture(&mut self, id: String, image: crate::texture::Image) -> Result<(), String> {
+		check_texture_data(&image)?;
+		let is_bound = self.shader_inputs.values().any(|inputs| inputs.textures.contains(&id));
+		let image = if is_bound {
+			self.atlas.remove(&id);
+			Some(image)
+		}else {
+			self.atlas.pack(&self.device, &self.queue, image)
+		};
+		match image {
+			Some(image) => {
+				// same size and sampler, frames of a animation mostly, no need to create it again
+				if let Some(texture) = self.texture_map.get(&id) {
+					let size = texture.texture.size();
+					if texture.options == image.options && size.width == image.size.x as u32 && size.height == image.size.y as u32 {
+						write_texture_levels(&texture.texture, &self.queue, image.rgba);
+						return Ok(());
+					}
+				}
+				self.texture_map.insert(id.clone(), create_texture_with_options(image.size, &self.device, &self.queue, image.rgba, &image.options));
+			},
+			None => {
+				self.texture_map.remove(&id);
+			},
+		}
+		self.rebind_texture(&id);
+		Ok(())
 	}
 
 	pub(crate) fn remove_texture(&mut self, id: &String) {
 		self.texture_map.remove(id);
+		self.offscreen_msaa_views.remove(id);
+		self.atlas.remove(id);
+		self.rebind_texture(id);
+	}
+
+	/// texture by id, including atlas pages
+	fn texture(&self, id: &str) -> Option<&WTexture> {
+		self.texture_map.get(id).or_else(|| self.atlas.page(id))
+	}
+
+	/// shaders using texture `id` need new bind groups after it changed
+	fn rebind_texture(&mut self, id: &String) {
+		let shaders: Vec<String> = self.shader_inputs.iter().filter(|(_, inputs)| inputs.textures.contains(id)).map(|(shader, _)| shader.clone()).collect();
+		for shader in shaders {
+			self.rebind_shader_inputs(&shader);
+		}
 	}
 
-	pub(crate) fn registrate_shader(&mut self, id: String, shader_code: String) {
-		self.fragment_shaders.insert(id.clone(), self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+	/// checks the code by naga, then by the device without waiting for it, see [`Self::poll_shaders`].
+	/// a broken shader never replaces a working one
+	pub(crate) fn registrate_shader(&mut self, id: String, shader_code: String) -> Result<(), Vec<ShaderDiagnostic>> {
+		let source = format!("{}\n{}", SHADER_STRUCT, shader_code);
+		validate_shader(&source)?;
+		// naga may accept what current device does not support
+		self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+		let module = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
 			label: Some(&id),
-			source: wgpu::ShaderSource::Wgsl(format!("{}\n{}",SHADER_STRUCT ,shader_code).into()),
-		}));
+			source: wgpu::ShaderSource::Wgsl(source.into()),
+		});
+		let pipeline = self.create_pipeline(&module, true);
+		let error = Box::pin(self.device.pop_error_scope());
+		// registrated again before last one is checked
+		self.pending_shaders.retain(|pending| pending.id != id);
+		self.pending_shaders.push(PendingShader {
+			id,
+			module,
+			pipeline,
+			error,
+		});
+		Ok(())
+	}
+
+	pub(crate) fn has_pending_shaders(&self) -> bool {
+		!self.pending_shaders.is_empty()
+	}
+
+	/// shaders the device finished checking since last call, working ones are used from now on
+	pub(crate) fn poll_shaders(&mut self) -> Vec<(String, Result<(), Vec<ShaderDiagnostic>>)> {
+		if self.pending_shaders.is_empty() {
+			return vec!();
+		}
+		self.device.poll(wgpu::Maintain::Poll);
+		let waker = Waker::from(Arc::new(NoopWaker));
+		let mut context = Context::from_waker(&waker);
+		let mut results = vec!();
+		for mut pending in std::mem::take(&mut self.pending_shaders) {
+			let error = match pending.error.as_mut().poll(&mut context) {
+				Poll::Ready(error) => error,
+				Poll::Pending => {
+					self.pending_shaders.push(pending);
+					continue;
+				},
+			};
+			if let Some(error) = error {
+				results.push((pending.id, Err(vec!(device_diagnostic(&error.to_string())))));
+				continue;
+			}
+			if !self.shader_inputs.contains_key(&pending.id) {
+				self.set_shader_uniform(pending.id.clone(), vec!());
+			}
+			self.pipelines.insert(pending.id.clone(), pending.pipeline);
+			self.fragment_shaders.insert(pending.id.clone(), pending.module);
+			results.push((pending.id, Ok(())));
+		}
+		results
 	}
 
 	pub(crate) fn remove_shader(&mut self, id: String) {
+		self.pending_shaders.retain(|pending| pending.id != id);
 		self.fragment_shaders.remove(&id);
+		self.pipelines.remove(&id);
+		self.shader_inputs.remove(&id);
 	}
 
+	/// shapes without a shader of their own are drawn by this one
 	pub(crate) fn change_shader(&mut self, id: Option<String>) {
 		self.current_shader = id;
-		let render_pipeline_layout = self.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-			label: Some("Render Pipeline Layout"),
-			bind_group_layouts: &[&self.empty_texture.layout, &self.uniform_bind_group_layout],
-			push_constant_ranges: &[],
+	}
+
+	/// write custom uniform block of shader `id`, a larger buffer is created if `bytes` do not fit in current one
+	pub(crate) fn set_shader_uniform(&mut self, id: String, mut bytes: Vec<u8>) {
+		// uniform buffers are read in 16 bytes
+		let size = (bytes.len() as u64).div_ceil(16).max(1) * 16;
+		bytes.resize(size as usize, 0);
+		if let Some(inputs) = self.shader_inputs.get(&id) {
+			if inputs.uniform_buffer.size() >= size {
+				self.queue.write_buffer(&inputs.uniform_buffer, 0, &bytes);
+				return;
+			}
+		}
+		let uniform_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+			label: Some("custom uniform Buffer"),
+			size: size.max(DEFAULT_CUSTOM_UNIFORM_SIZE),
+			usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+			mapped_at_creation: false,
 		});
-		let module = if let Some(shader_id) = &self.current_shader {
-			if let Some(shader) = self.fragment_shaders.get(shader_id) {
-				shader
-			}else {
-				&self.shader_default
+		self.queue.write_buffer(&uniform_buffer, 0, &bytes);
+		let textures = self.shader_inputs.remove(&id).map(|inputs| inputs.textures).unwrap_or_default();
+		let bind_group = self.create_shader_inputs_bind_group(&uniform_buffer, &textures);
+		self.shader_inputs.insert(id, ShaderInputs {
+			uniform_buffer,
+			textures,
+			bind_group,
+		});
+	}
+
+	pub(crate) fn set_shader_textures(&mut self, id: String, mut textures: Vec<String>) {
+		textures.truncate(crate::MAX_SHADER_TEXTURES);
+		if !self.shader_inputs.contains_key(&id) {
+			self.set_shader_uniform(id.clone(), vec!());
+		}
+		// shaders sample whole textures, so packed ones get their own
+		for texture in &textures {
+			if let Some(taken) = self.atlas.take_out(&self.device, &self.queue, texture) {
+				self.texture_map.insert(texture.clone(), taken);
+			}
+		}
+		if let Some(inputs) = self.shader_inputs.get_mut(&id) {
+			inputs.textures = textures;
+		}
+		self.rebind_shader_inputs(&id);
+	}
+
+	fn rebind_shader_inputs(&mut self, id: &String) {
+		if let Some(inputs) = self.shader_inputs.get(id) {
+			let bind_group = self.create_shader_inputs_bind_group(&inputs.uniform_buffer, &inputs.textures);
+			if let Some(inputs) = self.shader_inputs.get_mut(id) {
+				inputs.bind_group = bind_group;
 			}
+		}
+	}
+
+	fn create_shader_inputs_bind_group(&self, uniform_buffer: &wgpu::Buffer, textures: &[String]) -> wgpu::BindGroup {
+		let views: Vec<wgpu::TextureView> = (0..crate::MAX_SHADER_TEXTURES).map(|index| {
+			let texture = textures.get(index).and_then(|id| self.texture_map.get(id)).unwrap_or(&self.blank_texture);
+			texture.texture.create_view(&wgpu::TextureViewDescriptor::default())
+		}).collect();
+		let mut entries = vec!(
+			wgpu::BindGroupEntry {
+				binding: 0,
+				resource: uniform_buffer.as_entire_binding(),
+			},
+			wgpu::BindGroupEntry {
+				binding: 1,
+				resource: wgpu::BindingResource::Sampler(&self.shader_inputs_sampler),
+			},
+		);
+		for (index, view) in views.iter().enumerate() {
+			entries.push(wgpu::BindGroupEntry {
+				binding: 2 + index as u32,
+				resource: wgpu::BindingResource::TextureView(view),
+			});
+		}
+		self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+			layout: &self.shader_inputs_layout,
+			entries: &entries,
+			label: Some("shader inputs bind group"),
+		})
+	}
+
+	/// `has_inputs` for registrated shaders, which use [`ShaderInputs`] at group 2
+	fn create_pipeline(&self, module: &wgpu::ShaderModule, has_inputs: bool) -> wgpu::RenderPipeline {
+		let bind_group_layouts = if has_inputs {
+			vec!(&self.empty_texture.layout, &self.uniform_bind_group_layout, &self.shader_inputs_layout)
 		}else {
-			&self.shader_default
+			vec!(&self.empty_texture.layout, &self.uniform_bind_group_layout)
 		};
-			
-		let render_pipeline = self.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+		let render_pipeline_layout = self.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+			label: Some("Render Pipeline Layout"),
+			bind_group_layouts: &bind_group_layouts,
+			push_constant_ranges: &[],
+		});
+		self.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
 			label: Some("Render Pipeline"),
 			layout: Some(&render_pipeline_layout),
 			vertex: wgpu::VertexState {
@@ -584,18 +1349,208 @@ impl State {
 			},
 			depth_stencil: None,
 			multisample: wgpu::MultisampleState {
-				count: 1, 
+				count: self.sample_count, 
 				mask: !0, 
 				alpha_to_coverage_enabled: false, 
 			},
 			multiview: None,
-		});
-		self.render_pipeline = render_pipeline;
-		self.surface.configure(&self.device, &self.config);
+		})
+	}
+}
+
+/// `None` if `sample_count` is 1
+fn create_msaa_view(device: &wgpu::Device, format: wgpu::TextureFormat, size: Vec2, sample_count: u32) -> Option<wgpu::TextureView> {
+	if sample_count <= 1 {
+		return None;
+	}
+	let texture = device.create_texture(&wgpu::TextureDescriptor {
+		label: Some("msaa texture"),
+		size: wgpu::Extent3d {
+			width: size.x as u32,
+			height: size.y as u32,
+			depth_or_array_layers: 1,
+		},
+		mip_level_count: 1,
+		sample_count,
+		dimension: wgpu::TextureDimension::D2,
+		format,
+		usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+		view_formats: &[],
+	});
+	Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+}
+
+fn render_buffer(device: &wgpu::Device, label: &str, size: u64, usage: wgpu::BufferUsages) -> wgpu::Buffer {
+	device.create_buffer(&wgpu::BufferDescriptor {
+		label: Some(label),
+		size,
+		usage: usage | wgpu::BufferUsages::COPY_DST,
+		mapped_at_creation: false,
+	})
+}
+
+/// binds one [`Uniform`] of `uniform_buffer`, choose which one by dynamic offset
+fn create_uniform_bind_group(device: &wgpu::Device, layout: &wgpu::BindGroupLayout, uniform_buffer: &wgpu::Buffer) -> wgpu::BindGroup {
+	device.create_bind_group(&wgpu::BindGroupDescriptor {
+		layout,
+		entries: &[wgpu::BindGroupEntry {
+			binding: 0,
+			resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+				buffer: uniform_buffer,
+				offset: 0,
+				size: wgpu::BufferSize::new(UNIFORM_SIZE),
+			}),
+		}],
+		label: Some("uniform bind group layout"),
+	})
+}
+
+/// binds one [`PaintUniform`] of `paint_buffer`, choose which one by dynamic offset
+fn create_paint_bind_group(device: &wgpu::Device, layout: &wgpu::BindGroupLayout, paint_buffer: &wgpu::Buffer) -> wgpu::BindGroup {
+	device.create_bind_group(&wgpu::BindGroupDescriptor {
+		layout,
+		entries: &[wgpu::BindGroupEntry {
+			binding: 0,
+			resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+				buffer: paint_buffer,
+				offset: 0,
+				size: wgpu::BufferSize::new(PAINT_UNIFORM_SIZE),
+			}),
+		}],
+		label: Some("paint bind group"),
+	})
+}
+
+/// checks `source` by naga, an entry point `fs_main` for fragment stage is required
+fn validate_shader(source: &str) -> Result<(), Vec<ShaderDiagnostic>> {
+	let module = naga::front::wgsl::parse_str(source).map_err(|error| {
+		let labels = error.labels().map(|(span, label)| diagnostic(source, span, join_message(error.message(), label)));
+		with_fallback(labels.collect(), error.message())
+	})?;
+	let mut validator = naga::valid::Validator::new(naga::valid::ValidationFlags::all(), naga::valid::Capabilities::all());
+	if let Err(error) = validator.validate(&module) {
+		// validation errors are nested, such as a function is invalid because a expression is invalid
+		let mut message = error.as_inner().to_string();
+		let mut inner = std::error::Error::source(error.as_inner());
+		while let Some(current) = inner {
+			message = format!("{message}: {current}");
+			inner = current.source();
+		}
+		let spans = error.spans().map(|(span, label)| diagnostic(source, *span, join_message(&message, label)));
+		return Err(with_fallback(spans.collect(), &message));
+	}
+	if !module.entry_points.iter().any(|entry| entry.name == "fs_main" && entry.stage == naga::ShaderStage::Fragment) {
+		return Err(with_fallback(vec!(), "missing entry point `@fragment fn fs_main(in: VertexOutput) -> @location(0) vec4f`"));
+	}
+	Ok(())
+}
+
+fn join_message(message: &str, label: &str) -> String {
+	if label.is_empty() {
+		message.to_string()
+	}else {
+		format!("{message}, {label}")
+	}
+}
+
+/// a diagnostic without position if we have nothing
+fn with_fallback(diagnostics: Vec<ShaderDiagnostic>, message: &str) -> Vec<ShaderDiagnostic> {
+	if diagnostics.is_empty() {
+		vec!(ShaderDiagnostic {
+			message: message.to_string(),
+			..Default::default()
+		})
+	}else {
+		diagnostics
+	}
+}
+
+/// what [`crate::Ui`] is told about a shader checked by [`State::registrate_shader`] or [`State::poll_shaders`], failures are logged as well
+pub(crate) fn shader_event(id: String, result: Result<(), Vec<ShaderDiagnostic>>) -> Event {
+	match result {
+		Ok(()) => Event::ShaderRegistrated(id),
+		Err(diagnostics) => {
+			#[cfg(feature = "info")]
+			println!("compiling shader {} failed, info: {:?}", id, diagnostics);
+			#[cfg(feature = "log")]
+			log::error!("compiling shader {} failed, info: {:?}", id, diagnostics);
+			Event::ShaderError(id, diagnostics)
+		}
+	}
+}
+
+/// lines [`SHADER_STRUCT`] takes before the registrated code
+fn prelude_lines() -> usize {
+	SHADER_STRUCT.matches('\n').count() + 1
+}
+
+/// `line` counted in the whole source, turned into the line of registrated code
+fn shader_diagnostic(line: usize, column: usize, length: usize, message: String) -> ShaderDiagnostic {
+	if line <= prelude_lines() {
+		return ShaderDiagnostic { message, is_in_prelude: true, ..Default::default() };
+	}
+	ShaderDiagnostic {
+		line: line - prelude_lines(),
+		column,
+		length,
+		message,
+		is_in_prelude: false,
+	}
+}
+
+/// position of `span` in code after [`SHADER_STRUCT`]
+fn diagnostic(source: &str, span: naga::Span, message: String) -> ShaderDiagnostic {
+	let Some(range) = span.to_range() else {
+		return ShaderDiagnostic { message, ..Default::default() };
+	};
+	let start = range.start.min(source.len());
+	let end = range.end.clamp(start, source.len());
+	let line = source[..start].matches('\n').count() + 1;
+	let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+	shader_diagnostic(line, source[line_start..start].chars().count() + 1, source[start..end].chars().count(), message)
+}
+
+/// errors from the device only tell where the problem is in their message, such as `┌─ shader:12:5`
+fn device_diagnostic(message: &str) -> ShaderDiagnostic {
+	let position = message.split_whitespace().find_map(|word| {
+		let mut parts = word.rsplitn(3, ':');
+		let column = parts.next()?.parse::<usize>().ok()?;
+		let line = parts.next()?.parse::<usize>().ok()?;
+		parts.next()?;
+		Some((line, column))
+	});
+	match position {
+		Some((line, column)) => shader_diagnostic(line, column, 0, message.to_string()),
+		None => ShaderDiagnostic { message: message.to_string(), ..Default::default() },
 	}
 }
 
-fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+/// `None` for shapes not filled by gradients, stops are padded by the last one
+fn paint_uniform(paint: &Paint) -> Option<PaintUniform> {
+	let (kind, radius, points, stops) = match paint {
+		Paint::LinearGradient { start, end, stops } => (PAINT_LINEAR_GRADIENT, 0.0, [start.x, start.y, end.x, end.y], stops),
+		Paint::RadialGradient { center, radius, stops } => (PAINT_RADIAL_GRADIENT, *radius, [center.x, center.y, 0.0, 0.0], stops),
+		Paint::Color | Paint::Backdrop { .. } => return None,
+	};
+	let mut stop_colors = [[1.0; 4]; crate::MAX_GRADIENT_STOPS];
+	let mut stop_offsets = [0.0; crate::MAX_GRADIENT_STOPS];
+	for index in 0..crate::MAX_GRADIENT_STOPS {
+		if let Some((offset, color)) = stops.get(index.min(stops.len().saturating_sub(1))) {
+			stop_colors[index] = color.normalized();
+			stop_offsets[index] = if index < stops.len() { *offset } else { stop_offsets[index - 1] + 1.0 };
+		}
+	}
+	Some(PaintUniform {
+		kind,
+		radius,
+		_padding: [0.0; 2],
+		points,
+		stop_colors,
+		stop_offsets,
+	})
+}
+
+pub(crate) fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
 	wgpu::VertexBufferLayout {
 		array_stride: std::mem::size_of::<Vertex>() as u64,
 		step_mode: wgpu::VertexStepMode::Vertex,
@@ -614,7 +1569,42 @@ fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
 				offset: std::mem::size_of::<[f32; 7]>() as u64,
 				shader_location: 2,
 				format: wgpu::VertexFormat::Uint32,
-			}
+			},
+			wgpu::VertexAttribute {
+				offset: std::mem::size_of::<[f32; 8]>() as u64,
+				shader_location: 3,
+				format: wgpu::VertexFormat::Float32x4,
+			},
 		]
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn diagnostic_lines_skip_prelude() {
+		let source = format!("{}\n{}", SHADER_STRUCT, "fn a() {}\nfn b() { c }");
+		let start = source.rfind('c').unwrap();
+		let diagnostic = diagnostic(&source, naga::Span::new(start as u32, start as u32 + 1), "c".to_string());
+		assert_eq!((diagnostic.line, diagnostic.column, diagnostic.length), (2, 10, 1));
+		assert!(!diagnostic.is_in_prelude);
+	}
+
+	#[test]
+	fn diagnostic_in_prelude_is_flagged() {
+		let source = format!("{}\n{}", SHADER_STRUCT, "fn a() {}");
+		let diagnostic = diagnostic(&source, naga::Span::new(1, 2), "prelude".to_string());
+		assert!(diagnostic.is_in_prelude);
+		assert_eq!((diagnostic.line, diagnostic.column), (0, 0));
+	}
+
+	#[test]
+	fn device_diagnostic_reads_position_from_message() {
+		let message = format!("Shader validation error:\n  ┌─ shader:{}:5\n", prelude_lines() + 3);
+		let diagnostic = device_diagnostic(&message);
+		assert_eq!((diagnostic.line, diagnostic.column), (3, 5));
+		assert_eq!(device_diagnostic("out of memory").line, 0);
+	}
+}
@@ -0,0 +1,86 @@
+/// `GlyphVertex` in `src/glyph_cache.rs`
+struct VertexInput {
+	@location(0) position: vec2f,
+	/// in glyph atlas, reaches out of `uv_rect` where only the shadow is drawn
+	@location(1) uv: vec2f,
+	/// left top and right bottom of the glyph in atlas
+	@location(2) uv_rect: vec4f,
+	/// left top and right bottom in pixel, nothing is drawn outside
+	@location(3) clip: vec4f,
+	@location(4) fill: vec4f,
+	@location(5) outline_color: vec4f,
+	@location(6) shadow_color: vec4f,
+	/// where the shadow is sampled relative to `uv`
+	@location(7) shadow_offset: vec2f,
+	/// x is 1.0 for distance fields, y is outline width and z is shadow softness, both in distance
+	@location(8) params: vec4f,
+}
+
+struct VertexOutput {
+	@builtin(position) clip_position: vec4f,
+	@location(0) uv: vec2f,
+	@location(1) @interpolate(flat) uv_rect: vec4f,
+	@location(2) @interpolate(flat) clip: vec4f,
+	@location(3) @interpolate(flat) fill: vec4f,
+	@location(4) @interpolate(flat) outline_color: vec4f,
+	@location(5) @interpolate(flat) shadow_color: vec4f,
+	@location(6) @interpolate(flat) shadow_offset: vec2f,
+	@location(7) @interpolate(flat) params: vec4f,
+}
+
+@vertex
+fn vs_main(
+	model: VertexInput,
+) -> VertexOutput {
+	var out: VertexOutput;
+	out.clip_position = vec4f(model.position, 0.0, 1.0);
+	out.uv = model.uv;
+	out.uv_rect = model.uv_rect;
+	out.clip = model.clip;
+	out.fill = model.fill;
+	out.outline_color = model.outline_color;
+	out.shadow_color = model.shadow_color;
+	out.shadow_offset = model.shadow_offset;
+	out.params = model.params;
+	return out;
+}
+
+@group(0)@binding(0)
+var t_diffuse: texture_2d<f32>;
+@group(0)@binding(1)
+var s_diffuse: sampler;
+
+/// alpha of the atlas at `uv`, nothing outside the glyph at `rect`
+fn sample_glyph(uv: vec2f, rect: vec4f) -> f32 {
+	let value = textureSample(t_diffuse, s_diffuse, uv).a;
+	return select(0.0, value, all(uv >= rect.xy) && all(uv <= rect.zw));
+}
+
+/// `top` drawn on `bottom`
+fn over(top: vec4f, bottom: vec4f) -> vec4f {
+	let alpha = top.a + bottom.a * (1.0 - top.a);
+	let rgb = (top.rgb * top.a + bottom.rgb * bottom.a * (1.0 - top.a)) / max(alpha, 0.0001);
+	return vec4f(rgb, alpha);
+}
+
+/// alpha of the atlas is coverage of bitmap glyphs.
+/// for distance fields it is distance to the edge of glyph, 0.5 is right on the edge
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+	let field = sample_glyph(in.uv, in.uv_rect);
+	let shadow_field = sample_glyph(in.uv - in.shadow_offset, in.uv_rect);
+	let width = max(fwidth(field), 0.0001);
+	let position = in.clip_position.xy;
+	let visible = select(0.0, 1.0, all(position >= in.clip.xy) && all(position < in.clip.zw));
+	if in.params.x == 0.0 {
+		return vec4f(in.fill.rgb, in.fill.a * field * visible);
+	}
+	let fill = smoothstep(0.5 - width, 0.5 + width, field);
+	let outline = smoothstep(0.5 - in.params.y - width, 0.5 - in.params.y + width, field);
+	let softness = max(in.params.z, width);
+	let shadow = smoothstep(0.5 - softness, 0.5 + softness, shadow_field);
+	var color = vec4f(in.shadow_color.rgb, in.shadow_color.a * shadow);
+	color = over(vec4f(in.outline_color.rgb, in.outline_color.a * outline), color);
+	color = over(vec4f(in.fill.rgb, in.fill.a * fill), color);
+	return vec4f(color.rgb, color.a * visible);
+}
@@ -0,0 +1,574 @@
+//! the text layout engine shared by widgets and the renderer.
+//!
+//! widgets measure text by [`layout`], and draw every [`Row`] of a [`Galley`] as a single line text shape. the renderer chooses fonts by same [`crate::font::FontBook`] with same [`text_scale`], therefore what you measured is what you get.
+
+use ab_glyph::Font;
+use ab_glyph::ScaleFont;
+use ab_glyph::PxScale;
+use crate::font::font_book;
+use crate::widgets::TextWrap;
+use nablo_shape::prelude::shape_elements::CORRECTION;
+use nablo_shape::prelude::shape_elements::EM;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use unicode_bidi::BidiInfo;
+use std::ops::Range;
+
+/// pixel height of fonts for a text with `size` as its scale
+pub fn text_scale(size: Vec2) -> f32 {
+	EM * CORRECTION * size.len() / 2_f32.sqrt()
+}
+
+/// is this language written right to left, `language` is a language tag like `ar` or `he-IL`
+pub fn is_rtl_language(language: &str) -> bool {
+	let primary = language.split(['-', '_']).next().unwrap_or("").to_lowercase();
+	matches!(primary.as_str(), "ar" | "he" | "iw" | "fa" | "ur" | "yi" | "ps" | "sd" | "ug" | "dv" | "ckb")
+}
+
+/// joining forms of arabic letters from `U+0621` to `U+064A` in Arabic Presentation Forms-B, 0 for not a letter, 2 for joining right only and 4 for joining both sides.
+const ARABIC_FORMS: [u8; 42] = [
+	1, 2, 2, 2, 2, 4, 2, 4, 2, 4, 4, 4, 4, 4, 2, 2, 2, 2, 4, 4, 4, 4, 4, 4, 4, 4,
+	0, 0, 0, 0, 0,
+	// tatweel
+	0,
+	4, 4, 4, 4, 4, 4, 4, 2, 2, 4,
+];
+
+/// how many joining forms a charater has and where they start
+fn arabic_forms(chr: char) -> Option<(u32, u8)> {
+	let code = chr as u32;
+	if !(0x0621..=0x064A).contains(&code) {
+		return None;
+	}
+	let index = (code - 0x0621) as usize;
+	let count = ARABIC_FORMS[index];
+	if count == 0 {
+		return None;
+	}
+	let start = 0xFE80 + ARABIC_FORMS[..index].iter().map(|inner| *inner as u32).sum::<u32>();
+	Some((start, count))
+}
+
+/// marks between letters do not break joining
+fn is_transparent(chr: char) -> bool {
+	matches!(chr as u32, 0x064B..=0x065F | 0x0670 | 0x06D6..=0x06ED)
+}
+
+/// can `chr` join the charater after it
+fn joins_next(chr: char) -> bool {
+	chr == '\u{0640}' || arabic_forms(chr).is_some_and(|(_, count)| count == 4)
+}
+
+/// can `chr` join the charater before it
+fn joins_previous(chr: char) -> bool {
+	chr == '\u{0640}' || arabic_forms(chr).is_some_and(|(_, count)| count >= 2)
+}
+
+/// stands for the alef of a lam-alef ligature, which is drawn by the lam before and takes no space
+const LIGATURE_PART: char = '\u{200D}';
+
+/// isolated form of lam-alef ligatures in Arabic Presentation Forms-B by the alef, final forms follow them
+fn lam_alef(alef: char) -> Option<u32> {
+	match alef {
+		'\u{0622}' => Some(0xFEF5),
+		'\u{0623}' => Some(0xFEF7),
+		'\u{0625}' => Some(0xFEF9),
+		'\u{0627}' => Some(0xFEFB),
+		_ => None,
+	}
+}
+
+/// replace arabic letters with their contextual forms, charater count is kept.
+///
+/// a lam followed by an alef becomes a lam-alef ligature, and the alef becomes [`LIGATURE_PART`]
+fn shape_arabic(chars: &[char]) -> Vec<char> {
+	let mut back: Vec<char> = (0..chars.len()).map(|index| {
+		let chr = chars[index];
+		let Some((start, count)) = arabic_forms(chr) else {
+			return chr;
+		};
+		let previous = chars[..index].iter().rev().find(|inner| !is_transparent(**inner)).is_some_and(|inner| joins_next(*inner)) && joins_previous(chr);
+		let next = chars[index + 1..].iter().find(|inner| !is_transparent(**inner)).is_some_and(|inner| joins_previous(*inner)) && joins_next(chr);
+		let form = match (previous, next) {
+			(true, true) if count == 4 => 3,
+			(true, _) if count >= 2 => 1,
+			(false, true) if count == 4 => 2,
+			_ => 0,
+		};
+		char::from_u32(start + form).unwrap_or(chr)
+	}).collect();
+	for index in 0..chars.len().saturating_sub(1) {
+		if chars[index] != '\u{0644}' {
+			continue;
+		}
+		let Some(start) = lam_alef(chars[index + 1]) else {
+			continue;
+		};
+		let is_final = chars[..index].iter().rev().find(|inner| !is_transparent(**inner)).is_some_and(|inner| joins_next(*inner));
+		back[index] = char::from_u32(start + u32::from(is_final)).unwrap_or(chars[index]);
+		back[index + 1] = LIGATURE_PART;
+	}
+	back
+}
+
+/// mirrored charater used in right to left runs
+fn mirror(chr: char) -> char {
+	match chr {
+		'(' => ')',
+		')' => '(',
+		'[' => ']',
+		']' => '[',
+		'{' => '}',
+		'}' => '{',
+		'<' => '>',
+		'>' => '<',
+		'«' => '»',
+		'»' => '«',
+		_ => chr,
+	}
+}
+
+/// a charater with its position
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PositionedGlyph {
+	/// charater in logical order
+	pub chr: char,
+	/// what actually drawn, after arabic shaping and mirroring
+	pub display: char,
+	/// is this glyph in a right to left run
+	pub is_rtl: bool,
+	/// byte index in laid out text
+	pub byte_index: usize,
+	/// left top of this glyph, relative to left top of the galley
+	pub position: Vec2,
+	/// how far next charater goes, kerning included
+	pub advance: f32,
+	/// index of [`crate::font::FontBook::fonts`]
+	pub font: usize,
+	/// charater index in the text before truncated, `None` for the `…` put by [`crate::widgets::Truncate`]
+	pub source: Option<usize>,
+}
+
+/// a line of a [`Galley`]
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Row {
+	/// range of [`Galley::glyphs`]
+	pub glyphs: Range<usize>,
+	/// relative to the top of the galley
+	pub top: f32,
+	pub height: f32,
+	/// relative to the top of the galley
+	pub baseline: f32,
+	/// trailing spaces not included
+	pub width: f32,
+	/// where the first glyph in visual order starts
+	pub left: f32,
+	/// is the paragraph of this row right to left
+	pub is_rtl: bool,
+}
+
+/// a row of a [`Galley`] ready to draw, see [`Galley::text_row`]
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct TextRow {
+	/// from the top of the row to its baseline
+	pub ascent: f32,
+	/// from left to right
+	pub glyphs: Vec<RowGlyph>,
+}
+
+/// a glyph of a [`TextRow`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RowGlyph {
+	/// what is drawn, after arabic shaping and mirroring
+	pub display: char,
+	/// index of [`crate::font::FontBook::fonts`]
+	pub font: usize,
+	/// from the left of the row
+	pub x: f32,
+}
+
+/// laid out text, glyphs are splited into rows
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Galley {
+	pub text: String,
+	/// one glyph for one charater
+	pub glyphs: Vec<PositionedGlyph>,
+	/// at least one row, even if text is empty
+	pub rows: Vec<Row>,
+	pub size: Vec2,
+}
+
+impl Galley {
+	/// how many charaters we have
+	pub fn char_len(&self) -> usize {
+		self.glyphs.len()
+	}
+
+	/// text in `row` without line breaks, in logical order
+	pub fn row_text(&self, row: &Row) -> &str {
+		if row.glyphs.is_empty() {
+			return "";
+		}
+		let start = self.glyphs[row.glyphs.start].byte_index;
+		let end = self.glyphs[row.glyphs.end - 1].byte_index + self.glyphs[row.glyphs.end - 1].chr.len_utf8();
+		self.text[start..end].trim_end_matches('\n')
+	}
+
+	/// what should be drawn for `row`, from left to right
+	pub fn visual_row_text(&self, row: &Row) -> String {
+		self.visual_order(row).into_iter().map(|index| self.glyphs[index].display).filter(|display| *display != LIGATURE_PART).collect()
+	}
+
+	/// glyphs of `row` from left to right as renderers draw them, relative to left top of the row
+	pub fn text_row(&self, row: &Row) -> TextRow {
+		TextRow {
+			ascent: row.baseline - row.top,
+			glyphs: self.visual_order(row).into_iter().map(|index| &self.glyphs[index]).filter(|glyph| glyph.display != LIGATURE_PART).map(|glyph| RowGlyph {
+				display: glyph.display,
+				font: glyph.font,
+				x: glyph.position.x - row.left,
+			}).collect(),
+		}
+	}
+
+	/// glyph indices of `row` from left to right, line breaks not included
+	pub fn visual_order(&self, row: &Row) -> Vec<usize> {
+		let mut back: Vec<usize> = row.glyphs.clone().filter(|index| self.glyphs[*index].chr != '\n').collect();
+		back.sort_by(|a, b| self.glyphs[*a].position.x.total_cmp(&self.glyphs[*b].position.x));
+		back
+	}
+
+	/// left top of the caret standing before `char_index`th charater
+	pub fn caret_position(&self, char_index: usize) -> Vec2 {
+		if let Some(glyph) = self.glyphs.get(char_index) {
+			return if glyph.is_rtl {
+				glyph.position + Vec2::x(glyph.advance)
+			}else {
+				glyph.position
+			};
+		}
+		match (self.rows.last(), self.glyphs.last()) {
+			(Some(_), Some(glyph)) if glyph.chr != '\n' => if glyph.is_rtl {
+				glyph.position
+			}else {
+				glyph.position + Vec2::x(glyph.advance)
+			},
+			(Some(row), _) => Vec2::new(if row.is_rtl { row.left + row.width } else { row.left }, row.top),
+			(None, _) => Vec2::ZERO,
+		}
+	}
+
+	/// charater index in the text before truncated for a caret standing before `char_index`th charater, a caret before `…` goes to the next shown charater
+	pub fn source_index(&self, char_index: usize) -> usize {
+		match self.glyphs[char_index.min(self.glyphs.len())..].iter().find_map(|glyph| glyph.source) {
+			Some(index) => index,
+			None => self.glyphs.iter().rev().find_map(|glyph| glyph.source).map_or(0, |index| index + 1),
+		}
+	}
+
+	/// is a caret standing before `char_index`th charater in a right to left run, decided by the charater before the caret
+	pub fn is_rtl_at(&self, char_index: usize) -> bool {
+		match self.glyphs.get(char_index.saturating_sub(1)) {
+			Some(glyph) => glyph.is_rtl,
+			None => self.rows.first().is_some_and(|row| row.is_rtl),
+		}
+	}
+
+	/// height of the caret standing before `char_index`th charater
+	pub fn caret_height(&self, char_index: usize) -> f32 {
+		let caret = self.caret_position(char_index);
+		self.rows.iter().find(|row| row.top == caret.y).map_or(0.0, |row| row.height)
+	}
+
+	/// which charater index should caret be if we click `position`, relative to left top of the galley
+	pub fn char_index_at(&self, position: Vec2) -> usize {
+		let row = match self.rows.iter().find(|row| position.y < row.top + row.height) {
+			Some(row) => row,
+			None => match self.rows.last() {
+				Some(row) => row,
+				None => return 0,
+			}
+		};
+		let visual = self.visual_order(row);
+		for index in &visual {
+			let glyph = &self.glyphs[*index];
+			if position.x < glyph.position.x + glyph.advance / 2.0 {
+				return if glyph.is_rtl { index + 1 } else { *index };
+			}
+		}
+		match visual.last() {
+			Some(index) => if self.glyphs[*index].is_rtl { *index } else { index + 1 },
+			None => row.glyphs.start,
+		}
+	}
+
+	/// areas covering charaters in `range`, several areas are given if text directions are mixed.
+	pub fn selection_areas(&self, range: Range<usize>) -> Vec<Area> {
+		let mut back: Vec<Area> = vec!();
+		for row in &self.rows {
+			for index in self.visual_order(row) {
+				if !range.contains(&index) {
+					continue;
+				}
+				let glyph = &self.glyphs[index];
+				let area = Area::new(glyph.position, glyph.position + Vec2::new(glyph.advance, row.height));
+				match back.last_mut() {
+					Some(last) if last.right_top().y == area.left_top().y && (last.right_top().x - area.left_top().x).abs() < 0.5 => last.combine(&area),
+					_ => back.push(area),
+				}
+			}
+		}
+		back
+	}
+}
+
+/// lay out `text` drawn with font family `family` and scale `size`. lines will be broken as `wrap` says if `max_width` is given.
+///
+/// charaters are reordered by unicode bidirectional algorithm in every row, arabic letters are shaped into contextual forms.
+pub fn layout(text: &str, family: u32, size: Vec2, max_width: Option<f32>, wrap: TextWrap) -> Galley {
+	let fonts = font_book();
+	let book = fonts.read().unwrap();
+	let scale = PxScale::from(text_scale(size));
+	let max_width = match wrap {
+		TextWrap::None => f32::INFINITY,
+		_ => max_width.unwrap_or(f32::INFINITY),
+	};
+	let chars: Vec<char> = text.chars().collect();
+	let shaped = shape_arabic(&chars);
+	let mut glyphs: Vec<PositionedGlyph> = vec!();
+	let mut last_glyph = None;
+	for ((byte_index, chr), shaped) in text.char_indices().zip(shaped) {
+		// the alef is already in the ligature, unless no font has the ligature
+		let lam = glyphs.last().filter(|last| last.display != last.chr).map(|last| last.font);
+		if let (LIGATURE_PART, Some(font)) = (shaped, lam) {
+			glyphs.push(PositionedGlyph { chr, display: LIGATURE_PART, is_rtl: false, byte_index, position: Vec2::ZERO, advance: 0.0, font, source: Some(glyphs.len()) });
+			last_glyph = None;
+			continue;
+		}
+		let shaped = if shaped == LIGATURE_PART { chr } else { shaped };
+		let font = book.font_for(family, shaped);
+		// fallback to unshaped one if no font has the contextual form
+		let (display, font) = if book.fonts()[font].glyph_id(shaped).0 != 0 {
+			(shaped, font)
+		}else {
+			(chr, book.font_for(family, chr))
+		};
+		let scaled = book.fonts()[font].as_scaled(scale);
+		let id = scaled.glyph_id(display);
+		if let Some((last_font, last_id)) = last_glyph {
+			if last_font == font {
+				if let Some(last) = glyphs.last_mut() {
+					last.advance += scaled.kern(last_id, id);
+				}
+			}
+		}
+		let advance = if chr == '\n' { 0.0 } else { scaled.h_advance(id) };
+		glyphs.push(PositionedGlyph { chr, display, is_rtl: false, byte_index, position: Vec2::ZERO, advance, font, source: Some(glyphs.len()) });
+		last_glyph = Some((font, id));
+	}
+
+	// break lines
+	let mut ranges: Vec<Range<usize>> = vec!();
+	let mut start = 0;
+	let mut x = 0.0;
+	let mut last_break: Option<usize> = None;
+	for index in 0..glyphs.len() {
+		let chr = glyphs[index].chr;
+		if chr == '\n' {
+			ranges.push(start..index + 1);
+			start = index + 1;
+			x = 0.0;
+			last_break = None;
+			continue;
+		}
+		let advance = glyphs[index].advance;
+		if x + advance > max_width && index > start && !chr.is_whitespace() {
+			match last_break.filter(|inner| *inner > start) {
+				Some(inner) => {
+					ranges.push(start..inner);
+					start = inner;
+					x = glyphs[inner..index].iter().map(|glyph| glyph.advance).sum();
+				},
+				None => {
+					ranges.push(start..index);
+					start = index;
+					x = 0.0;
+				}
+			}
+			last_break = None;
+		}
+		x += advance;
+		if chr.is_whitespace() && wrap == TextWrap::Word {
+			last_break = Some(index + 1);
+		}
+	}
+	ranges.push(start..glyphs.len());
+
+	// position glyphs
+	let bidi = BidiInfo::new(text, None);
+	let mut rows = vec!();
+	let mut top = 0.0;
+	let mut width: f32 = 0.0;
+	let own_font = book.font_for(family, ' ');
+	for range in ranges {
+		let mut fonts: Vec<usize> = glyphs[range.clone()].iter().map(|glyph| glyph.font).collect();
+		if fonts.is_empty() {
+			fonts.push(own_font);
+		}
+		let (ascent, descent, line_gap) = fonts.iter().fold((f32::MIN, f32::MAX, f32::MIN), |(ascent, descent, line_gap), font| {
+			let scaled = book.fonts()[*font].as_scaled(scale);
+			(ascent.max(scaled.ascent()), descent.min(scaled.descent()), line_gap.max(scaled.line_gap()))
+		});
+		// reorder
+		let line: Vec<usize> = range.clone().filter(|index| glyphs[*index].chr != '\n').collect();
+		let mut is_rtl = false;
+		let mut visual = line.clone();
+		if let (Some(first), Some(last)) = (line.first(), line.last()) {
+			let line_bytes = glyphs[*first].byte_index..glyphs[*last].byte_index + glyphs[*last].chr.len_utf8();
+			if let Some(paragraph) = bidi.paragraphs.iter().find(|paragraph| paragraph.range.contains(&line_bytes.start)) {
+				is_rtl = paragraph.level.is_rtl();
+				let (levels, runs) = bidi.visual_runs(paragraph, line_bytes);
+				visual.clear();
+				for run in runs {
+					let run_rtl = levels[run.start].is_rtl();
+					let mut indices: Vec<usize> = line.iter().copied().filter(|index| run.contains(&glyphs[*index].byte_index)).collect();
+					if run_rtl {
+						indices.reverse();
+						for index in &indices {
+							glyphs[*index].is_rtl = true;
+							glyphs[*index].display = mirror(glyphs[*index].display);
+						}
+					}
+					visual.append(&mut indices);
+				}
+			}
+		}
+		let total: f32 = visual.iter().map(|index| glyphs[*index].advance).sum();
+		let left = if is_rtl && max_width.is_finite() {
+			(max_width - total).max(0.0)
+		}else {
+			0.0
+		};
+		let mut x = left;
+		let mut row_width: f32 = 0.0;
+		for index in &visual {
+			let glyph = &mut glyphs[*index];
+			glyph.position = Vec2::new(x, top);
+			x += glyph.advance;
+			if !glyph.chr.is_whitespace() {
+				row_width = x - left;
+			}
+		}
+		// line breaks stay at the logical end of the row
+		for glyph in &mut glyphs[range.clone()] {
+			if glyph.chr == '\n' {
+				glyph.is_rtl = is_rtl;
+				glyph.position = Vec2::new(if is_rtl { left } else { x }, top);
+			}
+		}
+		let height = ascent - descent + line_gap;
+		rows.push(Row {
+			glyphs: range,
+			top,
+			height,
+			baseline: top + ascent,
+			width: row_width,
+			left,
+			is_rtl,
+		});
+		width = width.max(if is_rtl { left + row_width } else { row_width });
+		top += height;
+	}
+	Galley {
+		text: text.to_string(),
+		glyphs,
+		rows,
+		size: Vec2::new(width, top),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::font::DEFAULT_FAMILY;
+
+	fn shape(text: &str) -> Vec<u32> {
+		shape_arabic(&text.chars().collect::<Vec<char>>()).into_iter().map(|chr| chr as u32).collect()
+	}
+
+	#[test]
+	fn arabic_letters_join() {
+		// beh beh: initial and final
+		assert_eq!(shape("\u{0628}\u{0628}"), vec!(0xFE91, 0xFE90));
+		// beh beh beh: medial in the middle
+		assert_eq!(shape("\u{0628}\u{0628}\u{0628}"), vec!(0xFE91, 0xFE92, 0xFE90));
+		// alef does not join next one
+		assert_eq!(shape("\u{0627}\u{0628}"), vec!(0xFE8D, 0xFE8F));
+		// marks do not break joining
+		assert_eq!(shape("\u{0628}\u{064E}\u{0628}"), vec!(0xFE91, 0x064E, 0xFE90));
+		// latin stays same
+		assert_eq!(shape("ab"), vec!('a' as u32, 'b' as u32));
+	}
+
+	#[test]
+	fn lam_alef_becomes_ligature() {
+		assert_eq!(shape("\u{0644}\u{0627}"), vec!(0xFEFB, LIGATURE_PART as u32));
+		assert_eq!(shape("\u{0644}\u{0622}"), vec!(0xFEF5, LIGATURE_PART as u32));
+		// joined by beh before: final form
+		assert_eq!(shape("\u{0628}\u{0644}\u{0623}"), vec!(0xFE91, 0xFEF8, LIGATURE_PART as u32));
+		assert_eq!(shape("\u{0628}\u{0644}\u{0625}"), vec!(0xFE91, 0xFEFA, LIGATURE_PART as u32));
+		// lam without alef after keeps its own forms
+		assert_eq!(shape("\u{0644}\u{0628}"), vec!(0xFEDF, 0xFE90));
+	}
+
+	#[test]
+	fn rtl_runs_are_reversed_and_mirrored() {
+		let galley = layout("abc \u{05D0}(\u{05D1})", DEFAULT_FAMILY, Vec2::NOT_TO_SCALE, None, TextWrap::None);
+		assert!(!galley.rows[0].is_rtl);
+		assert!(galley.glyphs[..4].iter().all(|glyph| !glyph.is_rtl));
+		assert!(galley.glyphs[4..].iter().all(|glyph| glyph.is_rtl));
+		assert_eq!(galley.glyphs[5].display, ')');
+		assert_eq!(galley.glyphs[7].display, '(');
+		// logical order goes right to left in the hebrew run
+		assert_eq!(galley.visual_order(&galley.rows[0])[4..], [7, 6, 5, 4]);
+	}
+
+	#[test]
+	fn caret_direction_follows_run() {
+		let galley = layout("abc \u{05D0}\u{05D1}", DEFAULT_FAMILY, Vec2::NOT_TO_SCALE, None, TextWrap::None);
+		assert!(!galley.is_rtl_at(0));
+		assert!(!galley.is_rtl_at(3));
+		assert!(galley.is_rtl_at(6));
+		let galley = layout("\u{05D0}\u{05D1} abc", DEFAULT_FAMILY, Vec2::NOT_TO_SCALE, None, TextWrap::None);
+		assert!(galley.rows[0].is_rtl);
+		assert!(galley.is_rtl_at(1));
+		assert!(!galley.is_rtl_at(6));
+		let empty = layout("", DEFAULT_FAMILY, Vec2::NOT_TO_SCALE, None, TextWrap::None);
+		assert!(!empty.is_rtl_at(0));
+	}
+
+	#[test]
+	fn text_rows_are_drawn_left_to_right() {
+		let galley = layout("ab \u{05D0}\u{05D1}\ncd", DEFAULT_FAMILY, Vec2::NOT_TO_SCALE, None, TextWrap::None);
+		let row = galley.text_row(&galley.rows[0]);
+		let displays: String = row.glyphs.iter().map(|glyph| glyph.display).collect();
+		assert_eq!(displays, galley.visual_row_text(&galley.rows[0]));
+		assert_eq!(displays, "ab \u{05D1}\u{05D0}");
+		assert_eq!(row.glyphs[0].x, 0.0);
+		assert!(row.glyphs.windows(2).all(|pair| pair[0].x < pair[1].x));
+		assert_eq!(row.ascent, galley.rows[0].baseline - galley.rows[0].top);
+		// relative to its own row
+		let second = galley.text_row(&galley.rows[1]);
+		assert_eq!(second.glyphs.iter().map(|glyph| glyph.display).collect::<String>(), "cd");
+		assert_eq!(second.glyphs[0].x, 0.0);
+	}
+
+	#[test]
+	fn rtl_languages() {
+		assert!(is_rtl_language("ar"));
+		assert!(is_rtl_language("he-IL"));
+		assert!(is_rtl_language("fa_IR"));
+		assert!(!is_rtl_language("en-US"));
+		assert!(!is_rtl_language(""));
+	}
+}
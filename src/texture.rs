@@ -9,7 +9,217 @@ use nablo_shape::prelude::Vec2;
 pub struct Image {
 	pub rgba: Vec<u8>,
 	pub id: String,
-	pub size: Vec2
+	pub size: Vec2,
+	pub options: TextureOptions,
+}
+
+/// how a texture is sampled, see [`crate::Ui::create_texture_with_options`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextureOptions {
+	/// used when the texture is drawn larger than it is
+	pub mag_filter: FilterMode,
+	/// used when the texture is drawn smaller than it is
+	pub min_filter: FilterMode,
+	/// used outside of the texture
+	pub address_mode: AddressMode,
+	/// generate smaller copies of the texture, so it does not shimmer when drawn small
+	pub mipmaps: bool,
+	/// small textures without mipmaps are packed into a shared atlas with others of same options, so images drawn next to each other share one draw call.
+	/// turn it off if a custom shader needs texture coordinates from 0 to 1 of this texture
+	pub atlas: bool,
+}
+
+impl Default for TextureOptions {
+	fn default() -> Self {
+		Self {
+			mag_filter: FilterMode::Linear,
+			min_filter: FilterMode::Nearest,
+			address_mode: AddressMode::MirrorRepeat,
+			mipmaps: false,
+			atlas: true,
+		}
+	}
+}
+
+impl TextureOptions {
+	/// sharp pixels, for pixel arts
+	pub const NEAREST: Self = Self {
+		mag_filter: FilterMode::Nearest,
+		min_filter: FilterMode::Nearest,
+		address_mode: AddressMode::ClampToEdge,
+		mipmaps: false,
+		atlas: true,
+	};
+
+	/// smooth in any size, for photos
+	pub const LINEAR: Self = Self {
+		mag_filter: FilterMode::Linear,
+		min_filter: FilterMode::Linear,
+		address_mode: AddressMode::ClampToEdge,
+		mipmaps: true,
+		atlas: true,
+	};
+
+	/// set both filters
+	pub fn filter(self, filter: FilterMode) -> Self {
+		Self {
+			mag_filter: filter,
+			min_filter: filter,
+			..self
+		}
+	}
+
+	/// set address mode
+	pub fn address_mode(self, address_mode: AddressMode) -> Self {
+		Self {
+			address_mode,
+			..self
+		}
+	}
+
+	/// set if mipmaps are generated
+	pub fn mipmaps(self, mipmaps: bool) -> Self {
+		Self {
+			mipmaps,
+			..self
+		}
+	}
+
+	/// set if the texture can be packed into a shared atlas
+	pub fn atlas(self, atlas: bool) -> Self {
+		Self {
+			atlas,
+			..self
+		}
+	}
+}
+
+/// where a texture loaded by [`crate::Ui::load_texture`] or [`crate::Ui::load_texture_from_path`] is, see [`crate::Ui::texture_status`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TextureStatus {
+	/// still decoding
+	Loading,
+	/// can be drawn
+	Ready,
+	/// could not be read or decoded, with the reason
+	Failed(String),
+}
+
+/// how an animation plays, see [`crate::Ui::create_animated_texture`] and [`crate::widgets::SpriteSheet`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Playback {
+	/// paused animations stay on current frame
+	pub is_playing: bool,
+	/// start over after the last frame, otherwise stops on it
+	pub is_looping: bool,
+	/// 2.0 for twice as fast, not playing if not positive
+	pub speed: f32,
+}
+
+impl Default for Playback {
+	fn default() -> Self {
+		Self {
+			is_playing: true,
+			is_looping: true,
+			speed: 1.0,
+		}
+	}
+}
+
+impl Playback {
+	/// set if the animation is playing
+	pub fn playing(self, is_playing: bool) -> Self {
+		Self {
+			is_playing,
+			..self
+		}
+	}
+
+	/// set if the animation starts over after the last frame
+	pub fn looping(self, is_looping: bool) -> Self {
+		Self {
+			is_looping,
+			..self
+		}
+	}
+
+	/// set how fast the animation plays
+	pub fn speed(self, speed: f32) -> Self {
+		Self {
+			speed,
+			..self
+		}
+	}
+
+	/// move `frame` forward by `delta` seconds, `elapsed` is time already spent on `frame`.
+	/// returns seconds until next frame, or `None` if not playing or stopped on the last frame
+	pub(crate) fn advance(&self, frame: &mut usize, elapsed: &mut f32, delta: f32, count: usize, delay: impl Fn(usize) -> f32) -> Option<f32> {
+		if !self.is_playing || self.speed <= 0.0 || count == 0 {
+			return None;
+		}
+		*elapsed += delta * self.speed;
+		// a round at most, in case of a long pause between frames
+		for _ in 0..count {
+			if *elapsed < delay(*frame) {
+				break;
+			}
+			if *frame + 1 < count {
+				*elapsed -= delay(*frame);
+				*frame += 1;
+			}else if self.is_looping {
+				*elapsed -= delay(*frame);
+				*frame = 0;
+			}else {
+				*elapsed = delay(*frame);
+				return None;
+			}
+		}
+		*elapsed = elapsed.min(delay(*frame));
+		Some((delay(*frame) - *elapsed) / self.speed)
+	}
+}
+
+/// how pixels of a texture are mixed when sampling
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FilterMode {
+	/// the closest pixel
+	Nearest,
+	/// mix pixels around
+	#[default]
+	Linear,
+}
+
+/// what is sampled outside of a texture
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AddressMode {
+	/// the closest edge
+	ClampToEdge,
+	/// the texture repeated
+	Repeat,
+	/// the texture repeated and mirrored every time
+	#[default]
+	MirrorRepeat,
+}
+
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+impl From<FilterMode> for wgpu::FilterMode {
+	fn from(value: FilterMode) -> Self {
+		match value {
+			FilterMode::Nearest => Self::Nearest,
+			FilterMode::Linear => Self::Linear,
+		}
+	}
+}
+
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+impl From<AddressMode> for wgpu::AddressMode {
+	fn from(value: AddressMode) -> Self {
+		match value {
+			AddressMode::ClampToEdge => Self::ClampToEdge,
+			AddressMode::Repeat => Self::Repeat,
+			AddressMode::MirrorRepeat => Self::MirrorRepeat,
+		}
+	}
 }
 
 #[cfg(any(feature = "manager", feature = "baseview_manager"))]
@@ -20,45 +230,45 @@ pub(crate) fn create_texture(size: Vec2, device: &wgpu::Device, queue: &wgpu::Qu
 
 #[cfg(any(feature = "manager", feature = "baseview_manager"))]
 pub(crate) fn create_texture_with_data(size: Vec2, device: &wgpu::Device, queue: &wgpu::Queue, data: Vec<u8>) -> WTexture {
+	create_texture_with_options(size, device, queue, data, &TextureOptions::default())
+}
+
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+pub(crate) fn create_texture_with_options(size: Vec2, device: &wgpu::Device, queue: &wgpu::Queue, data: Vec<u8>, options: &TextureOptions) -> WTexture {
 	let texture_size = wgpu::Extent3d {
 		width: size.x as u32,
 		height: size.y as u32,
 		depth_or_array_layers: 1,
 	};
+	let mip_level_count = if options.mipmaps {
+		texture_size.max_mips(wgpu::TextureDimension::D2)
+	}else {
+		1
+	};
 
 	let diffuse_texture = device.create_texture(
 		&wgpu::TextureDescriptor {
 			size: texture_size,
-			mip_level_count: 1,
+			mip_level_count,
 			sample_count: 1,
 			dimension: wgpu::TextureDimension::D2,
 			format: wgpu::TextureFormat::Rgba8UnormSrgb,
-			usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::RENDER_ATTACHMENT,
+			usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
 			label: Some("main_texture"),
 			view_formats: &[],
 		}
 	);
 
-	let slice = data.as_slice();
-
-	queue.write_texture(wgpu::ImageCopyTexture {
-		texture: &diffuse_texture,
-		mip_level: 0,
-		origin: wgpu::Origin3d::ZERO,
-		aspect: wgpu::TextureAspect::All,
-	}, slice, wgpu::ImageDataLayout {
-		offset: 0,
-		bytes_per_row: Some(size.x as u32 * 4),
-		rows_per_image: Some(size.y as u32),
-	}, texture_size);
+	write_texture_levels(&diffuse_texture, queue, data);
 	let diffuse_texture_view = diffuse_texture.create_view(&wgpu::TextureViewDescriptor::default());
+	let address_mode = options.address_mode.into();
 	let diffuse_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-		address_mode_u: wgpu::AddressMode::MirrorRepeat,
-		address_mode_v: wgpu::AddressMode::MirrorRepeat,
-		address_mode_w: wgpu::AddressMode::MirrorRepeat,
-		mag_filter: wgpu::FilterMode::Linear,
-		min_filter: wgpu::FilterMode::Nearest,
-		mipmap_filter: wgpu::FilterMode::Nearest,
+		address_mode_u: address_mode,
+		address_mode_v: address_mode,
+		address_mode_w: address_mode,
+		mag_filter: options.mag_filter.into(),
+		min_filter: options.min_filter.into(),
+		mipmap_filter: if options.mipmaps { wgpu::FilterMode::Linear } else { wgpu::FilterMode::Nearest },
 		..Default::default()
 	});
 	let texture_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -101,10 +311,79 @@ pub(crate) fn create_texture_with_data(size: Vec2, device: &wgpu::Device, queue:
 	WTexture {
 		texture: diffuse_texture,
 		layout: texture_bind_group_layout,
-		bind_group: diffuse_bind_group
+		bind_group: diffuse_bind_group,
+		options: *options,
+	}
+}
+
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+/// fills every mip level of `texture` from `data` of its full size, see [`check_texture_data`].
+/// smaller levels are mixed in linear space, or they get darker than the texture
+pub(crate) fn write_texture_levels(texture: &wgpu::Texture, queue: &wgpu::Queue, data: Vec<u8>) {
+	let texture_size = texture.size();
+	let mip_level_count = texture.mip_level_count();
+	write_texture_level(texture, queue, 0, &data);
+	if mip_level_count == 1 {
+		return;
+	}
+	let linear = data.chunks_exact(4).flat_map(|pixel| [srgb_to_linear(pixel[0]), srgb_to_linear(pixel[1]), srgb_to_linear(pixel[2]), pixel[3] as f32 / 255.0]).collect();
+	let Some(mut level) = image::Rgba32FImage::from_raw(texture_size.width, texture_size.height, linear) else {
+		return;
+	};
+	for mip_level in 1..mip_level_count {
+		let mip_size = texture_size.mip_level_size(mip_level, wgpu::TextureDimension::D2);
+		level = image::imageops::resize(&level, mip_size.width, mip_size.height, image::imageops::FilterType::Triangle);
+		let pixels: Vec<u8> = level.pixels().flat_map(|pixel| [linear_to_srgb(pixel[0]), linear_to_srgb(pixel[1]), linear_to_srgb(pixel[2]), (pixel[3] * 255.0).round().clamp(0.0, 255.0) as u8]).collect();
+		write_texture_level(texture, queue, mip_level, &pixels);
 	}
 }
 
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+fn write_texture_level(texture: &wgpu::Texture, queue: &wgpu::Queue, mip_level: u32, data: &[u8]) {
+	let mip_size = texture.size().mip_level_size(mip_level, wgpu::TextureDimension::D2);
+	queue.write_texture(wgpu::ImageCopyTexture {
+		texture,
+		mip_level,
+		origin: wgpu::Origin3d::ZERO,
+		aspect: wgpu::TextureAspect::All,
+	}, data, wgpu::ImageDataLayout {
+		offset: 0,
+		bytes_per_row: Some(mip_size.width * 4),
+		rows_per_image: Some(mip_size.height),
+	}, mip_size);
+}
+
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+/// `image` should have 4 bytes for every pixel, or it can not be uploaded
+pub(crate) fn check_texture_data(image: &Image) -> Result<(), String> {
+	let expected = image.size.x as usize * image.size.y as usize * 4;
+	if image.rgba.len() != expected {
+		return Err(format!("texture {} of {}x{} pixels needs {} bytes, but got {}", image.id, image.size.x, image.size.y, expected, image.rgba.len()));
+	}
+	Ok(())
+}
+
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+fn srgb_to_linear(value: u8) -> f32 {
+	let value = value as f32 / 255.0;
+	if value <= 0.04045 {
+		value / 12.92
+	}else {
+		((value + 0.055) / 1.055).powf(2.4)
+	}
+}
+
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+fn linear_to_srgb(value: f32) -> u8 {
+	let value = value.clamp(0.0, 1.0);
+	let value = if value <= 0.0031308 {
+		value * 12.92
+	}else {
+		1.055 * value.powf(1.0 / 2.4) - 0.055
+	};
+	(value * 255.0).round() as u8
+}
+
 // #[cfg(any(feature = "manager", feature = "baseview_manager"))]
 // pub(crate) fn write_texture_with_data(size: Vec2, texture: &wgpu::Texture, queue: &wgpu::Queue, data: &[u8]) {
 // 	let texture_size = wgpu::Extent3d {
@@ -124,3 +403,58 @@ pub(crate) fn create_texture_with_data(size: Vec2, device: &wgpu::Device, queue:
 // 		rows_per_image: Some(size.y as u32),
 // 	}, texture_size);
 // }
+
+#[cfg(test)]
+mod tests {
+	use super::Playback;
+
+	fn close(a: f32, b: f32) -> bool {
+		(a - b).abs() < 1e-5
+	}
+
+	#[test]
+	fn advance_moves_to_next_frame() {
+		let (mut frame, mut elapsed) = (0, 0.0);
+		let next = Playback::default().advance(&mut frame, &mut elapsed, 0.15, 3, |_| 0.1);
+		assert_eq!(frame, 1);
+		assert!(close(elapsed, 0.05));
+		assert!(close(next.unwrap(), 0.05));
+	}
+
+	#[test]
+	fn advance_stops_on_last_frame_if_not_looping() {
+		let (mut frame, mut elapsed) = (2, 0.0);
+		let next = Playback::default().looping(false).advance(&mut frame, &mut elapsed, 1.0, 3, |_| 0.1);
+		assert_eq!(frame, 2);
+		assert!(close(elapsed, 0.1));
+		assert_eq!(next, None);
+	}
+
+	#[test]
+	fn advance_loops_at_most_one_round() {
+		let (mut frame, mut elapsed) = (0, 0.0);
+		let next = Playback::default().advance(&mut frame, &mut elapsed, 10.0, 3, |_| 0.1);
+		assert_eq!(frame, 0);
+		assert!(close(elapsed, 0.1));
+		assert!(close(next.unwrap(), 0.0));
+	}
+
+	#[test]
+	fn advance_scales_by_speed() {
+		let (mut frame, mut elapsed) = (0, 0.0);
+		let next = Playback::default().speed(2.0).advance(&mut frame, &mut elapsed, 0.04, 3, |_| 0.1);
+		assert_eq!(frame, 0);
+		assert!(close(elapsed, 0.08));
+		assert!(close(next.unwrap(), 0.01));
+	}
+
+	#[test]
+	fn advance_does_nothing_when_paused() {
+		let (mut frame, mut elapsed) = (1, 0.02);
+		for playback in [Playback::default().playing(false), Playback::default().speed(0.0)] {
+			assert_eq!(playback.advance(&mut frame, &mut elapsed, 1.0, 3, |_| 0.1), None);
+			assert_eq!(frame, 1);
+			assert!(close(elapsed, 0.02));
+		}
+	}
+}
@@ -0,0 +1,348 @@
+//! atlas of small textures in the wgpu renderer.
+//!
+//! textures created by [`crate::Ui::create_texture`] and so on without mipmaps and no larger than [`PACK_MAX_SIZE`] are packed into shared pages,
+//! each page holds textures of the same [`TextureOptions`], so images next to each other share one bind group.
+//! texture ids stay the same, the renderer remaps texture coordinates into the page.
+//!
+//! pages are packed by shelves like the glyph atlas. when no page has room, pages with removed textures are repacked, then the last page grows, then a new page is added.
+//! pixels are only kept on gpu, repacking copies them between pages.
+
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use std::collections::HashMap;
+use crate::state::WTexture;
+use crate::texture::create_texture_with_options;
+use crate::texture::Image;
+use crate::texture::TextureOptions;
+
+/// textures with both sides no larger than this are packed
+const PACK_MAX_SIZE: u32 = 256;
+/// edge pixels repeated around each texture, avoid sampling neighbours
+const PADDING: u32 = 1;
+const PAGE_START_SIZE: u32 = 512;
+const PAGE_MAX_SIZE: u32 = 4096;
+/// texture id of a page is this followed by its index
+const PAGE_PREFIX: &str = "\u{0}atlas:";
+
+struct Shelf {
+	y: u32,
+	height: u32,
+	x: u32,
+}
+
+/// places rects in a square of `size`, row by row
+struct Shelves {
+	size: u32,
+	shelves: Vec<Shelf>,
+}
+
+struct Page {
+	texture: WTexture,
+	/// every texture on this page is sampled by these
+	options: TextureOptions,
+	shelves: Shelves,
+	/// pixels taken by removed textures, reclaimed by repacking
+	freed: u32,
+}
+
+#[derive(Clone, Copy)]
+struct Entry {
+	page: usize,
+	/// left top inside padding, in pixel
+	position: [u32; 2],
+	/// in pixel, without padding
+	size: [u32; 2],
+}
+
+pub(crate) struct TextureAtlas {
+	pages: Vec<Page>,
+	entries: HashMap<String, Entry>,
+	max_size: u32,
+}
+
+impl Shelves {
+	fn new(size: u32) -> Self {
+		Self {
+			size,
+			shelves: vec!(),
+		}
+	}
+
+	/// find a place for a texture, returns left top inside padding
+	fn allocate(&mut self, width: u32, height: u32) -> Option<[u32; 2]> {
+		let width = width + PADDING * 2;
+		let height = height + PADDING * 2;
+		let size = self.size;
+		if let Some(shelf) = self.shelves.iter_mut().find(|shelf| shelf.height >= height && shelf.height <= height * 2 && shelf.x + width <= size) {
+			let position = [shelf.x + PADDING, shelf.y + PADDING];
+			shelf.x += width;
+			return Some(position);
+		}
+		let y = self.shelves.last().map(|shelf| shelf.y + shelf.height).unwrap_or(0);
+		if y + height > size || width > size {
+			return None;
+		}
+		self.shelves.push(Shelf { y, height, x: width });
+		Some([PADDING, y + PADDING])
+	}
+}
+
+impl Page {
+	fn new(device: &wgpu::Device, queue: &wgpu::Queue, size: u32, options: TextureOptions) -> Self {
+		Self {
+			texture: create_texture_with_options(Vec2::same(size as f32), device, queue, vec![0; (size * size * 4) as usize], &options),
+			options,
+			shelves: Shelves::new(size),
+			freed: 0,
+		}
+	}
+
+	fn write(&self, queue: &wgpu::Queue, position: [u32; 2], image: &Image) {
+		let [width, height] = image_size(image);
+		let padded_width = width + PADDING * 2;
+		let padded_height = height + PADDING * 2;
+		// edge pixels are repeated into padding
+		let mut rgba = vec![0u8; (padded_width * padded_height * 4) as usize];
+		for y in 0..padded_height {
+			let source_y = y.saturating_sub(PADDING).min(height - 1);
+			for x in 0..padded_width {
+				let source_x = x.saturating_sub(PADDING).min(width - 1);
+				let source = ((source_y * width + source_x) * 4) as usize;
+				let target = ((y * padded_width + x) * 4) as usize;
+				rgba[target..target + 4].copy_from_slice(&image.rgba[source..source + 4]);
+			}
+		}
+		queue.write_texture(wgpu::ImageCopyTexture {
+			texture: &self.texture.texture,
+			mip_level: 0,
+			origin: wgpu::Origin3d { x: position[0] - PADDING, y: position[1] - PADDING, z: 0 },
+			aspect: wgpu::TextureAspect::All,
+		}, &rgba, wgpu::ImageDataLayout {
+			offset: 0,
+			bytes_per_row: Some(padded_width * 4),
+			rows_per_image: Some(padded_height),
+		}, wgpu::Extent3d {
+			width: padded_width,
+			height: padded_height,
+			depth_or_array_layers: 1,
+		});
+	}
+}
+
+impl TextureAtlas {
+	pub(crate) fn new(device: &wgpu::Device) -> Self {
+		Self {
+			pages: vec!(),
+			entries: HashMap::new(),
+			max_size: device.limits().max_texture_dimension_2d.min(PAGE_MAX_SIZE),
+		}
+	}
+
+	/// pack `image` replacing the one with same id, returns `image` back if it should have its own texture
+	pub(crate) fn pack(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, image: Image) -> Option<Image> {
+		if let Some(entry) = self.entries.get(&image.id) {
+			// changed in place when size and options are the same
+			let page = &self.pages[entry.page];
+			if entry.size == image_size(&image) && page.options == image.options && is_packable(&image) {
+				page.write(queue, entry.position, &image);
+				return None;
+			}
+		}
+		self.remove(&image.id);
+		if !is_packable(&image) {
+			return Some(image);
+		}
+		let pages: Vec<usize> = (0..self.pages.len()).filter(|index| self.pages[*index].options == image.options).collect();
+		for index in pages.iter().copied() {
+			if self.place_on(queue, index, &image) {
+				return None;
+			}
+		}
+		// make room by repacking, then growing, then a new page
+		for index in pages.iter().copied() {
+			if self.pages[index].freed > 0 {
+				let size = self.pages[index].shelves.size;
+				self.repack(device, queue, index, size);
+				if self.place_on(queue, index, &image) {
+					return None;
+				}
+			}
+		}
+		if let Some(index) = pages.last().copied() {
+			if self.pages[index].shelves.size < self.max_size {
+				let size = (self.pages[index].shelves.size * 2).min(self.max_size);
+				self.repack(device, queue, index, size);
+				if self.place_on(queue, index, &image) {
+					return None;
+				}
+			}
+		}
+		self.pages.push(Page::new(device, queue, PAGE_START_SIZE.min(self.max_size), image.options));
+		if self.place_on(queue, self.pages.len() - 1, &image) {
+			None
+		}else {
+			Some(image)
+		}
+	}
+
+	pub(crate) fn remove(&mut self, id: &str) {
+		self.remove_entry(id);
+	}
+
+	/// move texture `id` out of its page into a texture of its own, for shaders sampling whole textures
+	pub(crate) fn take_out(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, id: &str) -> Option<WTexture> {
+		let entry = self.remove_entry(id)?;
+		let [width, height] = entry.size;
+		let page = &self.pages[entry.page];
+		let texture = create_texture_with_options(Vec2::new(width as f32, height as f32), device, queue, vec![0; (width * height * 4) as usize], &page.options);
+		let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+			label: Some("Atlas Take Out Encoder"),
+		});
+		copy_region(&mut encoder, &page.texture.texture, entry.position, &texture.texture, [0, 0], entry.size);
+		queue.submit(Some(encoder.finish()));
+		Some(texture)
+	}
+
+	/// texture id of the page containing texture `id` and where it is in the page, normalized
+	pub(crate) fn region(&self, id: &str) -> Option<(String, Area)> {
+		let entry = self.entries.get(id)?;
+		let page_size = self.pages[entry.page].shelves.size as f32;
+		let position = Vec2::new(entry.position[0] as f32, entry.position[1] as f32);
+		let [width, height] = entry.size;
+		let region = Area::new(position / page_size, (position + Vec2::new(width as f32, height as f32)) / page_size);
+		Some((format!("{}{}", PAGE_PREFIX, entry.page), region))
+	}
+
+	/// page by texture id given by [`Self::region`]
+	pub(crate) fn page(&self, id: &str) -> Option<&WTexture> {
+		let index: usize = id.strip_prefix(PAGE_PREFIX)?.parse().ok()?;
+		self.pages.get(index).map(|page| &page.texture)
+	}
+
+	fn remove_entry(&mut self, id: &str) -> Option<Entry> {
+		let entry = self.entries.remove(id)?;
+		let [width, height] = entry.size;
+		self.pages[entry.page].freed += (width + PADDING * 2) * (height + PADDING * 2);
+		Some(entry)
+	}
+
+	fn place_on(&mut self, queue: &wgpu::Queue, index: usize, image: &Image) -> bool {
+		let size = image_size(image);
+		let Some(position) = self.pages[index].shelves.allocate(size[0], size[1]) else {
+			return false;
+		};
+		self.pages[index].write(queue, position, image);
+		self.entries.insert(image.id.clone(), Entry {
+			page: index,
+			position,
+			size,
+		});
+		true
+	}
+
+	/// pack textures of page `index` again into a new page of `size`, those can not fit go to other pages of same options.
+	/// pixels are copied from the old page on gpu
+	fn repack(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, index: usize, size: u32) {
+		let mut moved: Vec<(String, Entry)> = self.entries.iter().filter(|(_, entry)| entry.page == index).map(|(id, entry)| (id.clone(), *entry)).collect();
+		// taller first packs shelves tighter
+		moved.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.size[1]));
+		let options = self.pages[index].options;
+		let old = std::mem::replace(&mut self.pages[index], Page::new(device, queue, size, options));
+		let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+			label: Some("Atlas Repack Encoder"),
+		});
+		for (id, entry) in moved {
+			let [width, height] = entry.size;
+			let candidates: Vec<usize> = std::iter::once(index).chain((0..self.pages.len()).filter(|other| *other != index && self.pages[*other].options == options)).collect();
+			let mut placed = None;
+			for page in candidates {
+				if let Some(position) = self.pages[page].shelves.allocate(width, height) {
+					placed = Some((page, position));
+					break;
+				}
+			}
+			if placed.is_none() {
+				self.pages.push(Page::new(device, queue, PAGE_START_SIZE.min(self.max_size), options));
+				let page = self.pages.len() - 1;
+				placed = self.pages[page].shelves.allocate(width, height).map(|position| (page, position));
+			}
+			let Some((page, position)) = placed else {
+				// larger than a page, can not happen as long as `PACK_MAX_SIZE` is smaller than pages
+				self.entries.remove(&id);
+				continue;
+			};
+			copy_region(&mut encoder, &old.texture.texture, padded(entry.position), &self.pages[page].texture.texture, padded(position), [width + PADDING * 2, height + PADDING * 2]);
+			self.entries.insert(id, Entry {
+				page,
+				position,
+				size: entry.size,
+			});
+		}
+		queue.submit(Some(encoder.finish()));
+	}
+}
+
+/// left top of the padding around a texture at `position`
+fn padded(position: [u32; 2]) -> [u32; 2] {
+	[position[0] - PADDING, position[1] - PADDING]
+}
+
+fn copy_region(encoder: &mut wgpu::CommandEncoder, from: &wgpu::Texture, from_position: [u32; 2], to: &wgpu::Texture, to_position: [u32; 2], size: [u32; 2]) {
+	encoder.copy_texture_to_texture(wgpu::ImageCopyTexture {
+		texture: from,
+		mip_level: 0,
+		origin: wgpu::Origin3d { x: from_position[0], y: from_position[1], z: 0 },
+		aspect: wgpu::TextureAspect::All,
+	}, wgpu::ImageCopyTexture {
+		texture: to,
+		mip_level: 0,
+		origin: wgpu::Origin3d { x: to_position[0], y: to_position[1], z: 0 },
+		aspect: wgpu::TextureAspect::All,
+	}, wgpu::Extent3d {
+		width: size[0],
+		height: size[1],
+		depth_or_array_layers: 1,
+	});
+}
+
+/// small enough and without mipmaps, which would mix neighbours in a page
+fn is_packable(image: &Image) -> bool {
+	let [width, height] = image_size(image);
+	image.options.atlas && !image.options.mipmaps && width > 0 && height > 0 && width <= PACK_MAX_SIZE && height <= PACK_MAX_SIZE && image.rgba.len() >= (width * height * 4) as usize
+}
+
+fn image_size(image: &Image) -> [u32; 2] {
+	[image.size.x as u32, image.size.y as u32]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn shelves_fill_rows_before_opening_new_ones() {
+		let mut shelves = Shelves::new(64);
+		assert_eq!(shelves.allocate(10, 10), Some([1, 1]));
+		assert_eq!(shelves.allocate(10, 8), Some([13, 1]));
+		// too short for the first shelf, more than twice lower
+		assert_eq!(shelves.allocate(10, 3), Some([1, 13]));
+		assert_eq!(shelves.shelves.len(), 2);
+	}
+
+	#[test]
+	fn shelves_open_new_row_when_full() {
+		let mut shelves = Shelves::new(32);
+		assert_eq!(shelves.allocate(14, 6), Some([1, 1]));
+		assert_eq!(shelves.allocate(14, 6), Some([17, 1]));
+		assert_eq!(shelves.allocate(14, 6), Some([1, 9]));
+	}
+
+	#[test]
+	fn shelves_reject_what_does_not_fit() {
+		let mut shelves = Shelves::new(32);
+		assert_eq!(shelves.allocate(31, 4), None);
+		assert_eq!(shelves.allocate(30, 20), Some([1, 1]));
+		assert_eq!(shelves.allocate(4, 20), None);
+		assert_eq!(shelves.allocate(4, 8), Some([1, 23]));
+	}
+}
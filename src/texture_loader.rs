@@ -0,0 +1,321 @@
+//! decoding textures on rayon threads, see [`crate::Ui::load_texture`]. wasm has no threads, textures are decoded right away there.
+//!
+//! finished textures are picked up at the beginning of next frame and sent as [`crate::event::OutputEvent::TextureChange`].
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::SystemTime;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use anyhow::Result;
+use nablo_shape::math::Vec2;
+use resvg::usvg::TreeParsing;
+use crate::texture::Image;
+use crate::texture::TextureOptions;
+use crate::texture::TextureStatus;
+use crate::ui::texture;
+use crate::ui::texture_svg;
+
+/// bytes of decoded files kept by default, see [`crate::Ui::set_texture_cache_limit`]
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) const DEFAULT_CACHE_LIMIT: usize = 256 * 1024 * 1024;
+
+#[cfg(not(target_arch = "wasm32"))]
+struct CacheEntry {
+	modified: SystemTime,
+	image: Image,
+	/// `TextureLoader::next_use` when last read, the smallest one is dropped first
+	last_use: u64,
+}
+
+pub(crate) struct TextureLoader {
+	/// textures loading or failed, with the load they belong to. ready ones are removed
+	statuses: HashMap<String, (u64, TextureStatus)>,
+	/// results not picked up yet
+	finished: Vec<(u64, String, Result<Image, String>)>,
+	/// called when a result is ready, so a waiting event loop draws next frame
+	waker: Option<Box<dyn Fn() + Send>>,
+	/// decoded files by path
+	#[cfg(not(target_arch = "wasm32"))]
+	cache: HashMap<PathBuf, CacheEntry>,
+	#[cfg(not(target_arch = "wasm32"))]
+	cache_bytes: usize,
+	#[cfg(not(target_arch = "wasm32"))]
+	cache_limit: usize,
+	#[cfg(not(target_arch = "wasm32"))]
+	next_use: u64,
+	next_load: u64,
+}
+
+impl Default for TextureLoader {
+	fn default() -> Self {
+		Self {
+			statuses: HashMap::new(),
+			finished: vec!(),
+			waker: None,
+			#[cfg(not(target_arch = "wasm32"))]
+			cache: HashMap::new(),
+			#[cfg(not(target_arch = "wasm32"))]
+			cache_bytes: 0,
+			#[cfg(not(target_arch = "wasm32"))]
+			cache_limit: DEFAULT_CACHE_LIMIT,
+			#[cfg(not(target_arch = "wasm32"))]
+			next_use: 0,
+			next_load: 0,
+		}
+	}
+}
+
+impl TextureLoader {
+	pub(crate) fn status(&self, id: &str) -> Option<TextureStatus> {
+		self.statuses.get(id).map(|(_, status)| status.clone())
+	}
+
+	pub(crate) fn is_loading(&self) -> bool {
+		self.statuses.values().any(|(_, status)| status == &TextureStatus::Loading)
+	}
+
+	/// forget texture `id`, its loading result will be dropped
+	pub(crate) fn cancel(&mut self, id: &str) {
+		self.statuses.remove(id);
+	}
+
+	pub(crate) fn has_waker(&self) -> bool {
+		self.waker.is_some()
+	}
+
+	pub(crate) fn set_waker(&mut self, waker: Box<dyn Fn() + Send>) {
+		self.waker = Some(waker);
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	pub(crate) fn clear_cache(&mut self) {
+		self.cache.clear();
+		self.cache_bytes = 0;
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	pub(crate) fn set_cache_limit(&mut self, limit: usize) {
+		self.cache_limit = limit;
+		self.shrink_cache();
+	}
+
+	/// decoded file at `path` if it was not modified since
+	#[cfg(not(target_arch = "wasm32"))]
+	fn cached(&mut self, path: &PathBuf, modified: SystemTime) -> Option<Image> {
+		let entry = self.cache.get_mut(path)?;
+		if entry.modified != modified {
+			return None;
+		}
+		entry.last_use = self.next_use;
+		self.next_use += 1;
+		Some(entry.image.clone())
+	}
+
+	/// keep decoded `image`, least recently used files are dropped once over the limit
+	#[cfg(not(target_arch = "wasm32"))]
+	fn cache(&mut self, path: PathBuf, modified: SystemTime, image: Image) {
+		// larger than the whole cache, keeping it would only push everything else out
+		if image.rgba.len() > self.cache_limit {
+			return;
+		}
+		self.cache_bytes += image.rgba.len();
+		let entry = CacheEntry {
+			modified,
+			image,
+			last_use: self.next_use,
+		};
+		self.next_use += 1;
+		if let Some(old) = self.cache.insert(path, entry) {
+			self.cache_bytes -= old.image.rgba.len();
+		}
+		self.shrink_cache();
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	fn shrink_cache(&mut self) {
+		while self.cache_bytes > self.cache_limit {
+			let Some(path) = self.cache.iter().min_by_key(|(_, entry)| entry.last_use).map(|(path, _)| path.clone()) else {
+				break;
+			};
+			if let Some(entry) = self.cache.remove(&path) {
+				self.cache_bytes -= entry.image.rgba.len();
+			}
+		}
+	}
+
+	fn finish(&mut self, load: u64, id: String, result: Result<Image, String>) {
+		self.finished.push((load, id, result));
+		if let Some(waker) = &self.waker {
+			waker();
+		}
+	}
+
+	/// textures decoded since last call, failed ones are marked as [`TextureStatus::Failed`]
+	pub(crate) fn take_finished(&mut self) -> Vec<Image> {
+		let mut images = vec!();
+		for (load, id, result) in std::mem::take(&mut self.finished) {
+			// deleted or loaded again meanwhile
+			if !matches!(self.statuses.get(&id), Some((current, TextureStatus::Loading)) if *current == load) {
+				continue;
+			}
+			match result {
+				Ok(image) => {
+					self.statuses.remove(&id);
+					images.push(image);
+				},
+				Err(error) => {
+					self.statuses.insert(id, (load, TextureStatus::Failed(error)));
+				},
+			}
+		}
+		images
+	}
+
+	fn begin(&mut self, id: &str) -> u64 {
+		let load = self.next_load;
+		self.next_load += 1;
+		self.statuses.insert(id.to_string(), (load, TextureStatus::Loading));
+		load
+	}
+}
+
+/// decode `bytes` on rayon pool as texture `id`, or right away on wasm
+pub(crate) fn load_bytes(loader: &Arc<Mutex<TextureLoader>>, bytes: Vec<u8>, id: String, options: TextureOptions) {
+	let load = loader.lock().unwrap().begin(&id);
+	let loader = loader.clone();
+	let task = move || {
+		let result = decode(&bytes, id.clone()).map(|image| Image {
+			options,
+			..image
+		}).map_err(|error| error.to_string());
+		loader.lock().unwrap().finish(load, id, result);
+	};
+	#[cfg(target_arch = "wasm32")]
+	task();
+	#[cfg(not(target_arch = "wasm32"))]
+	rayon::spawn(task);
+}
+
+/// read and decode file at `path` on rayon pool as texture `id`, files not modified since last time are not decoded again
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn load_path(loader: &Arc<Mutex<TextureLoader>>, path: PathBuf, id: String, options: TextureOptions) {
+	let load = loader.lock().unwrap().begin(&id);
+	let loader = loader.clone();
+	rayon::spawn(move || {
+		let result = (|| -> Result<Image> {
+			// some platforms have no modification time, never cached there
+			let modified = std::fs::metadata(&path)?.modified().ok();
+			if let Some(modified) = modified {
+				if let Some(image) = loader.lock().unwrap().cached(&path, modified) {
+					return Ok(image);
+				}
+			}
+			let image = decode(&std::fs::read(&path)?, String::new())?;
+			if let Some(modified) = modified {
+				loader.lock().unwrap().cache(path, modified, image.clone());
+			}
+			Ok(image)
+		})().map(|image| Image {
+			id: id.clone(),
+			options,
+			..image
+		}).map_err(|error| error.to_string());
+		loader.lock().unwrap().finish(load, id, result);
+	});
+}
+
+/// png, jpeg, webp or anything else `image` supports, otherwise svg rendered in its own size
+fn decode(bytes: &[u8], id: String) -> Result<Image> {
+	if image::guess_format(bytes).is_ok() {
+		return texture(bytes, id);
+	}
+	let size = resvg::usvg::Tree::from_data(bytes, &resvg::usvg::Options::default())?.size;
+	texture_svg(bytes, Vec2::new(size.width().ceil(), size.height().ceil()), id)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn image(id: &str, bytes: usize) -> Image {
+		Image {
+			rgba: vec!(0; bytes),
+			id: id.to_string(),
+			size: Vec2::new(bytes as f32 / 4.0, 1.0),
+			options: TextureOptions::default(),
+		}
+	}
+
+	#[test]
+	fn take_finished_returns_loaded_images() {
+		let mut loader = TextureLoader::default();
+		let load = loader.begin("a");
+		loader.finish(load, "a".to_string(), Ok(image("a", 4)));
+		let images = loader.take_finished();
+		assert_eq!(images.len(), 1);
+		assert_eq!(images[0].id, "a");
+		assert_eq!(loader.status("a"), None);
+		assert!(loader.take_finished().is_empty());
+	}
+
+	#[test]
+	fn take_finished_marks_failed_loads() {
+		let mut loader = TextureLoader::default();
+		let load = loader.begin("a");
+		loader.finish(load, "a".to_string(), Err("broken".to_string()));
+		assert!(loader.take_finished().is_empty());
+		assert_eq!(loader.status("a"), Some(TextureStatus::Failed("broken".to_string())));
+		assert!(!loader.is_loading());
+	}
+
+	#[test]
+	fn take_finished_drops_canceled_and_outdated_loads() {
+		let mut loader = TextureLoader::default();
+		let canceled = loader.begin("a");
+		loader.cancel("a");
+		loader.finish(canceled, "a".to_string(), Ok(image("a", 4)));
+		assert!(loader.take_finished().is_empty());
+
+		let outdated = loader.begin("b");
+		let current = loader.begin("b");
+		loader.finish(outdated, "b".to_string(), Ok(image("b", 4)));
+		assert!(loader.take_finished().is_empty());
+		assert!(loader.is_loading());
+		loader.finish(current, "b".to_string(), Ok(image("b", 8)));
+		let images = loader.take_finished();
+		assert_eq!(images.len(), 1);
+		assert_eq!(images[0].rgba.len(), 8);
+	}
+
+	#[test]
+	fn finish_wakes() {
+		let woken = Arc::new(Mutex::new(0));
+		let mut loader = TextureLoader::default();
+		let counter = woken.clone();
+		loader.set_waker(Box::new(move || *counter.lock().unwrap() += 1));
+		let load = loader.begin("a");
+		loader.finish(load, "a".to_string(), Ok(image("a", 4)));
+		assert_eq!(*woken.lock().unwrap(), 1);
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	#[test]
+	fn cache_drops_least_recently_used() {
+		let mut loader = TextureLoader::default();
+		loader.set_cache_limit(8);
+		let time = SystemTime::UNIX_EPOCH;
+		loader.cache(PathBuf::from("a"), time, image("a", 4));
+		loader.cache(PathBuf::from("b"), time, image("b", 4));
+		assert!(loader.cached(&PathBuf::from("a"), time).is_some());
+		loader.cache(PathBuf::from("c"), time, image("c", 4));
+		assert!(loader.cached(&PathBuf::from("b"), time).is_none());
+		assert!(loader.cached(&PathBuf::from("a"), time).is_some());
+		assert!(loader.cached(&PathBuf::from("c"), time).is_some());
+		assert_eq!(loader.cache_bytes, 8);
+		// modified since
+		assert!(loader.cached(&PathBuf::from("a"), time + std::time::Duration::from_secs(1)).is_none());
+	}
+}
@@ -10,19 +10,36 @@ use std::path::Path;
 use image::io::Reader;
 use resvg::usvg::TreeParsing;
 use crate::OutputEvent;
+use crate::font::Script;
+use crate::font::SharedFontBook;
+use crate::font::with_font_book;
+use crate::text_layout::layout;
+use crate::text_layout::Galley;
+use crate::text_layout::TextWrap;
+use crate::text_layout::TextRow;
+use crate::text_layout::is_rtl_language;
 use crate::texture::Image;
+use crate::texture::TextureOptions;
+use crate::texture::TextureStatus;
+use crate::texture::Playback;
+use crate::animated_texture::AnimatedTexture;
 use image::GenericImageView;
 use crate::Shapes;
 use crate::prelude::Empty;
 use crate::MemoryTemp;
+use crate::OffscreenTemp;
+use crate::event::Offscreen;
 use rayon::prelude::*;
 use crate::parse_json;
 use crate::to_json;
 use crate::Metadata;
 use crate::Layout;
-#[cfg(feature = "vertexs")]
-use nablo_shape::shape::ShapeElement;
+use crate::integrator::RenderStats;
+use crate::integrator::ShapeExtra;
 use nablo_shape::shape::Painter;
+use nablo_shape::prelude::shape_elements::Color;
+use nablo_shape::shape::Shape;
+use nablo_shape::shape::ShapeElement;
 use time::Duration;
 use crate::Event;
 use crate::Response;
@@ -34,6 +51,7 @@ use nablo_shape::math::Vec2;
 use crate::Style;
 use crate::Instant;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use anyhow::Result;
 use std::sync::Mutex;
 use std::sync::Arc;
@@ -58,13 +76,25 @@ impl Default for Ui {
 			paint_style: PaintStyle::default(),
 			layout: Layout::default(),
 			output_events: vec!(),
-			texture_id: Arc::new(Mutex::new(vec!())),
+			texture_id: Arc::new(Mutex::new(HashMap::new())),
+			texture_loader: Arc::new(Mutex::new(Default::default())),
+			animated_textures: Arc::new(Mutex::new(HashMap::new())),
+			shown_images: Arc::new(Mutex::new(HashSet::new())),
+			changed_textures: Arc::new(Mutex::new(HashSet::new())),
 			offset: Vec2::ZERO,
 			parent_area: None,
 			start_position: Vec2::ZERO,
 			window_crossed: Area::new_with_origin([640.0,480.0].into()),
 			// scale_factor: 1.0,
-			collapse_times: 0
+			collapse_times: 0,
+			render_stats: RenderStats::default(),
+			repaint_after: None,
+			feather: 0.0,
+			offscreens: Arc::new(Mutex::new(HashMap::new())),
+			fonts: SharedFontBook::default(),
+			text_rows: HashMap::new(),
+			#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+			paint_callbacks: Arc::new(Mutex::new(HashMap::new())),
 		}
 	}
 }
@@ -125,8 +155,9 @@ impl Ui {
 		drop(memory);
 		if need_draw {
 			let mut shapes = self.painter();
+			let text_extras = self.shape.text_extras.len();
 			widget.draw(self, &response, &mut shapes);
-			self.shape.append(shapes);
+			self.shape.append_painter(shapes, vec!(), text_extras);
 		}
 		let memory = self.memory.lock().unwrap();
 		memory.get(&id).unwrap().response.clone()
@@ -176,19 +207,21 @@ impl Ui {
 		let is_clickable = container.is_clickable(self);
 		let is_dragable = container.is_dragable(self);
 		let response = self.response_update(size, input_id.clone(), is_clickable, is_dragable);
+		let text_extras = self.shape.text_extras.len();
 		let if_show = container.begin(self, &mut painter, &response, &input_id);
 		let style = painter.style().clone();
 		let offset = painter.offset;
-		self.shape.append(painter);
+		self.shape.append_painter(painter, vec!(), text_extras);
 		if if_show {
 			let return_value = InnerResponse {
 				response,
 				..self.sub_ui(size, id, style.clone(), offset, container, inner_widget)
 			};
-			let split = self.shape.raw_shape.split_off(shapes_len);
+			let (split, extras) = self.shape.split_off(shapes_len);
 			let mut painter = Painter::new(&size, split, style);
+			let text_extras = self.shape.text_extras.len();
 			container.end(self, &mut painter, &return_value, &input_id);
-			self.shape.append(painter);
+			self.shape.append_painter(painter, extras, text_extras);
 			return_value
 		}else {
 			InnerResponse {
@@ -217,6 +250,21 @@ impl Ui {
 		self.add_widget(id, Empty::EMPTY , response)
 	}
 
+	/// take clicks inside `area` on `layer` before widgets under it, for popups drawn outside of their widget.
+	/// call it every frame the popup is shown
+	pub(crate) fn block_input(&mut self, id: String, area: Area, layer: Layer) {
+		let response = Response {
+			area,
+			metadata: Metadata::new(layer, true, false),
+			..Default::default()
+		};
+		self.add_widget(id.clone(), Empty::EMPTY, response);
+		// popups are not clipped by their parents
+		if let Some(temp) = self.memory.lock().unwrap().get_mut(&id) {
+			temp.update_area = area;
+		}
+	}
+
 	/// get where should we print
 	pub fn available_position(&self) -> Vec2 {
 		self.available_position
@@ -239,6 +287,78 @@ impl Ui {
 		back
 	}
 
+	/// draw calls, vertices and bytes uploaded of last frame, reported by the window manager.
+	/// always zero if nobody reports them, see [`crate::integrator::Integrator::report_render_stats`]
+	pub fn render_stats(&self) -> RenderStats {
+		self.render_stats
+	}
+
+	/// run next frame after `after` even if there's no input. default manager stops drawing when nothing changed,
+	/// so use this for timers or animations not changing every frame, such as a custom shader using `uniforms.time`.
+	///
+	/// the shortest one is used if this is called many times in a frame.
+	pub fn request_repaint_after(&mut self, after: Duration) {
+		self.repaint_after = Some(self.repaint_after.map_or(after, |current| current.min(after)));
+	}
+
+	/// run next frame as soon as possible, see [`Self::request_repaint_after`]
+	pub fn request_repaint(&mut self) {
+		self.request_repaint_after(Duration::ZERO);
+	}
+
+	/// draw `contents` into texture `id` of `size` pixels instead of the window, show it as any other texture such as `ui.add(Image::new(id, size))`.
+	///
+	/// the texture is redrawn only when shapes inside changed, and deleted once this is not called in a frame.
+	/// `contents` gets no input, and ids inside are apart from the ones outside.
+	pub fn offscreen<R>(&mut self, id: impl Into<String>, size: Vec2, contents: impl FnOnce(&mut Ui) -> R) -> R {
+		let id = id.into();
+		// taken out, so `contents` can have offscreens too
+		let mut temp = self.offscreens.lock().unwrap().remove(&id).unwrap_or_default();
+		let inner = &mut temp.ui;
+		inner.window = Area::new_with_origin(size);
+		inner.window_crossed = inner.window;
+		inner.style = self.style.clone();
+		inner.language = self.language.clone();
+		inner.texture_id = self.texture_id.clone();
+		inner.texture_loader = self.texture_loader.clone();
+		inner.animated_textures = self.animated_textures.clone();
+		inner.shown_images = self.shown_images.clone();
+		inner.changed_textures = self.changed_textures.clone();
+		inner.feather = self.feather;
+		inner.fonts = self.fonts.clone();
+		inner.update();
+		let return_value = contents(inner);
+		inner.raw_shape();
+		inner.remove_unused_offscreens();
+		// textures inside changed, such as animated textures or offscreens inside
+		let is_animated = {
+			let changed_textures = self.changed_textures.lock().unwrap();
+			!changed_textures.is_empty() && inner.shape.raw_shape.iter().any(|shape| matches!(&shape.shape, ShapeElement::Image(image) if changed_textures.contains(&image.id)))
+		};
+		if is_animated || temp.size != Some(size) || inner.shape.raw_shape != temp.shapes || inner.shape.extras != temp.extras {
+			temp.size = Some(size);
+			temp.shapes = inner.shape.raw_shape.clone();
+			temp.extras = inner.shape.extras.clone();
+			self.texture_id.lock().unwrap().insert(id.clone(), (size, TextureOptions::default()));
+			self.changed_textures.lock().unwrap().insert(id.clone());
+			self.output_events.push(OutputEvent::Offscreen(Offscreen {
+				id: id.clone(),
+				size,
+				shapes: temp.shapes.clone(),
+				extras: temp.extras.clone(),
+				feather: self.feather,
+			}));
+		}
+		self.output_events.append(&mut inner.output_events);
+		if let Some(after) = inner.repaint_after {
+			self.request_repaint_after(after);
+		}
+		inner.clear();
+		temp.is_used = true;
+		self.offscreens.lock().unwrap().insert(id, temp);
+		return_value
+	}
+
 	/// how long have passed since last frame?
 	pub fn delay(&self) -> Duration {
 		self.last_frame.elapsed()
@@ -310,6 +430,11 @@ impl Ui {
 		self.language.clone()
 	}
 
+	/// is current language written right to left, language should be a tag like `ar` or `he-IL` to be recognized.
+	pub fn is_rtl(&self) -> bool {
+		is_rtl_language(&self.language)
+	}
+
 	/// to change current language, for multi language support. feel free to use your way to stand for a language.
 	pub fn change_language(&mut self, language: String){
 		self.language = language;
@@ -320,6 +445,12 @@ impl Ui {
 		self.window_crossed
 	}
 
+	/// width in pixel of the fading edge added around vector shapes when they are turned into vertexs, smoothing them without msaa.
+	/// 0.0 by default for no feathering, 1.0 is usually enough. images and texts are not affected.
+	pub fn set_feather(&mut self, feather: f32) {
+		self.feather = feather.max(0.0);
+	}
+
 	/// change scale factor
 	pub fn scale_factor(&mut self, scale_factor: f32) {
 		self.paint_style.scale_factor = scale_factor
@@ -336,6 +467,8 @@ impl Ui {
 	}
 
 	/// change current shader to indexed shader, you can use [`Self::registrate_shader`] to registrate / modified one
+	///
+	/// this affects every shape without a shader of its own, use [`Self::with_shader`] to draw only some of them with a shader.
 	pub fn change_current_shader(&mut self, id: Option<String>) {
 		if let Some(inner) = id {
 			self.send_output_event(OutputEvent::ChangeShader(Some(inner)));
@@ -361,6 +494,8 @@ impl Ui {
 	///     @builtin(position) clip_position: vec4f,
 	///     @location(0) color: vec4f,
 	///     @location(1) is_texture: u32,
+	///     // fill color of the image, multiply it with sampled color to tint textures
+	///     @location(2) tint: vec4f,
 	/// }
 	/// ```
 	///
@@ -391,10 +526,12 @@ impl Ui {
 	/// var s_diffuse: sampler;
 	/// ```
 	///
+	/// your own uniform block and textures are at group 2, see [`Self::set_shader_uniform`] and [`Self::set_shader_textures`].
+	///
 	/// Note: *dont* call this every frame, or `nablo` will be unstandably lagging.
 	///
-	/// # Panics
-	/// when shader_code is invaild
+	/// default mannager checks the code before using it, you will get a [`Event::ShaderRegistrated`] or a [`Event::ShaderError`] with line and column of each problem in [`Self::events`] next frame.
+	/// a broken shader never replaces a working one with same id.
 	pub fn registrate_shader(&mut self, id: impl Into<String>, shader_code: String) {
 		self.send_output_event(OutputEvent::RegstrateShader(id.into(), shader_code));
 	}
@@ -403,6 +540,109 @@ impl Ui {
 		self.send_output_event(OutputEvent::RemoveShader(id.into()));
 	}
 
+	/// shapes painted inside `add_contents`, including widgets and containers, are drawn by indexed shader.
+	/// `None` draws them by the shader set in [`Self::change_current_shader`].
+	///
+	/// texts are always drawn by the default text pipeline.
+	/// ```no_run
+	/// # use nablo::prelude::*;
+	/// # fn shader_example(ui: &mut Ui) {
+	/// ui.with_shader(Some("wave".to_string()), |ui| {
+	/// 	ui.add(Button::new("waving"));
+	/// });
+	/// # }
+	/// ```
+	pub fn with_shader<R>(&mut self, id: Option<String>, add_contents: impl FnOnce(&mut Self) -> R) -> R {
+		let previous = std::mem::replace(&mut self.shape.shader, id);
+		let back = add_contents(self);
+		self.shape.shader = previous;
+		back
+	}
+
+	/// paint `shapes` by indexed shader, see [`Self::with_shader`]
+	pub fn paint_with_shader(&mut self, id: Option<String>, shapes: impl Into<Vec<Shape>>) {
+		let shapes = shapes.into();
+		let extras = vec!(ShapeExtra {
+			shader: id,
+			..Default::default()
+		}; shapes.len());
+		self.shape.append_with_extras(shapes, extras);
+	}
+
+	/// paint `shapes` with gradients, shadows or backdrop blur, see [`Effect`]. gradients are sized by the bounding box of each shape,
+	/// shadows take the outline of the shape itself.
+	///
+	/// # Example
+	/// ```no_run
+	/// # use nablo::prelude::*;
+	/// # fn effect_example(ui: &mut Ui) {
+	/// let mut painter = ui.painter();
+	/// painter.set_position(Vec2::same(32.0));
+	/// painter.rect(Vec2::new(200.0, 100.0), Vec2::same(8.0));
+	/// ui.paint_with_effects(vec!(
+	/// 	Effect::DropShadow(Shadow::default()),
+	/// 	Effect::Gradient(Gradient::vertical([98, 0, 234, 255], [30, 30, 30, 255])),
+	/// ), painter);
+	/// # }
+	/// ```
+	pub fn paint_with_effects(&mut self, effects: Vec<Effect>, shapes: impl Into<Vec<Shape>>) {
+		let shapes = shapes.into();
+		let extras = vec!(ShapeExtra {
+			shader: self.shape.shader.clone(),
+			effects,
+			..Default::default()
+		}; shapes.len());
+		self.shape.append_with_extras(shapes, extras);
+	}
+
+	/// paint image shapes showing part `uv` of their textures multiplied by `tint`, `uv` is normalized.
+	/// images painted by a [`Painter`] show their whole textures untinted, see [`crate::widgets::Image`] for a widget.
+	pub fn paint_image(&mut self, shapes: impl Into<Vec<Shape>>, uv: Area, tint: impl Into<Color>) {
+		let shapes = shapes.into();
+		let extras = vec!(ShapeExtra {
+			shader: self.shape.shader.clone(),
+			tint: Some(tint.into()),
+			uv: Some(uv),
+			..Default::default()
+		}; shapes.len());
+		self.shape.append_with_extras(shapes, extras);
+	}
+
+	/// set the custom uniform block of indexed shader, declare it in your shader like
+	///
+	/// ```wgsl
+	/// struct Custom {
+	///     color: vec4f,
+	///     strength: f32,
+	/// }
+	///
+	/// @group(2)@binding(0)
+	/// var<uniform> custom: Custom;
+	/// ```
+	///
+	/// `value` should follow the layout rules of wgsl uniforms, such as 16 bytes alignment of `vec4f`.
+	/// values are kept until you set them again, so you only need to call this when they changed.
+	/// blocks never set are zeroed and 256 bytes long.
+	pub fn set_shader_uniform<T: bytemuck::Pod>(&mut self, id: impl Into<String>, value: &T) {
+		self.send_output_event(OutputEvent::ShaderUniform(id.into(), bytemuck::bytes_of(value).to_vec()));
+	}
+
+	/// bind textures created by [`Self::create_texture`] to indexed shader, they can be used as
+	///
+	/// ```wgsl
+	/// @group(2)@binding(1)
+	/// var s_custom: sampler;
+	/// @group(2)@binding(2)
+	/// var t_custom_0: texture_2d<f32>;
+	/// // ... until t_custom_3
+	/// ```
+	///
+	/// these are declared already, you dont need to write them. 
+	/// at most [`crate::MAX_SHADER_TEXTURES`] textures, missing textures are transparent.
+	pub fn set_shader_textures(&mut self, id: impl Into<String>, textures: Vec<String>) {
+		self.send_output_event(OutputEvent::ShaderTextures(id.into(), textures));
+	}
+
 	/// send a output event to host
 	pub fn send_output_event(&mut self, output_event: OutputEvent) {
 		self.output_events.push(output_event);
@@ -432,9 +672,23 @@ impl Ui {
 			paint_style,
 			language: self.language.clone(),
 			texture_id: self.texture_id.clone(),
+			texture_loader: self.texture_loader.clone(),
+			animated_textures: self.animated_textures.clone(),
+			shown_images: self.shown_images.clone(),
+			changed_textures: self.changed_textures.clone(),
+			offscreens: self.offscreens.clone(),
+			fonts: self.fonts.clone(),
+			#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+			paint_callbacks: self.paint_callbacks.clone(),
 			offset,
 			parent_area: Some(self.window_area()), 
 			collapse_times: self.collapse_times + 1, 
+			render_stats: self.render_stats,
+			repaint_after: self.repaint_after,
+			shape: Shapes {
+				shader: self.shape.shader.clone(),
+				..Default::default()
+			},
 			..Default::default()
 		};
 		let return_value = widgets(&mut sub_ui, container);
@@ -456,8 +710,9 @@ impl Ui {
 		self.memory = sub_ui.memory;
 		self.input_state = sub_ui.input_state;
 		self.style = sub_ui.style;
-		self.shape.raw_shape.append(&mut sub_ui.shape.raw_shape);
+		self.shape.append_shapes(&mut sub_ui.shape);
 		self.output_events.append(&mut sub_ui.output_events);
+		self.repaint_after = sub_ui.repaint_after;
 		self.texture_id = sub_ui.texture_id;
 		self.memory_clip = sub_ui.memory_clip;
 		self.memory_clip_total = sub_ui.memory_clip_total;
@@ -550,6 +805,7 @@ impl Ui {
 			Event::Scroll(scroll) => self.input_state.current_scroll = *scroll,
 			Event::ImeEnable => self.input_state.is_ime_on = true,
 			Event::ImeDisable => self.input_state.is_ime_on = false,
+			Event::ShaderRegistrated(_) | Event::ShaderError(_, _) => {},
 			Event::NotSupported => {},
 		};
 		if let Event::NotSupported =  event {}
@@ -559,24 +815,46 @@ impl Ui {
 	}
 
 	pub(crate) fn raw_shape(&mut self) {
-		self.shape.raw_shape.par_sort_by(|a, b| a.style.layer.cmp(&b.style.layer));
+		self.shape.sort();
+		self.lay_out_texts();
+		self.shown_images.lock().unwrap().extend(self.shape.raw_shape.iter().filter_map(|shape| match &shape.shape {
+			ShapeElement::Image(image) => Some(image.id.clone()),
+			_ => None,
+		}));
+	}
+
+	/// give [`ShapeExtra::text_row`] to text shapes not drawn by [`Text`], they are laid out as a single line. rows of last frame are reused
+	fn lay_out_texts(&mut self) {
+		let last_rows = std::mem::take(&mut self.text_rows);
+		let generation = self.fonts.read().unwrap().generation();
+		for (shape, extra) in self.shape.raw_shape.iter().zip(self.shape.extras.iter_mut()) {
+			let ShapeElement::Text(text) = &shape.shape else {
+				continue;
+			};
+			if extra.text_row.is_some() {
+				continue;
+			}
+			let style = &shape.style;
+			let key = (text.text.clone(), extra.font_family, [style.size.x.to_bits(), style.size.y.to_bits()], generation);
+			let row = match last_rows.get(&key).or(self.text_rows.get(&key)) {
+				Some(row) => row.clone(),
+				None => {
+					let galley = with_font_book(&self.fonts, || layout(&text.text.replace('\n', ""), extra.font_family, style.size, None, TextWrap::None));
+					Arc::new(galley.rows.first().map_or_else(TextRow::default, |row| galley.text_row(row)))
+				},
+			};
+			extra.text_row = Some(row.clone());
+			self.text_rows.insert(key, row);
+		}
 	}
 
 	#[cfg(feature = "vertexs")]
+	/// shapes should be sorted by [`Self::raw_shape`] before
 	pub(crate) fn handle_raw_shape(&mut self) {
-		self.raw_shape();
-		let shapes = self.shape.raw_shape.clone();
-		self.shape.raw_shape.clear();
-		for shape in shapes {
-			if let ShapeElement::Text(inner) = shape.shape {
-				self.shape.parsed_shapes.push(ParsedShape::Text(inner, shape.style))
-			}else if let ShapeElement::Image(inner) = shape.shape {
-				self.shape.parsed_shapes.push(ParsedShape::Image(inner, shape.style))
-			}else {
-				let (vertexs, indices, clip_area) = shape.into_vertexs(self.window.width_and_height());
-				self.shape.parsed_shapes.push(ParsedShape::Vertexs { vertexs, indices, clip_area, scale_factor: shape.style.scale_factor, info: shape.style.info })
-			}
-		}
+		let shapes = std::mem::take(&mut self.shape.raw_shape);
+		let extras = std::mem::take(&mut self.shape.extras);
+		let mut parsed_shapes = crate::integrator::parse_shapes(shapes, extras, self.window.width_and_height(), self.feather);
+		self.shape.parsed_shapes.append(&mut parsed_shapes);
 	}
 
 	pub(crate) fn clear(&mut self) {
@@ -588,6 +866,7 @@ impl Ui {
 		self.available_id.1 = 0;
 		self.memory_clip.lock().unwrap().clear();
 		self.output_events.clear();
+		self.repaint_after = None;
 		let scale_factor = self.paint_style.scale_factor;
 		self.paint_style = Default::default();
 		self.scale_factor(scale_factor);
@@ -608,6 +887,64 @@ impl Ui {
 		for key in remove_key {
 			memory.remove(&key);
 		}
+		drop(memory);
+	}
+
+	/// textures of offscreens not shown this frame are deleted, call this after ui code so the deletion is sent this frame
+	pub(crate) fn remove_unused_offscreens(&mut self) {
+		let mut offscreens = self.offscreens.lock().unwrap();
+		let mut texture_id = self.texture_id.lock().unwrap();
+		offscreens.retain(|id, temp| {
+			if !temp.is_used {
+				texture_id.remove(id);
+				self.output_events.push(OutputEvent::TextureDelete(id.clone()));
+			}
+			std::mem::take(&mut temp.is_used)
+		});
+	}
+
+	/// send textures decoded by [`Self::load_texture`] since last frame
+	pub(crate) fn take_loaded_textures(&mut self) {
+		let mut loader = self.texture_loader.lock().unwrap();
+		let images = loader.take_finished();
+		let needs_polling = loader.is_loading() && !loader.has_waker();
+		drop(loader);
+		let mut texture_id = self.texture_id.lock().unwrap();
+		for image in images {
+			texture_id.insert(image.id.clone(), (image.size, image.options));
+			self.output_events.push(OutputEvent::TextureChange(image));
+		}
+		drop(texture_id);
+		// without a waker set by `Integrator::set_texture_waker` nothing tells us when decoding is done
+		if needs_polling {
+			self.request_repaint_after(Duration::milliseconds(50));
+		}
+	}
+
+	/// call once before ui code of a top level frame. advance animated textures drawn last frame, in offscreens or not, others wait where they are
+	pub(crate) fn update_animated_textures(&mut self) {
+		let shown = std::mem::take(&mut *self.shown_images.lock().unwrap());
+		let mut changed_textures = self.changed_textures.lock().unwrap();
+		changed_textures.clear();
+		let mut next_frame: Option<f32> = None;
+		for (id, animation) in self.animated_textures.lock().unwrap().iter_mut() {
+			if !shown.contains(id) {
+				animation.hold();
+				continue;
+			}
+			let (image, next) = animation.update(id);
+			if let Some(image) = image {
+				changed_textures.insert(id.clone());
+				self.output_events.push(OutputEvent::TextureChange(image));
+			}
+			if let Some(next) = next {
+				next_frame = Some(next_frame.map_or(next, |current| current.min(next)));
+			}
+		}
+		drop(changed_textures);
+		if let Some(next_frame) = next_frame {
+			self.request_repaint_after(Duration::seconds_f32(next_frame));
+		}
 	}
 
 	pub(crate) fn update(&mut self) {
@@ -655,6 +992,9 @@ impl Ui {
 		let original_layout = self.layout.clone();
 		let original_position = self.available_position;
 		self.layout = layout;
+		if self.layout.is_horizental && self.layout.mirror_for_rtl && self.is_rtl() {
+			self.layout.is_inverse = !self.layout.is_inverse;
+		}
 		if self.layout.is_inverse {
 			if self.layout.is_horizental {
 				self.available_position = Vec2::new(self.window.right_top().x - self.style.space, self.available_position.y) + self.offset;
@@ -696,6 +1036,42 @@ impl Ui {
 	}
 }
 
+impl Ui {
+	/// # Fonts
+
+	/// register a font file as a font family, use it by [`Text::family`]. registering a existing family will replace it.
+	/// renderers are told by [`OutputEvent::FontChange`]
+	pub fn register_font(&mut self, name: impl Into<String>, bytes: Vec<u8>) -> Result<()> {
+		self.fonts.write().unwrap().register(&name.into(), bytes)?;
+		self.send_output_event(OutputEvent::FontChange);
+		Ok(())
+	}
+
+	/// when a charater of `script` is not in current font family, try `families` in order before build-in font.
+	///
+	/// # Example
+	/// ```no_run
+	/// # use nablo::prelude::*;
+	/// # use nablo::font::Script;
+	/// # let mut ui = nablo::Ui::default();
+	/// ui.set_font_fallback(Script::Cjk, vec!("Noto Sans SC".into(), "Noto Sans JP".into()));
+	/// ```
+	pub fn set_font_fallback(&mut self, script: Script, families: Vec<String>) {
+		self.fonts.write().unwrap().set_fallback(script, &families);
+		self.send_output_event(OutputEvent::FontChange);
+	}
+
+	/// get exact glyph positions, line breaks and baselines of a text, same as what will be drawn
+	pub fn layout_text(&self, text: &Text) -> Galley {
+		with_font_book(&self.fonts, || text.galley())
+	}
+
+	/// fonts of this ui and its renderer, texts are laid out with them in frames. see [`crate::font::font_book`]
+	pub fn font_book(&self) -> SharedFontBook {
+		self.fonts.clone()
+	}
+}
+
 impl Ui {
 	/// # Textures
 
@@ -704,7 +1080,7 @@ impl Ui {
 	pub fn create_texture_from_path<P: AsRef<Path>>(&mut self, path: P, id: impl Into<String>) -> Result<()> {
 		let id = id.into();
 		let mut texture_id = self.texture_id.lock().unwrap();
-		if !texture_id.contains(&id) {
+		if !texture_id.contains_key(&id) {
 			let diffuse_image = Reader::open(path)?.decode()?;
 			let diffuse_rgba = diffuse_image.to_rgba8();
 			let dimensions = diffuse_image.dimensions();
@@ -712,20 +1088,157 @@ impl Ui {
 				rgba: diffuse_rgba.to_vec(),
 				size: Vec2::new(dimensions.0 as f32, dimensions.1 as f32),
 				id: id.clone(),
+				options: TextureOptions::default(),
 			};
+			texture_id.insert(id, (image.size, image.options));
 			self.output_events.push(OutputEvent::TextureChange(image));
-			texture_id.push(id);
 		}
 		Ok(())
 	}
 
+	#[cfg(not(target_arch = "wasm32"))]
+	/// like [`Self::create_texture_from_path`], but the file is read and decoded in background, so large images do not stall frames.
+	/// png, jpeg, webp and svg are supported, svg is rendered in its own size.
+	///
+	/// see [`Self::texture_status`] for progress, [`crate::widgets::Image`] shows a spinner meanwhile.
+	/// files not modified since last load are not decoded again, see [`Self::clear_texture_cache`] and [`Self::set_texture_cache_limit`]
+	///
+	/// # Example
+	/// ```no_run
+	/// # use nablo::prelude::*;
+	/// # fn load_example(ui: &mut Ui) {
+	/// ui.load_texture_from_path("photo.jpg", "photo", TextureOptions::LINEAR);
+	/// ui.add(Image::new("photo", Vec2::new(320.0, 240.0)));
+	/// if let Some(TextureStatus::Failed(error)) = ui.texture_status("photo") {
+	/// 	ui.label(format!("can not load photo: {}", error));
+	/// }
+	/// # }
+	/// ```
+	pub fn load_texture_from_path<P: AsRef<Path>>(&mut self, path: P, id: impl Into<String>, options: TextureOptions) {
+		let id = id.into();
+		if self.texture_status(&id).is_none() {
+			crate::texture_loader::load_path(&self.texture_loader, path.as_ref().to_path_buf(), id, options);
+		}
+	}
+
+	/// like [`Self::create_texture_with_options`], but `bytes` are decoded in background, see [`Self::load_texture_from_path`]
+	pub fn load_texture(&mut self, bytes: Vec<u8>, id: impl Into<String>, options: TextureOptions) {
+		let id = id.into();
+		if self.texture_status(&id).is_none() {
+			crate::texture_loader::load_bytes(&self.texture_loader, bytes, id, options);
+			// decoded already, picked up next frame
+			#[cfg(target_arch = "wasm32")]
+			self.request_repaint();
+		}
+	}
+
+	/// whether texture `id` is loading, ready or failed to load. returns `None` if there's no texture using this id.
+	///
+	/// failed textures are not loaded again until deleted by [`Self::delete_texture`]
+	pub fn texture_status(&self, id: impl Into<String>) -> Option<TextureStatus> {
+		let id = id.into();
+		// reloading an existing texture is still loading
+		self.texture_loader.lock().unwrap().status(&id).or_else(|| self.texture_id.lock().unwrap().contains_key(&id).then_some(TextureStatus::Ready))
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	/// forget decoded files kept by [`Self::load_texture_from_path`]
+	pub fn clear_texture_cache(&mut self) {
+		self.texture_loader.lock().unwrap().clear_cache();
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	/// keep at most `bytes` of decoded files for [`Self::load_texture_from_path`], least recently used ones are dropped first. 256MiB by default
+	pub fn set_texture_cache_limit(&mut self, bytes: usize) {
+		self.texture_loader.lock().unwrap().set_cache_limit(bytes);
+	}
+
+	/// add a gif or apng texture playing by itself, this function will add a texture when only current id is not been taken.
+	/// other images are added as a single frame. show it as any other texture such as `ui.add(Image::new(id, size))`.
+	///
+	/// every frame is kept decoded, so prefer a [`crate::widgets::SpriteSheet`] for long animations.
+	///
+	/// # Example
+	/// ```no_run
+	/// # use nablo::prelude::*;
+	/// # fn animation_example(ui: &mut Ui, bytes: &[u8]) -> anyhow::Result<()> {
+	/// ui.create_animated_texture(bytes, "loading", TextureOptions::default())?;
+	/// ui.add(Image::new("loading", Vec2::same(32.0)));
+	/// if ui.button("slow down").is_clicked() {
+	/// 	if let Some(playback) = ui.animated_texture_playback("loading") {
+	/// 		ui.set_animated_texture_playback("loading", playback.speed(0.5));
+	/// 	}
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn create_animated_texture(&mut self, bytes: &[u8], id: impl Into<String>, options: TextureOptions) -> Result<()> {
+		let id = id.into();
+		let mut texture_id = self.texture_id.lock().unwrap();
+		if !texture_id.contains_key(&id) {
+			let animation = AnimatedTexture::new(bytes, options)?;
+			let image = animation.image(id.clone());
+			texture_id.insert(id.clone(), (image.size, image.options));
+			self.output_events.push(OutputEvent::TextureChange(image));
+			self.animated_textures.lock().unwrap().insert(id, animation);
+		}
+		Ok(())
+	}
+
+	/// how animated texture `id` plays, returns `None` if it is not a animated texture.
+	/// `is_playing` turns false once a animation not looping is finished
+	pub fn animated_texture_playback(&self, id: impl Into<String>) -> Option<Playback> {
+		self.animated_textures.lock().unwrap().get(&id.into()).map(|animation| animation.playback)
+	}
+
+	/// play, pause or change speed of animated texture `id`. a finished animation plays from the beginning again
+	pub fn set_animated_texture_playback(&mut self, id: impl Into<String>, playback: Playback) {
+		if let Some(animation) = self.animated_textures.lock().unwrap().get_mut(&id.into()) {
+			if playback.is_playing && animation.is_finished() {
+				animation.seek(0);
+			}
+			animation.playback = playback;
+		}
+	}
+
+	/// current frame and frame count of animated texture `id`
+	pub fn animated_texture_frame(&self, id: impl Into<String>) -> Option<(usize, usize)> {
+		self.animated_textures.lock().unwrap().get(&id.into()).map(|animation| (animation.frame(), animation.frame_count()))
+	}
+
+	/// jump to `frame` of animated texture `id`, the last frame is shown if `frame` is too large
+	pub fn seek_animated_texture(&mut self, id: impl Into<String>, frame: usize) {
+		if let Some(animation) = self.animated_textures.lock().unwrap().get_mut(&id.into()) {
+			animation.seek(frame);
+		}
+	}
+
 	/// add a texture, this function will add a texture when only current id is not been taken. for svg using [`Self::create_texture_svg`]
 	pub fn create_texture(&mut self, bytes: &[u8], id: impl Into<String>) -> Result<()> {
+		self.create_texture_with_options(bytes, id, TextureOptions::default())
+	}
+
+	/// add a texture sampled by `options`, such as [`TextureOptions::NEAREST`] for pixel arts, see [`Self::create_texture`]
+	///
+	/// # Example
+	/// ```no_run
+	/// # use nablo::prelude::*;
+	/// # fn texture_example(ui: &mut Ui, bytes: &[u8]) -> anyhow::Result<()> {
+	/// ui.create_texture_with_options(bytes, "sprite", TextureOptions::NEAREST)?;
+	/// ui.create_texture_with_options(bytes, "photo", TextureOptions::LINEAR)?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn create_texture_with_options(&mut self, bytes: &[u8], id: impl Into<String>, options: TextureOptions) -> Result<()> {
 		let id = id.into();
 		let mut texture_id = self.texture_id.lock().unwrap();
-		if !texture_id.contains(&id) {
-			self.output_events.push(OutputEvent::TextureChange(texture(bytes, id.clone())?));
-			texture_id.push(id);
+		if !texture_id.contains_key(&id) {
+			let image = Image {
+				options,
+				..texture(bytes, id.clone())?
+			};
+			texture_id.insert(id, (image.size, image.options));
+			self.output_events.push(OutputEvent::TextureChange(image));
 		}
 		Ok(())
 	}
@@ -737,27 +1250,40 @@ impl Ui {
 	pub fn create_texture_svg(&mut self, bytes: &[u8], size: Vec2, id: impl Into<String>) -> Result<()> {
 		let id = id.into();
 		let mut texture_id = self.texture_id.lock().unwrap();
-		if !texture_id.contains(&id) {
-			self.output_events.push(OutputEvent::TextureChange(texture_svg(bytes, size, id.clone())?));
-			texture_id.push(id);
+		if !texture_id.contains_key(&id) {
+			let image = texture_svg(bytes, size, id.clone())?;
+			texture_id.insert(id, (image.size, image.options));
+			self.output_events.push(OutputEvent::TextureChange(image));
 		}
 		Ok(())
 	}
 
 	/// change a texture, this function will add a texture when a texture is not added. for svg using [`Self::change_texture_svg`]
 	///
+	/// the texture keeps options it was created with, default ones for a new texture.
+	///
 	/// Note: *dont* call this every frame, or `nablo` will be unstandably lagging.
 	pub fn change_texture(&mut self, bytes: &[u8], id: impl Into<String>) -> Result<()> {
+		let id = id.into();
+		let options = self.texture_options(&id);
+		self.change_texture_with_options(bytes, id, options)
+	}
+
+	/// change a texture sampled by `options`, see [`Self::change_texture`] and [`Self::create_texture_with_options`]
+	pub fn change_texture_with_options(&mut self, bytes: &[u8], id: impl Into<String>, options: TextureOptions) -> Result<()> {
 		let id = id.into();
 		let mut texture_id = self.texture_id.lock().unwrap();
-		self.output_events.push(OutputEvent::TextureChange(texture(bytes, id.clone())?));
-		if !texture_id.contains(&id) {
-			texture_id.push(id);
-		}
+		let image = Image {
+			options,
+			..texture(bytes, id.clone())?
+		};
+		texture_id.insert(id, (image.size, image.options));
+		self.output_events.push(OutputEvent::TextureChange(image));
 		Ok(())
 	}
 
 	/// change a texture with a svg file, this function will add a texture when a texture is not added.
+	/// the texture keeps options it was created with.
 	///
 	/// Note: *dont* call this every frame, or `nablo` will be unstandably lagging.
 	///
@@ -765,21 +1291,36 @@ impl Ui {
 	/// `size.x < 0.0 || size.y < 0.0 || size.x.is_infinite() || size.y.is_infinite()`
 	pub fn change_texture_svg(&mut self, bytes: &[u8], size: Vec2, id: impl Into<String>) -> Result<()> {
 		let id = id.into();
+		let options = self.texture_options(&id);
 		let mut texture_id = self.texture_id.lock().unwrap();
-		self.output_events.push(OutputEvent::TextureChange(texture_svg(bytes, size, id.clone())?));
-		if !texture_id.contains(&id) {
-			texture_id.push(id);
-		}
+		let image = Image {
+			options,
+			..texture_svg(bytes, size, id.clone())?
+		};
+		texture_id.insert(id, (image.size, image.options));
+		self.output_events.push(OutputEvent::TextureChange(image));
 		Ok(())
 	}
 
+	/// options texture `id` was created with, default ones if there's no such texture
+	fn texture_options(&self, id: &str) -> TextureOptions {
+		self.texture_id.lock().unwrap().get(id).map(|(_, options)| *options).unwrap_or_default()
+	}
+
 	/// delete a texture by using id
 	pub fn delete_texture(&mut self, id: impl Into<String>) {
 		let id = id.into();
+		self.texture_loader.lock().unwrap().cancel(&id);
+		self.animated_textures.lock().unwrap().remove(&id);
 		let mut texture_id = self.texture_id.lock().unwrap();
-		texture_id.retain(|a| a != &id);
+		texture_id.remove(&id);
 		self.output_events.push(OutputEvent::TextureDelete(id));
 	}
+
+	/// get size of a texture in pixels, returns `None` if there's no texture using this id
+	pub fn texture_size(&self, id: impl Into<String>) -> Option<Vec2> {
+		self.texture_id.lock().unwrap().get(&id.into()).map(|(size, _)| *size)
+	}
 }
 
 impl Ui {
@@ -795,6 +1336,16 @@ impl Ui {
 		self.add(Button::new(text))
 	}
 
+	/// add a [`crate::widgets::Hyperlink`].
+	pub fn hyperlink(&mut self, text: impl Into<Text>, url: impl Into<String>) -> Response {
+		self.add(Hyperlink::new(text, url))
+	}
+
+	/// add a [`crate::widgets::Markdown`]
+	pub fn markdown(&mut self, text: impl Into<String>) -> Response {
+		self.add(Markdown::new(text))
+	}
+
 	/// add a [`crate::widgets::DivideLine`]
 	pub fn divide_line(&mut self) -> Response {
 		self.add(DivideLine::new())
@@ -805,6 +1356,16 @@ impl Ui {
 		self.add(Canvas::new(width_and_height, paint))
 	}
 
+	/// add a [`crate::widgets::Plot`].
+	pub fn plot(&mut self, width_and_height: Vec2, series: Vec<Series>) -> Response {
+		self.add(series.into_iter().fold(Plot::new(width_and_height), |plot, series| plot.series(series)))
+	}
+
+	/// add a [`crate::widgets::Image`].
+	pub fn image(&mut self, id: impl Into<String>, size: Vec2) -> Response {
+		self.add(crate::widgets::Image::new(id, size))
+	}
+
 	/// add a [`crate::widgets::DragableValue`].
 	pub fn dragable_value<T: Num>(&mut self, input: &mut T) -> Response {
 		self.add(DragableValue::new(input))
@@ -848,6 +1409,21 @@ impl Ui {
 		}
 		res
 	}
+
+	/// add a [`crate::widgets::Calendar`].
+	pub fn calendar(&mut self, input: &mut Date) -> Response {
+		self.add(Calendar::new(input))
+	}
+
+	/// add a [`crate::widgets::TimePicker`].
+	pub fn time_picker(&mut self, input: &mut Time) -> Response {
+		self.add(TimePicker::new(input))
+	}
+
+	/// add a [`crate::widgets::DateTimePicker`].
+	pub fn date_time_picker(&mut self, input: &mut OffsetDateTime) -> Response {
+		self.add(DateTimePicker::new(input))
+	}
 }
 
 impl Ui {
@@ -879,18 +1455,19 @@ impl Ui {
 	}
 }
 
-fn texture(bytes: &[u8], id: String) -> Result<Image> {
+pub(crate) fn texture(bytes: &[u8], id: String) -> Result<Image> {
 	let diffuse_image = image::load_from_memory(bytes)?;
 	let diffuse_rgba = diffuse_image.to_rgba8();
 	let dimensions = diffuse_image.dimensions();
 	Ok(Image {
 		rgba: diffuse_rgba.to_vec(),
 		size: Vec2::new(dimensions.0 as f32, dimensions.1 as f32),
-		id
+		id,
+		options: TextureOptions::default(),
 	})
 }
 
-fn texture_svg(bytes: &[u8], size: Vec2, id: String) -> Result<Image> {
+pub(crate) fn texture_svg(bytes: &[u8], size: Vec2, id: String) -> Result<Image> {
 	let tree = resvg::Tree::from_usvg(&resvg::usvg::Tree::from_data(bytes, &resvg::usvg::Options {
 		default_size: resvg::usvg::Size::from_wh(size.x, size.y).expect("invaild size"),
 		..Default::default()
@@ -901,7 +1478,8 @@ fn texture_svg(bytes: &[u8], size: Vec2, id: String) -> Result<Image> {
 	Ok(Image {
 		rgba: pixmap_mut.to_owned().data().to_vec(),
 		size,
-		id
+		id,
+		options: TextureOptions::default(),
 	})
 }
 
@@ -910,14 +1488,16 @@ impl Layout {
 	pub fn horizental() -> Self {
 		Self {
 			is_inverse: false,
-			is_horizental: true
+			is_horizental: true,
+			mirror_for_rtl: false,
 		}
 	}
 
 	pub fn horizental_inverse() -> Self {
 		Self {
 			is_inverse: true,
-			is_horizental: true
+			is_horizental: true,
+			mirror_for_rtl: false,
 		}
 	}
 
@@ -925,14 +1505,25 @@ impl Layout {
 	pub fn vertical() -> Self {
 		Self {
 			is_inverse: false,
-			is_horizental: false
+			is_horizental: false,
+			mirror_for_rtl: false,
 		}
 	}
 
 	pub fn vertical_inverse() -> Self {
 		Self {
 			is_inverse: true,
-			is_horizental: false
+			is_horizental: false,
+			mirror_for_rtl: false,
 		}
 	}
-}
\ No newline at end of file
+
+	/// put horizental content right to left when [`Ui::language`] is written right to left, such as arabic and hebrew.
+	pub fn mirror_for_rtl(self, mirror_for_rtl: bool) -> Self {
+		Self {
+			mirror_for_rtl,
+			..self
+		}
+	}
+}
+
@@ -4,6 +4,9 @@ use crate::prelude::Status;
 use crate::widgets::TextSetting;
 use crate::widgets::Text;
 use crate::widgets::Button;
+use crate::widgets::Effect;
+use crate::widgets::Gradient;
+use crate::widgets::Shadow;
 use time::Duration;
 use nablo_shape::shape::animation::Animation;
 use nablo_shape::math::Area;
@@ -67,6 +70,38 @@ impl Button {
 			..self
 		}
 	}
+
+	/// draw a shadow behind the button, see [`Effect::DropShadow`]
+	pub fn shadow(self, shadow: Shadow) -> Self {
+		self.effect(Effect::DropShadow(shadow))
+	}
+
+	/// fill the button by a gradient instead of its status color
+	pub fn gradient(self, gradient: Gradient) -> Self {
+		self.effect(Effect::Gradient(gradient))
+	}
+
+	/// blur what is behind the button
+	pub fn backdrop_blur(self, blur: f32) -> Self {
+		self.effect(Effect::BackdropBlur(blur))
+	}
+
+	/// draw the background of the button with an effect, see [`Ui::paint_with_effects`]
+	pub fn effect(self, effect: Effect) -> Self {
+		let mut effects = self.effects;
+		effects.push(effect);
+		Self {
+			effects,
+			..self
+		}
+	}
+}
+
+/// shapes given to [`Ui`] with the style of `painter`
+fn painter_under(painter: &Painter) -> Painter {
+	let mut under = Painter::from_area(&painter.paint_area);
+	*under.style_mut() = painter.style().clone();
+	under
 }
 
 impl Widget for Button {
@@ -115,6 +150,9 @@ impl Widget for Button {
 		} * ui.style().brighten_factor;
 
 		painter.set_transform_origin(response.area.area[0]);
+		// shapes given to `ui` are drawn before those of `painter`, so with effects click animations go there as well to stay under the background
+		let mut animations = painter_under(painter);
+		let animation_painter = if self.effects.is_empty() { &mut *painter } else { &mut animations };
 		// click animation
 		for ct in response.release_times() {
 			let alpha = (1.0 - animation.caculate(&ct).unwrap_or(1.0)) * match self.style {
@@ -127,41 +165,54 @@ impl Widget for Button {
 				ButtonStyle::Stroked => (0.5 - alpha) * Vec2::same(10.0),
 				ButtonStyle::Lined => Vec2::same(0.0),
 			};
-			painter.set_position(response.area.area[0] + offset);
+			animation_painter.set_position(response.area.area[0] + offset);
 			let mut color_animation = background_color;
 			color_animation.a = alpha;
-			painter.set_color(color_animation);
-			painter.rect(response.area.width_and_height(), Vec2::same(5.0));
+			animation_painter.set_color(color_animation);
+			animation_painter.rect(response.area.width_and_height(), Vec2::same(5.0));
 		}
 
+		let mut effect_painter = if self.effects.is_empty() {
+			None
+		}else {
+			animations.brighter(light_factor);
+			ui.shape.append(animations);
+			Some(painter_under(painter))
+		};
+		let background = effect_painter.as_mut().unwrap_or(&mut *painter);
+
 		// actual draw
 		match self.style {
 			ButtonStyle::Normal => {
-				painter.set_color(background_color);
-				painter.set_position(response.area.area[0]);
-				painter.rect(response.area.width_and_height(), Vec2::same(5.0));
+				background.set_color(background_color);
+				background.set_position(response.area.area[0]);
+				background.rect(response.area.width_and_height(), Vec2::same(5.0));
 			},
 			ButtonStyle::Stroked => {
-				painter.set_stroke_color(background_color);
+				background.set_stroke_color(background_color);
 				let stroke_width = 1.0;
-				painter.set_stroke_width(stroke_width);
-				painter.set_color(Color::TRANSPARENT);
-				painter.set_position(response.area.area[0] + Vec2::same(stroke_width));
-				painter.rect(response.area.width_and_height() - Vec2::same(stroke_width * 2.0), Vec2::same(5.0));
+				background.set_stroke_width(stroke_width);
+				background.set_color(Color::TRANSPARENT);
+				background.set_position(response.area.area[0] + Vec2::same(stroke_width));
+				background.rect(response.area.width_and_height() - Vec2::same(stroke_width * 2.0), Vec2::same(5.0));
 			},
 			ButtonStyle::Lined => {
-				let text_area = self.text.text_area(painter);
+				let text_area = self.text.text_area(background);
 				let icon_area = self.painter.paint_area;
 				let length = (response.area.width() - space * 2.0 - icon_area.width()) * light_factor / ui.style().brighten_factor / 2.0;
 				let position_l = response.area.area[0] + Vec2::new(response.area.width() / 2.0 - length, text_area.height() + 8.0);
 				let position_r = response.area.area[0] + Vec2::new(response.area.width() / 2.0,  text_area.height() + 8.0);
-				painter.set_color(background_color);
-				painter.set_position(position_l);
-				painter.rect(Vec2::new(length, 4.0), Vec2::same(2.0));
-				painter.set_position(position_r);
-				painter.rect(Vec2::new(length, 4.0), Vec2::same(2.0));
+				background.set_color(background_color);
+				background.set_position(position_l);
+				background.rect(Vec2::new(length, 4.0), Vec2::same(2.0));
+				background.set_position(position_r);
+				background.rect(Vec2::new(length, 4.0), Vec2::same(2.0));
 			},
 		}
+		if let Some(mut effect_painter) = effect_painter {
+			effect_painter.brighter(light_factor);
+			ui.paint_with_effects(self.effects.clone(), effect_painter);
+		}
 		let icon_area = self.painter.paint_area;
 		let position = response.area.area[0] + Vec2::new(space / 2.0, (response.area.height() - icon_area.height()) / 2.0);
 		self.painter.move_by(position);
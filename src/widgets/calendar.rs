@@ -0,0 +1,375 @@
+use time::Duration;
+use time::OffsetDateTime;
+use time::Weekday;
+use time::Month;
+use time::Date;
+use crate::widgets::CalendarSelection;
+use crate::widgets::Calendar;
+use crate::widgets::TextSetting;
+use crate::widgets::Text;
+use nablo_shape::prelude::shape_elements::Color;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use nablo_shape::shape::Painter;
+use crate::Ui;
+use crate::Response;
+use crate::Widget;
+
+const WEEKDAYS_EN: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+const WEEKDAYS_ZH: [&str; 7] = ["一", "二", "三", "四", "五", "六", "日"];
+const WEEKDAYS_JA: [&str; 7] = ["月", "火", "水", "木", "金", "土", "日"];
+const WEEKDAYS_DE: [&str; 7] = ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"];
+const WEEKDAYS_FR: [&str; 7] = ["Lu", "Ma", "Me", "Je", "Ve", "Sa", "Di"];
+const WEEKDAYS_ES: [&str; 7] = ["Lu", "Ma", "Mi", "Ju", "Vi", "Sá", "Do"];
+const WEEKDAYS_RU: [&str; 7] = ["Пн", "Вт", "Ср", "Чт", "Пт", "Сб", "Вс"];
+
+const MONTHS_EN: [&str; 12] = ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+const MONTHS_ZH: [&str; 12] = ["一月", "二月", "三月", "四月", "五月", "六月", "七月", "八月", "九月", "十月", "十一月", "十二月"];
+const MONTHS_JA: [&str; 12] = ["1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月"];
+const MONTHS_DE: [&str; 12] = ["Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober", "November", "Dezember"];
+const MONTHS_FR: [&str; 12] = ["janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre", "novembre", "décembre"];
+const MONTHS_ES: [&str; 12] = ["enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre", "noviembre", "diciembre"];
+const MONTHS_RU: [&str; 12] = ["Январь", "Февраль", "Март", "Апрель", "Май", "Июнь", "Июль", "Август", "Сентябрь", "Октябрь", "Ноябрь", "Декабрь"];
+
+/// the primary subtag of a language, such as `zh` for `zh-CN`
+fn primary_language(language: &str) -> String {
+	language.split(['-', '_']).next().unwrap_or("").to_lowercase()
+}
+
+/// short weekday name for given language, falls back to english
+pub(crate) fn weekday_name(language: &str, weekday: Weekday) -> &'static str {
+	let names = match primary_language(language).as_str() {
+		"zh" => WEEKDAYS_ZH,
+		"ja" => WEEKDAYS_JA,
+		"de" => WEEKDAYS_DE,
+		"fr" => WEEKDAYS_FR,
+		"es" => WEEKDAYS_ES,
+		"ru" => WEEKDAYS_RU,
+		_ => WEEKDAYS_EN,
+	};
+	names[weekday.number_days_from_monday() as usize]
+}
+
+/// month name for given language, falls back to english
+pub(crate) fn month_name(language: &str, month: Month) -> &'static str {
+	let names = match primary_language(language).as_str() {
+		"zh" => MONTHS_ZH,
+		"ja" => MONTHS_JA,
+		"de" => MONTHS_DE,
+		"fr" => MONTHS_FR,
+		"es" => MONTHS_ES,
+		"ru" => MONTHS_RU,
+		_ => MONTHS_EN,
+	};
+	names[u8::from(month) as usize - 1]
+}
+
+/// which month is displaying
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq)]
+pub(crate) struct CalendarView {
+	pub year: i32,
+	/// 1 to 12
+	pub month: u8,
+}
+
+impl Default for CalendarView {
+	fn default() -> Self {
+		OffsetDateTime::now_utc().date().into()
+	}
+}
+
+impl From<Date> for CalendarView {
+	fn from(date: Date) -> Self {
+		Self {
+			year: date.year(),
+			month: date.month().into(),
+		}
+	}
+}
+
+impl CalendarView {
+	fn month(&self) -> Month {
+		Month::try_from(self.month).unwrap_or(Month::January)
+	}
+
+	fn first_day(&self) -> Date {
+		Date::from_calendar_date(self.year, self.month(), 1).unwrap_or(Date::MIN)
+	}
+
+	fn previous_month(&mut self) {
+		if self.month <= 1 {
+			self.month = 12;
+			self.year -= 1;
+		}else {
+			self.month -= 1;
+		}
+	}
+
+	fn next_month(&mut self) {
+		if self.month >= 12 {
+			self.month = 1;
+			self.year += 1;
+		}else {
+			self.month += 1;
+		}
+	}
+
+	/// the first date shown in the month grid
+	fn grid_start(&self, first_weekday: Weekday) -> Date {
+		let first_day = self.first_day();
+		let offset = (first_day.weekday().number_days_from_monday() + 7 - first_weekday.number_days_from_monday()) % 7;
+		first_day.checked_sub(Duration::days(offset as i64)).unwrap_or(first_day)
+	}
+
+	/// all 42 dates shown in the month grid
+	fn grid(&self, first_weekday: Weekday) -> Vec<Date> {
+		let start = self.grid_start(first_weekday);
+		(0..42).filter_map(|i| start.checked_add(Duration::days(i))).collect()
+	}
+
+	pub(crate) fn apply(&mut self, hit: &CalendarHit) {
+		match hit {
+			CalendarHit::PreviousYear => self.year -= 1,
+			CalendarHit::PreviousMonth => self.previous_month(),
+			CalendarHit::NextMonth => self.next_month(),
+			CalendarHit::NextYear => self.year += 1,
+			CalendarHit::Day(date) => {
+				if date.year() != self.year || u8::from(date.month()) != self.month {
+					*self = (*date).into();
+				}
+			}
+		}
+	}
+}
+
+/// what did user clicked on a calendar
+pub(crate) enum CalendarHit {
+	PreviousYear,
+	PreviousMonth,
+	NextMonth,
+	NextYear,
+	Day(Date),
+}
+
+/// how large does a calendar take by given cell size, a header row, a weekday row and six weeks.
+pub(crate) fn calendar_size(cell: f32) -> Vec2 {
+	Vec2::new(cell * 7.0, cell * 8.0)
+}
+
+/// find out what is under `position`, `origin` is the left top of the calendar
+pub(crate) fn calendar_hit(position: Vec2, origin: Vec2, cell: f32, view: &CalendarView, first_weekday: Weekday) -> Option<CalendarHit> {
+	let local = position - origin;
+	if local.x < 0.0 || local.y < 0.0 || local.x >= cell * 7.0 || local.y >= cell * 8.0 {
+		return None;
+	}
+	let column = (local.x / cell) as usize;
+	let row = (local.y / cell) as usize;
+	match row {
+		0 => match column {
+			0 => Some(CalendarHit::PreviousYear),
+			1 => Some(CalendarHit::PreviousMonth),
+			5 => Some(CalendarHit::NextMonth),
+			6 => Some(CalendarHit::NextYear),
+			_ => None
+		},
+		1 => None,
+		_ => view.grid(first_weekday).get((row - 2) * 7 + column).map(|date| CalendarHit::Day(*date)),
+	}
+}
+
+/// draw a calendar at `origin`, `selected` is the selected date or range, `hover` is the cursor position in logical pixels
+pub(crate) fn draw_calendar(ui: &mut Ui, painter: &mut Painter, origin: Vec2, cell: f32, view: &CalendarView, first_weekday: Weekday, selected: (Date, Date), hover: Option<Vec2>) {
+	let language = ui.language();
+	let (from, to) = if selected.0 <= selected.1 {
+		selected
+	}else {
+		(selected.1, selected.0)
+	};
+	let today = OffsetDateTime::now_utc().date();
+	let hover_area = hover.and_then(|position| {
+		let local = position - origin;
+		if local.x < 0.0 || local.y < 0.0 || local.x >= cell * 7.0 || local.y >= cell * 8.0 {
+			None
+		}else {
+			let corner = Vec2::new((local.x / cell).floor() * cell, (local.y / cell).floor() * cell) + origin;
+			Some(Area::new(corner, corner + Vec2::same(cell)))
+		}
+	});
+	let centered = |painter: &mut Painter, text: &Text, area: Area| -> Vec2 {
+		let text_area = text.text_area(painter);
+		area.left_top() + (area.width_and_height() - text_area.width_and_height()) / 2.0
+	};
+	let cell_area = |column: usize, row: usize| -> Area {
+		let corner = origin + Vec2::new(column as f32 * cell, row as f32 * cell);
+		Area::new(corner, corner + Vec2::same(cell))
+	};
+
+	// # header
+	for (column, arrow) in [(0, "«"), (1, "‹"), (5, "›"), (6, "»")] {
+		let area = cell_area(column, 0);
+		if hover_area == Some(area) {
+			painter.set_color(ui.style().card_color.brighter(ui.style().brighten_factor));
+			painter.set_position(area.left_top() + Vec2::same(2.0));
+			painter.rect(area.width_and_height() - Vec2::same(4.0), Vec2::same(5.0));
+		}
+		let text: Text = arrow.into();
+		let position = centered(painter, &text, area);
+		text.text_draw(painter, position, ui);
+	}
+	let title: Text = format!("{} {}", month_name(&language, view.month()), view.year).into();
+	let title_area = Area::new(cell_area(2, 0).left_top(), cell_area(4, 0).right_bottom());
+	let position = centered(painter, &title, title_area);
+	title.text_draw(painter, position, ui);
+
+	// # weekdays
+	for column in 0..7 {
+		let weekday = (0..column).fold(first_weekday, |weekday, _| weekday.next());
+		let text = Text::from(weekday_name(&language, weekday)).set_color(ui.style().slider_peek_color);
+		let area = cell_area(column, 1);
+		let position = centered(painter, &text, area);
+		text.text_draw(painter, position, ui);
+	}
+
+	// # days
+	for (i, date) in view.grid(first_weekday).into_iter().enumerate() {
+		let area = cell_area(i % 7, i / 7 + 2);
+		let inner = area.shrink(Vec2::same(2.0));
+		let is_in_month = u8::from(date.month()) == view.month && date.year() == view.year;
+		let is_edge = date == from || date == to;
+		if is_edge {
+			painter.set_color(ui.style().primary_color);
+			painter.set_position(inner.left_top());
+			painter.rect(inner.width_and_height(), Vec2::same(5.0));
+		}else if date > from && date < to {
+			painter.set_color(ui.style().primary_color.set_alpha(80));
+			painter.set_position(area.left_top());
+			painter.rect(area.width_and_height(), Vec2::ZERO);
+		}else if hover_area == Some(area) {
+			painter.set_color(ui.style().card_color.brighter(ui.style().brighten_factor));
+			painter.set_position(inner.left_top());
+			painter.rect(inner.width_and_height(), Vec2::same(5.0));
+		}
+		if date == today {
+			painter.set_color(Color::TRANSPARENT);
+			painter.set_stroke_width(1.0);
+			painter.set_stroke_color(ui.style().primary_color.brighter(0.3));
+			painter.set_position(inner.left_top());
+			painter.rect(inner.width_and_height(), Vec2::same(5.0));
+			painter.set_stroke_width(0.0);
+		}
+		let mut text: Text = date.day().to_string().into();
+		if is_edge {
+			text = text.set_color(Color::WHITE);
+		}else if !is_in_month {
+			text = text.set_color([0.5, 0.5, 0.5, 0.5]);
+		}
+		let position = centered(painter, &text, area);
+		text.text_draw(painter, position, ui);
+	}
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+struct CalendarTemp {
+	view: Option<CalendarView>,
+	is_picking_end: bool,
+}
+
+impl<'a> Calendar<'a> {
+	/// get a calendar selecting a single date
+	pub fn new(input: &'a mut Date) -> Self {
+		Self {
+			selection: CalendarSelection::Single(input),
+			cell_size: 32.0,
+			first_weekday: Weekday::Monday,
+		}
+	}
+
+	/// get a calendar selecting a range of dates, the first click picks `from`, the second picks `to`.
+	///
+	/// `from` will always be earlier than or equal to `to` after selection.
+	pub fn range(from: &'a mut Date, to: &'a mut Date) -> Self {
+		Self {
+			selection: CalendarSelection::Range(from, to),
+			cell_size: 32.0,
+			first_weekday: Weekday::Monday,
+		}
+	}
+
+	/// set size of every day cell, by default its 32.0
+	pub fn cell_size(self, cell_size: f32) -> Self {
+		Self {
+			cell_size,
+			..self
+		}
+	}
+
+	/// set which day a week starts with, by default its monday
+	pub fn first_weekday(self, first_weekday: Weekday) -> Self {
+		Self {
+			first_weekday,
+			..self
+		}
+	}
+
+	fn selected(&self) -> (Date, Date) {
+		match &self.selection {
+			CalendarSelection::Single(date) => (**date, **date),
+			CalendarSelection::Range(from, to) => (**from, **to),
+		}
+	}
+}
+
+impl Widget for Calendar<'_> {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		painter.set_transform_origin(response.area.area[0]);
+		// logic
+		let selected = self.selected();
+		let mut temp: CalendarTemp = response.memory_read().unwrap_or_default();
+		let mut view = temp.view.unwrap_or(selected.0.into());
+		let scale_factor = painter.style().scale_factor;
+		let cursor = ui.input().cursor_position().map(|inner| inner / scale_factor);
+		let origin = response.area.left_top();
+		if response.is_clicked() {
+			if let Some(hit) = cursor.and_then(|cursor| calendar_hit(cursor, origin, self.cell_size, &view, self.first_weekday)) {
+				if let CalendarHit::Day(date) = hit {
+					match &mut self.selection {
+						CalendarSelection::Single(input) => **input = date,
+						CalendarSelection::Range(from, to) => {
+							if temp.is_picking_end {
+								if date < **from {
+									**to = **from;
+									**from = date;
+								}else {
+									**to = date;
+								}
+							}else {
+								**from = date;
+								**to = date;
+							}
+							temp.is_picking_end = !temp.is_picking_end;
+						}
+					}
+				}
+				view.apply(&hit);
+			}
+		}
+		temp.view = Some(view);
+		ui.memory_save(&response.id, &temp);
+
+		// draw
+		let hover = if response.is_hovering() {
+			cursor
+		}else {
+			None
+		};
+		draw_calendar(ui, painter, origin, self.cell_size, &view, self.first_weekday, self.selected(), hover);
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		let area = match area {
+			Some(t) => t,
+			None => Area::new(ui.available_position(), ui.available_position() + calendar_size(self.cell_size))
+		};
+		ui.response(area, true, false)
+	}
+}
@@ -0,0 +1,1221 @@
+use crate::OutputEvent;
+use crate::event::ShaderDiagnostic;
+use crate::font::MONOSPACE;
+use crate::InputState;
+use crate::Key;
+use crate::Ui;
+use crate::Response;
+use crate::Widget;
+use crate::widgets::CodeEditor;
+use crate::widgets::CodeMarker;
+use crate::widgets::PlainTokenizer;
+use crate::widgets::Status;
+use crate::widgets::Text;
+use crate::widgets::TextSetting;
+use crate::widgets::TokenKind;
+use crate::widgets::Tokenizer;
+use nablo_shape::prelude::shape_elements::Color;
+use nablo_shape::prelude::Area;
+use nablo_shape::prelude::Vec2;
+use nablo_shape::prelude::Painter;
+use std::ops::Range;
+
+/// oldest steps are dropped when there are more
+const MAX_UNDO_STEPS: usize = 100;
+
+impl<'a> CodeEditor<'a> {
+	/// create a new code editor, nothing is highlighted until a [`Tokenizer`] is set
+	pub fn new(input: &'a mut String) -> CodeEditor<'a> {
+		Self {
+			text: Text::default().family(MONOSPACE),
+			input,
+			tokenizer: Box::new(PlainTokenizer),
+			markers: vec!(),
+			width: None,
+			rows: 12,
+			indent: "\t".into(),
+			tab_size: 4,
+			line_numbers: true,
+		}
+	}
+
+	/// set how code is highlighted, see [`crate::widgets::WgslTokenizer`], [`crate::widgets::JsonTokenizer`] and [`crate::widgets::RustTokenizer`]
+	pub fn tokenizer(self, tokenizer: impl Tokenizer + 'static) -> Self {
+		Self {
+			tokenizer: Box::new(tokenizer),
+			..self
+		}
+	}
+
+	/// add a underline to code
+	pub fn marker(mut self, marker: CodeMarker) -> Self {
+		self.markers.push(marker);
+		self
+	}
+
+	/// replace all underlines
+	pub fn markers(self, markers: Vec<CodeMarker>) -> Self {
+		Self {
+			markers,
+			..self
+		}
+	}
+
+	/// set width of code editor, by default, it will take all rest place.
+	pub fn set_width(self, width: f32) -> Self {
+		Self {
+			width: Some(width),
+			..self
+		}
+	}
+
+	/// set how many lines can be seen without scrolling, 12 by default
+	pub fn set_rows(self, rows: usize) -> Self {
+		Self {
+			rows,
+			..self
+		}
+	}
+
+	/// set what a level of indent is, a tab by default
+	pub fn indent(self, indent: impl Into<String>) -> Self {
+		Self {
+			indent: indent.into(),
+			..self
+		}
+	}
+
+	/// set how many cells a tab takes, 4 by default
+	pub fn tab_size(self, tab_size: usize) -> Self {
+		Self {
+			tab_size: tab_size.max(1),
+			..self
+		}
+	}
+
+	/// show or hide the line number gutter
+	pub fn line_numbers(self, line_numbers: bool) -> Self {
+		Self {
+			line_numbers,
+			..self
+		}
+	}
+
+	/// use a font family registered by [`Ui::register_font`], the build-in [`MONOSPACE`] font by default.
+	/// every charater is put in a cell as wide as `M`, so fonts not monospace leave gaps
+	pub fn family(self, family: impl Into<String>) -> Self {
+		Self {
+			text: self.text.family(family),
+			..self
+		}
+	}
+}
+
+impl CodeMarker {
+	/// create a marker on `line` from `columns`, both start from 1
+	pub fn new(status: Status, line: usize, columns: Range<usize>, message: impl Into<String>) -> Self {
+		Self {
+			line,
+			columns,
+			status,
+			message: message.into(),
+		}
+	}
+
+	/// a red underline
+	pub fn error(line: usize, columns: Range<usize>, message: impl Into<String>) -> Self {
+		Self::new(Status::Error, line, columns, message)
+	}
+
+	/// a yellow underline
+	pub fn warning(line: usize, columns: Range<usize>, message: impl Into<String>) -> Self {
+		Self::new(Status::Warning, line, columns, message)
+	}
+
+	/// a blue underline
+	pub fn info(line: usize, columns: Range<usize>, message: impl Into<String>) -> Self {
+		Self::new(Status::Info, line, columns, message)
+	}
+}
+
+impl From<&ShaderDiagnostic> for CodeMarker {
+	/// underline a shader problem, problems without position underline the first line
+	fn from(diagnostic: &ShaderDiagnostic) -> Self {
+		let columns = if diagnostic.column == 0 {
+			0..0
+		}else {
+			diagnostic.column..diagnostic.column + diagnostic.length.max(1)
+		};
+		Self::error(diagnostic.line.max(1), columns, diagnostic.message.clone())
+	}
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+struct CodeEditorTemp {
+	is_focused: bool,
+	/// charater index of the caret
+	pointer: usize,
+	/// other end of the selection, nothing is selected if it equals to `pointer`
+	anchor: usize,
+	/// is mouse selecting code
+	is_selecting: bool,
+	/// cells kept when moving between lines
+	column: Option<f32>,
+	scroll: Vec2,
+	find: Option<FindBar>,
+	#[serde(default)]
+	history: History,
+}
+
+/// text replaced at charater `start`
+#[derive(Default, serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+struct Edit {
+	start: usize,
+	removed: String,
+	inserted: String,
+}
+
+/// edits made by one input, undone together
+#[derive(Default, serde::Deserialize, serde::Serialize, Debug)]
+struct UndoStep {
+	edits: Vec<Edit>,
+	/// pointer and anchor before the edits
+	before: (usize, usize),
+	/// pointer and anchor after the edits
+	after: (usize, usize),
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+struct History {
+	undo: Vec<UndoStep>,
+	redo: Vec<UndoStep>,
+}
+
+impl History {
+	/// remember edits, charaters typed one by one are merged into words and spaces after them
+	fn record(&mut self, edits: Vec<Edit>, before: (usize, usize), after: (usize, usize)) {
+		if edits.is_empty() {
+			return;
+		}
+		self.redo.clear();
+		if let (Some(last), [edit]) = (self.undo.last_mut(), &edits[..]) {
+			if let [last_edit] = &mut last.edits[..] {
+				let is_new_word = last_edit.inserted.ends_with(char::is_whitespace) && !edit.inserted.starts_with(char::is_whitespace);
+				let is_typing = edit.removed.is_empty() && last_edit.removed.is_empty() && !is_new_word;
+				if is_typing && last_edit.start + last_edit.inserted.chars().count() == edit.start && last.after == before {
+					last_edit.inserted.push_str(&edit.inserted);
+					last.after = after;
+					return;
+				}
+			}
+		}
+		self.undo.push(UndoStep { edits, before, after });
+		if self.undo.len() > MAX_UNDO_STEPS {
+			self.undo.remove(0);
+		}
+	}
+
+	/// revert last step, returns pointer and anchor to go to
+	fn undo(&mut self, buffer: &mut Buffer) -> Option<(usize, usize)> {
+		let step = self.undo.pop()?;
+		for edit in step.edits.iter().rev() {
+			buffer.replace(edit.start..edit.start + edit.inserted.chars().count(), &edit.removed);
+		}
+		buffer.edits.clear();
+		let back = step.before;
+		self.redo.push(step);
+		Some(back)
+	}
+
+	/// apply last undone step again, returns pointer and anchor to go to
+	fn redo(&mut self, buffer: &mut Buffer) -> Option<(usize, usize)> {
+		let step = self.redo.pop()?;
+		for edit in &step.edits {
+			buffer.replace(edit.start..edit.start + edit.removed.chars().count(), &edit.inserted);
+		}
+		buffer.edits.clear();
+		let back = step.after;
+		self.undo.push(step);
+		Some(back)
+	}
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+struct FindBar {
+	query: String,
+	replacement: String,
+	is_replacing: bool,
+	/// is keyboard input going to the bar instead of code
+	is_focused: bool,
+	is_replacement_focused: bool,
+}
+
+impl CodeEditorTemp {
+	fn selection(&self) -> Range<usize> {
+		self.pointer.min(self.anchor)..self.pointer.max(self.anchor)
+	}
+
+	fn move_to(&mut self, index: usize, is_selecting: bool) {
+		self.pointer = index;
+		if !is_selecting {
+			self.anchor = index;
+		}
+	}
+}
+
+/// code as charaters, so it can be edited by charater index
+struct Buffer {
+	chars: Vec<char>,
+	/// index of first charater of every line
+	line_starts: Vec<usize>,
+	is_changed: bool,
+	/// replaces made since last taken, for undo
+	edits: Vec<Edit>,
+}
+
+impl Buffer {
+	fn new(text: &str) -> Self {
+		let mut back = Self {
+			chars: text.chars().collect(),
+			line_starts: vec!(),
+			is_changed: false,
+			edits: vec!(),
+		};
+		back.refresh();
+		back
+	}
+
+	fn refresh(&mut self) {
+		let breaks = self.chars.iter().enumerate().filter(|(_, chr)| **chr == '\n').map(|(index, _)| index + 1);
+		self.line_starts = std::iter::once(0).chain(breaks).collect();
+	}
+
+	fn line_count(&self) -> usize {
+		self.line_starts.len()
+	}
+
+	fn line_of(&self, index: usize) -> usize {
+		self.line_starts.partition_point(|start| *start <= index) - 1
+	}
+
+	/// charaters of `line`, line break not included
+	fn line(&self, line: usize) -> Range<usize> {
+		let end = self.line_starts.get(line + 1).map_or(self.chars.len(), |next| next - 1);
+		self.line_starts[line]..end
+	}
+
+	fn replace(&mut self, range: Range<usize>, text: &str) {
+		if range.is_empty() && text.is_empty() {
+			return;
+		}
+		self.edits.push(Edit {
+			start: range.start,
+			removed: self.text(range.clone()),
+			inserted: text.to_string(),
+		});
+		self.chars.splice(range, text.chars());
+		self.refresh();
+		self.is_changed = true;
+	}
+
+	fn text(&self, range: Range<usize>) -> String {
+		self.chars[range].iter().collect()
+	}
+}
+
+/// cells taken by the first `column` charaters of `line`, tabs go to next tab stop
+fn visual_column(line: &[char], column: usize, tab_size: usize) -> usize {
+	line[..column.min(line.len())].iter().fold(0, |cells, chr| next_cell(cells, *chr, tab_size))
+}
+
+fn next_cell(cells: usize, chr: char, tab_size: usize) -> usize {
+	if chr == '\t' {
+		(cells / tab_size + 1) * tab_size
+	}else {
+		cells + 1
+	}
+}
+
+/// charater column nearest to `cells`
+fn column_at(line: &[char], cells: f32, tab_size: usize) -> usize {
+	let mut current = 0;
+	for (column, chr) in line.iter().enumerate() {
+		let next = next_cell(current, *chr, tab_size);
+		if cells < (current + next) as f32 / 2.0 {
+			return column;
+		}
+		current = next;
+	}
+	line.len()
+}
+
+/// runs of charaters with same kind in a line, as cells before the run and charater range in `line`, whitespaces are not in any run
+fn line_runs(line: &[char], kinds: &[TokenKind], tab_size: usize) -> Vec<(usize, Range<usize>)> {
+	let mut back: Vec<(usize, Range<usize>)> = vec!();
+	let mut cells = 0;
+	for (column, chr) in line.iter().enumerate() {
+		if !chr.is_whitespace() {
+			match back.last_mut() {
+				Some((_, run)) if run.end == column && kinds[run.start] == kinds[column] => run.end += 1,
+				_ => back.push((cells, column..column + 1)),
+			}
+		}
+		cells = next_cell(cells, *chr, tab_size);
+	}
+	back
+}
+
+fn closing_bracket(chr: char) -> Option<char> {
+	match chr {
+		'(' => Some(')'),
+		'[' => Some(']'),
+		'{' => Some('}'),
+		_ => None
+	}
+}
+
+fn opening_bracket(chr: char) -> Option<char> {
+	match chr {
+		')' => Some('('),
+		']' => Some('['),
+		'}' => Some('{'),
+		_ => None
+	}
+}
+
+/// position of the bracket matching the one at `index`, brackets in strings and comments are ignored
+fn matching_bracket(chars: &[char], kinds: &[TokenKind], index: usize) -> Option<usize> {
+	let is_code = |index: usize| !matches!(kinds.get(index), Some(TokenKind::String) | Some(TokenKind::Comment));
+	let chr = *chars.get(index)?;
+	if !is_code(index) {
+		return None;
+	}
+	let (pair, indexes): (char, Box<dyn Iterator<Item = usize>>) = if let Some(closing) = closing_bracket(chr) {
+		(closing, Box::new(index..chars.len()))
+	}else if let Some(opening) = opening_bracket(chr) {
+		(opening, Box::new((0..=index).rev()))
+	}else {
+		return None;
+	};
+	let mut depth = 0;
+	for current in indexes.filter(|current| is_code(*current)) {
+		if chars[current] == chr {
+			depth += 1;
+		}else if chars[current] == pair {
+			depth -= 1;
+			if depth == 0 {
+				return Some(current);
+			}
+		}
+	}
+	None
+}
+
+/// kind of every charater
+fn char_kinds(code: &str, tokenizer: &dyn Tokenizer) -> Vec<TokenKind> {
+	let starts: Vec<usize> = code.char_indices().map(|(byte, _)| byte).collect();
+	let mut kinds = vec![TokenKind::Plain; starts.len()];
+	for token in tokenizer.tokenize(code) {
+		let from = starts.partition_point(|byte| *byte < token.range.start);
+		let to = starts.partition_point(|byte| *byte < token.range.end).max(from);
+		for kind in &mut kinds[from..to] {
+			*kind = token.kind;
+		}
+	}
+	kinds
+}
+
+fn token_color(kind: TokenKind, text_color: Color) -> Color {
+	match kind {
+		TokenKind::Plain => text_color,
+		TokenKind::Punctuation => text_color.set_alpha(200),
+		TokenKind::Keyword => [197, 134, 192, 255].into(),
+		TokenKind::Type => [78, 201, 176, 255].into(),
+		TokenKind::Function => [220, 220, 170, 255].into(),
+		TokenKind::Number => [181, 206, 168, 255].into(),
+		TokenKind::String => [206, 145, 120, 255].into(),
+		TokenKind::Comment => [106, 153, 85, 255].into(),
+		TokenKind::Attribute => [86, 156, 214, 255].into(),
+		TokenKind::Property => [156, 220, 254, 255].into(),
+	}
+}
+
+/// ranges of every `query` in `chars`, not overlapped
+fn find_matches(chars: &[char], query: &str) -> Vec<Range<usize>> {
+	let query: Vec<char> = query.chars().collect();
+	let mut back = vec!();
+	if query.is_empty() {
+		return back;
+	}
+	let mut index = 0;
+	while index + query.len() <= chars.len() {
+		if chars[index..index + query.len()] == query[..] {
+			back.push(index..index + query.len());
+			index += query.len();
+		}else {
+			index += 1;
+		}
+	}
+	back
+}
+
+/// select first match starts after `from`, or last one ends before `from` if `is_backwards`, wraps around
+fn select_match(temp: &mut CodeEditorTemp, matches: &[Range<usize>], from: usize, is_backwards: bool) {
+	let found = if is_backwards {
+		matches.iter().rev().find(|range| range.end <= from).or(matches.last())
+	}else {
+		matches.iter().find(|range| range.start >= from).or(matches.first())
+	};
+	if let Some(range) = found {
+		temp.anchor = range.start;
+		temp.pointer = range.end;
+	}
+}
+
+fn is_control_pressing(input: &InputState) -> bool {
+	input.is_key_pressing(Key::ControlLeft) || input.is_key_pressing(Key::ControlRight)
+}
+
+fn is_shift_pressing(input: &InputState) -> bool {
+	input.is_key_pressing(Key::ShiftLeft) || input.is_key_pressing(Key::ShiftRight)
+}
+
+/// is `key` released while control is pressing or released at the same time
+fn is_shortcut(input: &InputState, key: Key) -> bool {
+	let is_control_released = input.is_key_released(Key::ControlLeft) || input.is_key_released(Key::ControlRight);
+	(is_control_pressing(input) || is_control_released) && input.is_key_released(key)
+}
+
+/// where the find bar is, at right top of code area
+fn find_bar_area(find: &FindBar, code_area: Area, cell: Vec2, padding: f32) -> Area {
+	let rows = if find.is_replacing { 2.0 } else { 1.0 };
+	let width = (cell.x * 32.0).min(code_area.width() - padding * 2.0);
+	let left_top = Vec2::new(code_area.area[1].x - padding - width, code_area.area[0].y + padding);
+	Area::new(left_top, left_top + Vec2::new(width, rows * (cell.y + padding) + (rows + 1.0) * padding / 2.0))
+}
+
+/// area of the find field for 0 and replace field for 1
+fn find_field_area(bar: Area, index: usize, cell: Vec2, padding: f32) -> Area {
+	let left_top = bar.area[0] + Vec2::new(padding / 2.0, padding / 2.0 + index as f32 * (cell.y + padding * 1.5));
+	Area::new(left_top, left_top + Vec2::new(bar.width() - padding, cell.y + padding))
+}
+
+/// a zigzag line under code
+fn wavy_line(painter: &mut Painter, position: Vec2, width: f32, color: Color) {
+	painter.set_color(color);
+	let mut x = 0.0;
+	let mut is_up = true;
+	while x < width {
+		painter.set_position(position + Vec2::new(x, if is_up { 0.0 } else { 1.5 }));
+		painter.rect(Vec2::new(2.0_f32.min(width - x), 1.5), Vec2::ZERO);
+		x += 2.0;
+		is_up = !is_up;
+	}
+}
+
+impl CodeEditor<'_> {
+	/// size of a charater cell
+	fn cell_size(&self) -> Vec2 {
+		self.text.clone().set_text("M").galley().size
+	}
+
+	fn gutter_width(&self, line_count: usize, cell: Vec2, padding: f32) -> f32 {
+		if self.line_numbers {
+			line_count.to_string().len().max(2) as f32 * cell.x + padding * 3.0
+		}else {
+			0.0
+		}
+	}
+
+	/// cells before charater `index` in its line
+	fn cells_of(&self, buffer: &Buffer, index: usize) -> usize {
+		let range = buffer.line(buffer.line_of(index));
+		visual_column(&buffer.chars[range.clone()], index - range.start, self.tab_size)
+	}
+
+	/// charater index nearest to `cells` in `line`
+	fn index_at(&self, buffer: &Buffer, line: usize, cells: f32) -> usize {
+		let range = buffer.line(line);
+		range.start + column_at(&buffer.chars[range.clone()], cells, self.tab_size)
+	}
+
+	/// charater index nearest to `position` relative to left top of code
+	fn index_at_position(&self, buffer: &Buffer, position: Vec2, cell: Vec2) -> usize {
+		if position.y < 0.0 {
+			return 0;
+		}
+		let line = (position.y / cell.y) as usize;
+		if line >= buffer.line_count() {
+			return buffer.chars.len();
+		}
+		self.index_at(buffer, line, position.x / cell.x)
+	}
+
+	/// how many charaters to remove from start of `line` to unindent it by one level
+	fn unindent_length(&self, line: &[char]) -> usize {
+		let indent: Vec<char> = self.indent.chars().collect();
+		if !indent.is_empty() && line.starts_with(&indent) {
+			indent.len()
+		}else if line.first() == Some(&'\t') {
+			1
+		}else {
+			line.iter().take(self.tab_size).take_while(|chr| **chr == ' ').count()
+		}
+	}
+
+	/// replace selection with `text`, a closing bracket typed right after indents goes back one level
+	fn insert(&self, buffer: &mut Buffer, temp: &mut CodeEditorTemp, text: &str) {
+		let selection = temp.selection();
+		buffer.replace(selection.clone(), text);
+		let mut pointer = selection.start + text.chars().count();
+		if selection.is_empty() && matches!(text, "}" | ")" | "]") {
+			let line = buffer.line(buffer.line_of(selection.start));
+			if buffer.chars[line.start..selection.start].iter().all(|chr| chr.is_whitespace()) {
+				let removed = self.unindent_length(&buffer.chars[line.start..selection.start]);
+				buffer.replace(line.start..line.start + removed, "");
+				pointer -= removed;
+			}
+		}
+		temp.move_to(pointer, false);
+	}
+
+	/// line break keeping indent of current line, one more level after a opening bracket
+	fn new_line(&self, buffer: &mut Buffer, temp: &mut CodeEditorTemp) {
+		let selection = temp.selection();
+		let line = buffer.line(buffer.line_of(selection.start));
+		let before = &buffer.chars[line.start..selection.start];
+		let leading: String = before.iter().take_while(|chr| **chr == ' ' || **chr == '\t').collect();
+		let opening = before.iter().rev().find(|chr| !chr.is_whitespace()).copied();
+		let after = buffer.chars.get(selection.end).copied();
+		match opening.and_then(closing_bracket) {
+			Some(closing) => {
+				let inner = format!("\n{leading}{}", self.indent);
+				if after == Some(closing) {
+					self.insert(buffer, temp, &format!("{inner}\n{leading}"));
+					temp.move_to(selection.start + inner.chars().count(), false);
+				}else {
+					self.insert(buffer, temp, &inner);
+				}
+			},
+			None => self.insert(buffer, temp, &format!("\n{leading}")),
+		}
+	}
+
+	/// indent or unindent every line touched by selection
+	fn indent_lines(&self, buffer: &mut Buffer, temp: &mut CodeEditorTemp, is_unindent: bool) {
+		let selection = temp.selection();
+		let first = buffer.line_of(selection.start);
+		let mut last = buffer.line_of(selection.end);
+		// selection ending at the start of a line does not touch that line
+		if last > first && buffer.line_starts[last] == selection.end {
+			last -= 1;
+		}
+		let indent_length = self.indent.chars().count();
+		for line in (first..=last).rev() {
+			let start = buffer.line_starts[line];
+			if is_unindent {
+				let removed = self.unindent_length(&buffer.chars[buffer.line(line)]);
+				buffer.replace(start..start + removed, "");
+				let shift = |index: usize| if index >= start + removed { index - removed } else { index.min(start) };
+				temp.pointer = shift(temp.pointer);
+				temp.anchor = shift(temp.anchor);
+			}else {
+				buffer.replace(start..start, &self.indent);
+				let shift = |index: usize| if index > start { index + indent_length } else { index };
+				temp.pointer = shift(temp.pointer);
+				temp.anchor = shift(temp.anchor);
+			}
+		}
+	}
+
+	/// keyboard input goes to code
+	fn code_input(&self, ui: &mut Ui, buffer: &mut Buffer, temp: &mut CodeEditorTemp, input: &InputState, page: usize) {
+		let is_control = is_control_pressing(input);
+		let is_shift = is_shift_pressing(input);
+		let column = temp.column.take();
+		let is_redo = (is_shift && is_shortcut(input, Key::Z)) || is_shortcut(input, Key::Y);
+		if is_redo || is_shortcut(input, Key::Z) {
+			let moved = if is_redo {
+				temp.history.redo(buffer)
+			}else {
+				temp.history.undo(buffer)
+			};
+			if let Some((pointer, anchor)) = moved {
+				temp.pointer = pointer;
+				temp.anchor = anchor;
+			}
+			return;
+		}
+		let text: String = input.input_text().chars().filter(|chr| *chr != '\r').collect();
+		if !text.is_empty() {
+			self.insert(buffer, temp, &text);
+		}
+		if is_shortcut(input, Key::A) {
+			temp.anchor = 0;
+			temp.pointer = buffer.chars.len();
+		}
+		if is_shortcut(input, Key::C) || is_shortcut(input, Key::X) {
+			let selection = temp.selection();
+			if !selection.is_empty() {
+				ui.send_output_event(OutputEvent::ClipboardCopy(buffer.text(selection)));
+				if is_shortcut(input, Key::X) {
+					self.insert(buffer, temp, "");
+				}
+			}
+		}
+		if input.is_key_repeat(Key::Enter) {
+			self.new_line(buffer, temp);
+		}
+		if input.is_key_repeat(Key::Tab) {
+			let selection = temp.selection();
+			if is_shift || buffer.line_of(selection.start) != buffer.line_of(selection.end) {
+				self.indent_lines(buffer, temp, is_shift);
+			}else {
+				self.insert(buffer, temp, &self.indent);
+			}
+		}
+		if input.is_key_repeat(Key::Backspace) {
+			let selection = temp.selection();
+			if selection.is_empty() && selection.start > 0 {
+				temp.anchor = selection.start - 1;
+			}
+			self.insert(buffer, temp, "");
+		}
+		if input.is_key_repeat(Key::Delete) {
+			let selection = temp.selection();
+			if selection.is_empty() && selection.end < buffer.chars.len() {
+				temp.pointer = selection.end + 1;
+			}
+			self.insert(buffer, temp, "");
+		}
+
+		// move pointer and select
+		let selection = temp.selection();
+		let line = buffer.line_of(temp.pointer);
+		if input.is_key_repeat(Key::ArrowLeft) {
+			let index = if !selection.is_empty() && !is_shift {
+				selection.start
+			}else {
+				temp.pointer.saturating_sub(1)
+			};
+			temp.move_to(index, is_shift);
+		}
+		if input.is_key_repeat(Key::ArrowRight) {
+			let index = if !selection.is_empty() && !is_shift {
+				selection.end
+			}else {
+				(temp.pointer + 1).min(buffer.chars.len())
+			};
+			temp.move_to(index, is_shift);
+		}
+		let vertical = if input.is_key_repeat(Key::ArrowUp) {
+			Some(-1)
+		}else if input.is_key_repeat(Key::ArrowDown) {
+			Some(1)
+		}else if input.is_key_repeat(Key::PageUp) {
+			Some(-(page as isize))
+		}else if input.is_key_repeat(Key::PageDown) {
+			Some(page as isize)
+		}else {
+			None
+		};
+		if let Some(delta) = vertical {
+			let cells = column.unwrap_or_else(|| self.cells_of(buffer, temp.pointer) as f32);
+			let target = line as isize + delta;
+			let index = if target < 0 {
+				0
+			}else if target as usize >= buffer.line_count() {
+				buffer.chars.len()
+			}else {
+				self.index_at(buffer, target as usize, cells)
+			};
+			temp.move_to(index, is_shift);
+			temp.column = Some(cells);
+		}
+		if input.is_key_repeat(Key::Home) {
+			let index = if is_control {
+				0
+			}else {
+				// first press goes to first non-space charater
+				let range = buffer.line(line);
+				let first = range.start + buffer.chars[range.clone()].iter().take_while(|chr| chr.is_whitespace()).count();
+				if temp.pointer == first {
+					range.start
+				}else {
+					first
+				}
+			};
+			temp.move_to(index, is_shift);
+		}
+		if input.is_key_repeat(Key::End) {
+			let index = if is_control {
+				buffer.chars.len()
+			}else {
+				buffer.line(line).end
+			};
+			temp.move_to(index, is_shift);
+		}
+	}
+
+	/// keyboard input goes to the find bar
+	fn find_input(&self, buffer: &mut Buffer, temp: &mut CodeEditorTemp, input: &InputState) {
+		let Some(mut find) = temp.find.take() else {
+			return;
+		};
+		let typed: String = input.input_text().chars().filter(|chr| *chr != '\r' && *chr != '\n').collect();
+		let is_erased = input.is_key_repeat(Key::Backspace);
+		let field = if find.is_replacement_focused {
+			&mut find.replacement
+		}else {
+			&mut find.query
+		};
+		field.push_str(&typed);
+		if is_erased {
+			field.pop();
+		}
+		let is_query_changed = !find.is_replacement_focused && (!typed.is_empty() || is_erased);
+		if input.is_key_repeat(Key::Tab) && find.is_replacing {
+			find.is_replacement_focused = !find.is_replacement_focused;
+		}
+		let matches = find_matches(&buffer.chars, &find.query);
+		if is_query_changed {
+			// search again from current match
+			select_match(temp, &matches, temp.selection().start, false);
+		}
+		if input.is_key_repeat(Key::Enter) {
+			if !find.is_replacement_focused {
+				let is_backwards = is_shift_pressing(input);
+				let from = if is_backwards {
+					temp.selection().start
+				}else {
+					temp.selection().end
+				};
+				select_match(temp, &matches, from, is_backwards);
+			}else if is_control_pressing(input) {
+				// replace from back, so ranges in front stay same
+				for range in matches.iter().rev() {
+					buffer.replace(range.clone(), &find.replacement);
+				}
+				temp.move_to(temp.pointer.min(buffer.chars.len()), false);
+			}else {
+				if matches.contains(&temp.selection()) {
+					self.insert(buffer, temp, &find.replacement);
+				}
+				select_match(temp, &find_matches(&buffer.chars, &find.query), temp.pointer, false);
+			}
+		}
+		temp.find = Some(find);
+	}
+}
+
+impl Widget for CodeEditor<'_> {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		painter.set_transform_origin(response.area.area[0]);
+		let clip = painter.style().clip;
+		let padding = ui.style().space / 2.0;
+		let cell = self.cell_size();
+		let mut buffer = Buffer::new(self.input);
+		let gutter_width = self.gutter_width(buffer.line_count(), cell, padding);
+		let code_area = Area::new(response.area.area[0] + Vec2::x(gutter_width), response.area.area[1]);
+		let view = code_area.width_and_height() - Vec2::same(padding * 2.0);
+		let page = ((view.y / cell.y) as usize).max(1);
+		let mut temp: CodeEditorTemp = match response.memory_read() {
+			Some(t) => t,
+			None => {
+				ui.memory_save(&response.id, CodeEditorTemp::default());
+				CodeEditorTemp::default()
+			}
+		};
+		temp.pointer = temp.pointer.min(buffer.chars.len());
+		temp.anchor = temp.anchor.min(buffer.chars.len());
+		let before = (temp.pointer, temp.anchor);
+
+		// mouse
+		let input = ui.input().clone();
+		let cursor = input.cursor_position();
+		let is_hovering = cursor.is_some_and(|cursor| cursor.is_inside(response.area));
+		let code_origin = code_area.area[0] + Vec2::same(padding) - temp.scroll;
+		if response.is_pressed() {
+			temp.is_focused = true;
+			if let Some(cursor) = cursor {
+				let bar = temp.find.as_ref().map(|find| find_bar_area(find, code_area, cell, padding)).filter(|bar| bar.is_point_inside(&cursor));
+				match bar {
+					Some(bar) => if let Some(find) = &mut temp.find {
+						find.is_focused = true;
+						find.is_replacement_focused = find.is_replacing && find_field_area(bar, 1, cell, padding).is_point_inside(&cursor);
+					},
+					None => {
+						if let Some(find) = &mut temp.find {
+							find.is_focused = false;
+						}
+						let index = self.index_at_position(&buffer, cursor - code_origin, cell);
+						temp.move_to(index, is_shift_pressing(&input));
+						temp.column = None;
+						temp.is_selecting = true;
+					}
+				}
+			}
+		}else if input.is_any_mouse_pressed() && !is_hovering {
+			temp.is_focused = false;
+		}
+		if temp.is_selecting {
+			if !input.is_any_mouse_pressing() {
+				temp.is_selecting = false;
+			}else if let Some(cursor) = cursor {
+				temp.pointer = self.index_at_position(&buffer, cursor - code_origin, cell);
+			}
+		}
+		if response.is_multi_clicked(2) && !temp.find.as_ref().is_some_and(|find| find.is_focused) {
+			let is_word = |chr: char| chr.is_alphanumeric() || chr == '_';
+			let start = temp.pointer - buffer.chars[..temp.pointer].iter().rev().take_while(|chr| is_word(**chr)).count();
+			let end = temp.pointer + buffer.chars[temp.pointer..].iter().take_while(|chr| is_word(**chr)).count();
+			temp.anchor = start;
+			temp.pointer = end;
+		}
+		if is_hovering {
+			temp.scroll = temp.scroll - input.scroll();
+		}
+
+		// keyboard
+		if temp.is_focused {
+			let is_find = is_shortcut(&input, Key::F);
+			let is_replace = is_shortcut(&input, Key::H);
+			if is_find || is_replace {
+				let selected = buffer.text(temp.selection());
+				let find = temp.find.get_or_insert_with(FindBar::default);
+				if !selected.is_empty() && !selected.contains('\n') {
+					find.query = selected;
+				}
+				find.is_focused = true;
+				find.is_replacing = find.is_replacing || is_replace;
+				find.is_replacement_focused = is_replace;
+			}else if input.is_key_repeat(Key::Escape) && temp.find.is_some() {
+				temp.find = None;
+			}else if temp.find.as_ref().is_some_and(|find| find.is_focused) {
+				self.find_input(&mut buffer, &mut temp, &input);
+			}else {
+				self.code_input(ui, &mut buffer, &mut temp, &input, page);
+			}
+		}
+		let edits = std::mem::take(&mut buffer.edits);
+		temp.history.record(edits, before, (temp.pointer, temp.anchor));
+		if buffer.is_changed {
+			*self.input = buffer.text(0..buffer.chars.len());
+		}
+
+		// keep caret in view if it moved, then keep scroll inside code
+		let line_count = buffer.line_count();
+		let gutter_width = self.gutter_width(line_count, cell, padding);
+		let code_area = Area::new(response.area.area[0] + Vec2::x(gutter_width), response.area.area[1]);
+		let view = code_area.width_and_height() - Vec2::same(padding * 2.0);
+		if (temp.pointer, temp.anchor) != before || buffer.is_changed {
+			let caret = Vec2::new(self.cells_of(&buffer, temp.pointer) as f32 * cell.x, buffer.line_of(temp.pointer) as f32 * cell.y);
+			temp.scroll.x = temp.scroll.x.max(caret.x + cell.x - view.x).min(caret.x);
+			temp.scroll.y = temp.scroll.y.max(caret.y + cell.y - view.y).min(caret.y);
+		}
+		let widest = (0..line_count).map(|line| {
+			let range = buffer.line(line);
+			visual_column(&buffer.chars[range.clone()], range.len(), self.tab_size)
+		}).max().unwrap_or(0);
+		temp.scroll.x = temp.scroll.x.min(widest as f32 * cell.x + cell.x - view.x).max(0.0);
+		temp.scroll.y = temp.scroll.y.min(line_count as f32 * cell.y - view.y).max(0.0);
+		ui.memory_save(&response.id, &temp);
+
+		// draw
+		let text_color = ui.style().text_color;
+		let origin = code_area.area[0] + Vec2::same(padding) - temp.scroll;
+		let line_position = |line: usize, cells: usize| origin + Vec2::new(cells as f32 * cell.x, line as f32 * cell.y);
+		let first_line = (temp.scroll.y / cell.y) as usize;
+		let last_line = (((temp.scroll.y + view.y) / cell.y) as usize + 2).min(line_count);
+		let selection = temp.selection();
+		let current_line = buffer.line_of(temp.pointer);
+		let is_find_focused = temp.find.as_ref().is_some_and(|find| find.is_focused);
+		let matches = temp.find.as_ref().map(|find| find_matches(&buffer.chars, &find.query)).unwrap_or_default();
+		let kinds = char_kinds(self.input, self.tokenizer.as_ref());
+		// # background
+		painter.set_stroke_width(1.0);
+		painter.set_stroke_color(if temp.is_focused { ui.style().primary_color } else { ui.style().seprator_color });
+		painter.set_color(ui.style().background_color.brighter(0.05));
+		painter.set_position(response.area.area[0]);
+		painter.rect(response.area.width_and_height(), Vec2::same(4.0));
+		painter.set_stroke_width(0.0);
+		if self.line_numbers {
+			painter.set_color(ui.style().card_color);
+			painter.rect(Vec2::new(gutter_width, response.area.height()), Vec2::same(4.0));
+		}
+		painter.set_clip(code_area.cross_part(&clip));
+		// # lines
+		for line in first_line..last_line {
+			let range = buffer.line(line);
+			let chars = &buffer.chars[range.clone()];
+			let top = line_position(line, 0).y;
+			if temp.is_focused && line == current_line && selection.is_empty() {
+				painter.set_color(text_color.set_alpha(10));
+				painter.set_position(Vec2::new(code_area.area[0].x, top));
+				painter.rect(Vec2::new(code_area.width(), cell.y), Vec2::ZERO);
+			}
+			// selected line break takes a cell
+			let highlight = |painter: &mut Painter, part: &Range<usize>, color: Color| {
+				let from = part.start.max(range.start);
+				let to = part.end.min(range.end + 1);
+				if from < to {
+					let left = visual_column(chars, from - range.start, self.tab_size);
+					let right = visual_column(chars, to - range.start, self.tab_size) + usize::from(to > range.end);
+					painter.set_color(color);
+					painter.set_position(line_position(line, left));
+					painter.rect(Vec2::new((right - left) as f32 * cell.x, cell.y), Vec2::ZERO);
+				}
+			};
+			for part in &matches {
+				highlight(painter, part, ui.style().warning_color.set_alpha(60));
+			}
+			highlight(painter, &selection, ui.style().primary_color.set_alpha(100));
+			for (cells, run) in line_runs(chars, &kinds[range.clone()], self.tab_size) {
+				let left = line_position(line, cells).x;
+				let right = left + run.len() as f32 * cell.x;
+				if right >= code_area.area[0].x && left <= code_area.area[1].x {
+					let text: String = chars[run.clone()].iter().collect();
+					self.text.clone().set_text(text).set_color(token_color(kinds[range.start + run.start], text_color)).text_draw(painter, Vec2::new(left, top), ui);
+				}
+			}
+		}
+		// # matched brackets
+		if temp.is_focused && selection.is_empty() {
+			let brackets = [Some(temp.pointer), temp.pointer.checked_sub(1)].into_iter().flatten()
+				.find_map(|index| matching_bracket(&buffer.chars, &kinds, index).map(|other| [index, other]));
+			for index in brackets.into_iter().flatten() {
+				painter.set_stroke_width(1.0);
+				painter.set_stroke_color(text_color.set_alpha(120));
+				painter.set_color(text_color.set_alpha(30));
+				painter.set_position(line_position(buffer.line_of(index), self.cells_of(&buffer, index)));
+				painter.rect(cell, Vec2::same(2.0));
+			}
+			painter.set_stroke_width(0.0);
+		}
+		// # markers
+		let mut hovered_marker = None;
+		for marker in &self.markers {
+			let Some(line) = marker.line.checked_sub(1).filter(|line| *line < line_count) else {
+				continue;
+			};
+			let chars = &buffer.chars[buffer.line(line)];
+			let (from, to) = if marker.columns.is_empty() {
+				(chars.iter().take_while(|chr| chr.is_whitespace()).count(), chars.len())
+			}else {
+				(marker.columns.start.saturating_sub(1).min(chars.len()), marker.columns.end.saturating_sub(1).min(chars.len()))
+			};
+			let left = visual_column(chars, from, self.tab_size);
+			let right = visual_column(chars, to, self.tab_size).max(left + 1);
+			let area = Area::new(line_position(line, left), line_position(line, right) + Vec2::y(cell.y));
+			wavy_line(painter, area.left_bottom() - Vec2::y(2.0), area.width(), marker.status.into_color(ui));
+			if cursor.is_some_and(|cursor| area.is_point_inside(&cursor) && code_area.is_point_inside(&cursor)) {
+				hovered_marker = Some((marker, area));
+			}
+		}
+		// # caret
+		if temp.is_focused && !is_find_focused {
+			painter.set_color(text_color);
+			painter.set_position(line_position(current_line, self.cells_of(&buffer, temp.pointer)));
+			painter.rect(Vec2::new(2.0, cell.y), Vec2::ZERO);
+		}
+		// # gutter
+		if self.line_numbers {
+			painter.set_clip(Area::new(response.area.area[0], Vec2::new(code_area.area[0].x, response.area.area[1].y)).cross_part(&clip));
+			for line in first_line..last_line {
+				let color = if temp.is_focused && line == current_line {
+					text_color
+				}else {
+					text_color.set_alpha(100)
+				};
+				let number = self.text.clone().set_text((line + 1).to_string()).set_color(color);
+				let width = number.galley().size.x;
+				number.text_draw(painter, Vec2::new(code_area.area[0].x - padding - width, line_position(line, 0).y), ui);
+			}
+			for marker in &self.markers {
+				if let Some(line) = marker.line.checked_sub(1).filter(|line| (first_line..last_line).contains(line)) {
+					painter.set_color(marker.status.into_color(ui));
+					painter.set_position(Vec2::new(response.area.area[0].x + padding - 3.0, line_position(line, 0).y + cell.y / 2.0 - 3.0));
+					painter.cir(3.0);
+				}
+			}
+		}
+		// # find bar
+		if let Some(find) = &temp.find {
+			painter.set_clip(code_area.cross_part(&clip));
+			let bar = find_bar_area(find, code_area, cell, padding);
+			painter.set_stroke_width(1.0);
+			painter.set_stroke_color(ui.style().seprator_color);
+			painter.set_color(ui.style().card_color);
+			painter.set_position(bar.area[0]);
+			painter.rect(bar.width_and_height(), Vec2::same(4.0));
+			let fields = [(&find.query, "find", false), (&find.replacement, "replace", true)];
+			for (index, (content, hint, is_replacement)) in fields.into_iter().take(if find.is_replacing { 2 } else { 1 }).enumerate() {
+				let field = find_field_area(bar, index, cell, padding);
+				let is_field_focused = temp.is_focused && find.is_focused && find.is_replacement_focused == is_replacement;
+				painter.set_clip(code_area.cross_part(&clip));
+				painter.set_stroke_color(if is_field_focused { ui.style().primary_color } else { ui.style().seprator_color });
+				painter.set_stroke_width(1.0);
+				painter.set_color(ui.style().background_color.brighter(0.05));
+				painter.set_position(field.area[0]);
+				painter.rect(field.width_and_height(), Vec2::same(2.0));
+				painter.set_stroke_width(0.0);
+				painter.set_clip(field.cross_part(&code_area).cross_part(&clip));
+				let text_position = field.area[0] + Vec2::same(padding / 2.0);
+				let text = if content.is_empty() {
+					self.text.clone().set_text(hint).set_color(text_color.set_alpha(100))
+				}else {
+					self.text.clone().set_text(content.clone())
+				};
+				if !is_replacement {
+					let current = matches.iter().position(|range| *range == selection).map_or(0, |index| index + 1);
+					let count = self.text.clone().set_text(format!("{current}/{}", matches.len())).set_color(text_color.set_alpha(150));
+					let width = count.galley().size.x;
+					count.text_draw(painter, Vec2::new(field.area[1].x - padding / 2.0 - width, text_position.y), ui);
+				}
+				text.text_draw(painter, text_position, ui);
+				if is_field_focused {
+					let x = if content.is_empty() { 0.0 } else { text.galley().size.x };
+					painter.set_color(text_color);
+					painter.set_position(text_position + Vec2::x(x));
+					painter.rect(Vec2::new(2.0, cell.y), Vec2::ZERO);
+				}
+			}
+		}
+		// # marker message
+		if let Some((marker, area)) = hovered_marker {
+			painter.set_clip(clip);
+			let message = self.text.clone().set_text(marker.message.clone()).set_color(text_color);
+			let position = area.left_bottom() + Vec2::y(2.0);
+			painter.set_stroke_width(1.0);
+			painter.set_stroke_color(marker.status.into_color(ui));
+			painter.set_color(ui.style().card_color);
+			painter.set_position(position);
+			painter.rect(message.galley().size + Vec2::same(padding), Vec2::same(4.0));
+			painter.set_stroke_width(0.0);
+			message.text_draw(painter, position + Vec2::same(padding / 2.0), ui);
+		}
+		painter.set_clip(clip);
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		let padding = ui.style().space / 2.0;
+		let height = self.rows as f32 * self.cell_size().y + padding * 2.0;
+		let width = match self.width {
+			Some(t) => t,
+			None => ui.window_area().width() - (ui.available_position().x - ui.start_position().x) - ui.style().space
+		};
+		let area = match area {
+			Some(t) => t,
+			None => Area::new(ui.available_position(), ui.available_position() + Vec2::new(width, height))
+		};
+		ui.response(area, true, true)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// type `text` charater by charater at `at`
+	fn type_in(buffer: &mut Buffer, history: &mut History, text: &str, at: usize) -> usize {
+		let mut at = at;
+		for chr in text.chars() {
+			buffer.replace(at..at, &chr.to_string());
+			history.record(std::mem::take(&mut buffer.edits), (at, at), (at + 1, at + 1));
+			at += 1;
+		}
+		at
+	}
+
+	fn content(buffer: &Buffer) -> String {
+		buffer.text(0..buffer.chars.len())
+	}
+
+	#[test]
+	fn typed_words_are_undone_together() {
+		let mut buffer = Buffer::new("");
+		let mut history = History::default();
+		let end = type_in(&mut buffer, &mut history, "let x", 0);
+		assert_eq!(end, 5);
+		assert_eq!(history.undo.len(), 2);
+		assert_eq!(history.undo(&mut buffer), Some((4, 4)));
+		assert_eq!(content(&buffer), "let ");
+		assert_eq!(history.undo(&mut buffer), Some((0, 0)));
+		assert_eq!(content(&buffer), "");
+		assert_eq!(history.undo(&mut buffer), None);
+		assert_eq!(history.redo(&mut buffer), Some((4, 4)));
+		assert_eq!(history.redo(&mut buffer), Some((5, 5)));
+		assert_eq!(content(&buffer), "let x");
+		assert!(buffer.edits.is_empty());
+	}
+
+	#[test]
+	fn undo_reverts_several_replaces_in_order() {
+		let mut buffer = Buffer::new("a\nb\nc");
+		let mut history = History::default();
+		// indent two lines from back, like `CodeEditor::indent_lines`
+		buffer.replace(2..2, "\t");
+		buffer.replace(0..0, "\t");
+		history.record(std::mem::take(&mut buffer.edits), (0, 3), (1, 5));
+		assert_eq!(content(&buffer), "\ta\n\tb\nc");
+		assert_eq!(history.undo(&mut buffer), Some((0, 3)));
+		assert_eq!(content(&buffer), "a\nb\nc");
+		assert_eq!(history.redo(&mut buffer), Some((1, 5)));
+		assert_eq!(content(&buffer), "\ta\n\tb\nc");
+	}
+
+	#[test]
+	fn new_edits_drop_redo() {
+		let mut buffer = Buffer::new("");
+		let mut history = History::default();
+		type_in(&mut buffer, &mut history, "a", 0);
+		history.undo(&mut buffer);
+		buffer.replace(0..0, "b");
+		history.record(std::mem::take(&mut buffer.edits), (0, 0), (1, 1));
+		assert!(history.redo.is_empty());
+		assert_eq!(history.redo(&mut buffer), None);
+		assert_eq!(content(&buffer), "b");
+	}
+
+	#[test]
+	fn history_is_limited() {
+		let mut buffer = Buffer::new("");
+		let mut history = History::default();
+		for index in 0..MAX_UNDO_STEPS + 10 {
+			// removing breaks merging
+			buffer.replace(0..buffer.chars.len(), &index.to_string());
+			history.record(std::mem::take(&mut buffer.edits), (0, 0), (0, 0));
+		}
+		assert_eq!(history.undo.len(), MAX_UNDO_STEPS);
+	}
+
+	#[test]
+	fn runs_split_by_kind_and_whitespace() {
+		let line: Vec<char> = "\tlet ab = 1;".chars().collect();
+		let mut kinds = vec![TokenKind::Plain; line.len()];
+		kinds[1..4].fill(TokenKind::Keyword);
+		kinds[10] = TokenKind::Number;
+		kinds[11] = TokenKind::Punctuation;
+		let runs = line_runs(&line, &kinds, 4);
+		assert_eq!(runs, vec!((4, 1..4), (8, 5..7), (11, 8..9), (13, 10..11), (14, 11..12)));
+	}
+
+	#[test]
+	fn columns_follow_tab_stops() {
+		let line: Vec<char> = "a\tb".chars().collect();
+		assert_eq!(visual_column(&line, 2, 4), 4);
+		assert_eq!(visual_column(&line, 3, 4), 5);
+		assert_eq!(column_at(&line, 3.9, 4), 2);
+		assert_eq!(column_at(&line, 1.2, 4), 1);
+	}
+
+	#[test]
+	fn brackets_in_strings_are_skipped() {
+		let code = "f(\")\", (1))";
+		let chars: Vec<char> = code.chars().collect();
+		let kinds = char_kinds(code, &crate::widgets::RustTokenizer);
+		assert_eq!(matching_bracket(&chars, &kinds, 1), Some(10));
+		assert_eq!(matching_bracket(&chars, &kinds, 7), Some(9));
+		assert_eq!(matching_bracket(&chars, &kinds, 3), None);
+	}
+}
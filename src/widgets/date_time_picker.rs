@@ -0,0 +1,166 @@
+use time::Weekday;
+use time::OffsetDateTime;
+use crate::widgets::time_picker::draw_time_fields;
+use crate::widgets::time_picker::handle_time_fields;
+use crate::widgets::time_picker::time_fields_size;
+use crate::widgets::time_picker::TimeDragTemp;
+use crate::widgets::calendar::calendar_hit;
+use crate::widgets::calendar::calendar_size;
+use crate::widgets::calendar::draw_calendar;
+use crate::widgets::calendar::CalendarHit;
+use crate::widgets::calendar::CalendarView;
+use crate::widgets::DateTimePicker;
+use crate::widgets::TextSetting;
+use crate::widgets::Text;
+use nablo_shape::shape::shape_elements::Layer;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use nablo_shape::shape::Painter;
+use crate::Ui;
+use crate::Response;
+use crate::Widget;
+
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+struct DateTimePickerTemp {
+	is_open: bool,
+	view: Option<CalendarView>,
+	time: TimeDragTemp,
+}
+
+impl<'a> DateTimePicker<'a> {
+	/// get a date time picker
+	pub fn new(input: &'a mut OffsetDateTime) -> Self {
+		Self {
+			input,
+			show_seconds: true,
+			cell_size: 32.0,
+			first_weekday: Weekday::Monday,
+			speed: 0.1,
+		}
+	}
+
+	/// show seconds or not, by default its true
+	pub fn show_seconds(self, show_seconds: bool) -> Self {
+		Self {
+			show_seconds,
+			..self
+		}
+	}
+
+	/// set size of every day cell in the popup calendar, by default its 32.0
+	pub fn cell_size(self, cell_size: f32) -> Self {
+		Self {
+			cell_size,
+			..self
+		}
+	}
+
+	/// set which day a week starts with in the popup calendar, by default its monday
+	pub fn first_weekday(self, first_weekday: Weekday) -> Self {
+		Self {
+			first_weekday,
+			..self
+		}
+	}
+
+	/// set how many units will change when draging a pixel on time fields, by default its 0.1
+	pub fn speed(self, speed: f64) -> Self {
+		Self {
+			speed,
+			..self
+		}
+	}
+
+	fn date_text(&self) -> Text {
+		let date = self.input.date();
+		format!("{:04}-{:02}-{:02}", date.year(), u8::from(date.month()), date.day()).into()
+	}
+}
+
+impl Widget for DateTimePicker<'_> {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		painter.set_transform_origin(response.area.area[0]);
+		let space = ui.style().space;
+		let scale_factor = painter.style().scale_factor;
+		let date_text = self.date_text();
+		let date_text_area = date_text.text_area(painter);
+		let date_area = Area::new(response.area.left_top(), response.area.left_top() + Vec2::new(date_text_area.width() + space, response.area.height()));
+		let popup_size = calendar_size(self.cell_size) + Vec2::same(space);
+		let popup_area = Area::new(response.area.left_bottom() + Vec2::y(space / 2.0), response.area.left_bottom() + Vec2::y(space / 2.0) + popup_size);
+		let calendar_origin = popup_area.left_top() + Vec2::same(space / 2.0);
+
+		// logic
+		let mut temp: DateTimePickerTemp = response.memory_read().unwrap_or_default();
+		let mut view = temp.view.unwrap_or(self.input.date().into());
+		let cursor = ui.input().cursor_position().map(|inner| inner / scale_factor);
+		if ui.input().is_any_mouse_released() {
+			let cursor = cursor.unwrap_or(Vec2::INF);
+			if temp.is_open && popup_area.is_point_inside(&cursor) {
+				if let Some(hit) = calendar_hit(cursor, calendar_origin, self.cell_size, &view, self.first_weekday) {
+					if let CalendarHit::Day(date) = hit {
+						*self.input = self.input.replace_date(date);
+						temp.is_open = false;
+					}
+					view.apply(&hit);
+				}
+			}else if response.is_clicked() && date_area.is_point_inside(&cursor) {
+				temp.is_open = !temp.is_open;
+				view = self.input.date().into();
+			}else if !response.area.is_point_inside(&cursor) {
+				temp.is_open = false;
+			}
+		}
+		let time_origin = Vec2::new(date_area.right_top().x + space / 2.0, response.area.left_top().y);
+		let mut time = self.input.time();
+		let active = handle_time_fields(ui, painter, response, &mut temp.time, time_origin, self.show_seconds, self.speed, &mut time);
+		*self.input = self.input.replace_time(time);
+		temp.view = Some(view);
+		ui.memory_save(&response.id, &temp);
+		if temp.is_open {
+			ui.block_input(format!("{}:popup", response.id), popup_area, Layer::ToolTips);
+		}
+
+		// draw
+		// # date
+		painter.set_color(ui.style().background_color.brighter(if temp.is_open { 0.25 } else { 0.15 }));
+		painter.set_stroke_color(ui.style().slider_unreached_color);
+		painter.set_stroke_width(if temp.is_open { 2.0 } else { 1.0 });
+		painter.set_position(date_area.left_top());
+		painter.rect(date_area.width_and_height(), Vec2::same(2.5));
+		painter.set_stroke_width(0.0);
+		let date_text = self.date_text();
+		date_text.text_draw(painter, date_area.left_top() + (date_area.width_and_height() - date_text_area.width_and_height()) / 2.0, ui);
+		// # time
+		let color = ui.style().text_color;
+		draw_time_fields(ui, painter, time_origin, self.show_seconds, &time, active, color);
+		// # popup
+		if temp.is_open {
+			let clip = painter.style().clip;
+			let layer = painter.style().layer;
+			painter.set_clip(Area::INF);
+			painter.set_layer(Layer::ToolTips);
+			painter.set_color(ui.style().card_color.brighter(0.05));
+			painter.set_stroke_color(ui.style().seprator_color);
+			painter.set_stroke_width(1.0);
+			painter.set_position(popup_area.left_top());
+			painter.rect(popup_area.width_and_height(), Vec2::same(5.0));
+			painter.set_stroke_width(0.0);
+			let date = self.input.date();
+			draw_calendar(ui, painter, calendar_origin, self.cell_size, &view, self.first_weekday, (date, date), cursor);
+			painter.set_layer(layer);
+			painter.set_clip(clip);
+		}
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		if let Some(area) = area {
+			return ui.response(area, true, true);
+		}
+		let mut painter = ui.painter();
+		let space = ui.style().space;
+		let date_text_area = self.date_text().text_area(&mut painter);
+		let fields = time_fields_size(&mut painter, ui, self.show_seconds);
+		let size = Vec2::new(date_text_area.width() + space * 1.5 + fields.x, fields.y.max(date_text_area.height() + space));
+		ui.response(Area::new(ui.available_position(), ui.available_position() + size), true, true)
+	}
+}
@@ -0,0 +1,71 @@
+use nablo_shape::prelude::shape_elements::Color;
+use nablo_shape::math::Vec2;
+use crate::widgets::Gradient;
+use crate::widgets::Shadow;
+
+impl Default for Shadow {
+	fn default() -> Self {
+		Self {
+			offset: Vec2::new(0.0, 4.0),
+			blur: 12.0,
+			spread: 0.0,
+			color: [0, 0, 0, 100].into(),
+		}
+	}
+}
+
+impl Shadow {
+	/// a shadow with no spread
+	pub fn new(offset: Vec2, blur: f32, color: impl Into<Color>) -> Self {
+		Self {
+			offset,
+			blur,
+			spread: 0.0,
+			color: color.into(),
+		}
+	}
+
+	/// grow the shadow before blurring
+	pub fn spread(self, spread: f32) -> Self {
+		Self {
+			spread,
+			..self
+		}
+	}
+}
+
+impl Gradient {
+	/// from `top` to `bottom`
+	pub fn vertical(top: impl Into<Color>, bottom: impl Into<Color>) -> Self {
+		Self::Linear {
+			from: Vec2::new(0.5, 0.0),
+			to: Vec2::new(0.5, 1.0),
+			stops: vec!((0.0, top.into()), (1.0, bottom.into())),
+		}
+	}
+
+	/// from `left` to `right`
+	pub fn horizontal(left: impl Into<Color>, right: impl Into<Color>) -> Self {
+		Self::Linear {
+			from: Vec2::new(0.0, 0.5),
+			to: Vec2::new(1.0, 0.5),
+			stops: vec!((0.0, left.into()), (1.0, right.into())),
+		}
+	}
+
+	/// from `inner` at center of the shape to `outer` at `radius` away
+	pub fn radial(radius: f32, inner: impl Into<Color>, outer: impl Into<Color>) -> Self {
+		Self::Radial {
+			center: Vec2::same(0.5),
+			radius,
+			stops: vec!((0.0, inner.into()), (1.0, outer.into())),
+		}
+	}
+
+	/// color of the first stop, used where gradients are not drawn
+	pub fn fallback_color(&self) -> Option<Color> {
+		match self {
+			Self::Linear { stops, .. } | Self::Radial { stops, .. } => stops.first().map(|(_, color)| *color),
+		}
+	}
+}
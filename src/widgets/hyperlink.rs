@@ -0,0 +1,52 @@
+use crate::OutputEvent;
+use crate::widgets::TextSetting;
+use crate::widgets::Text;
+use crate::widgets::Hyperlink;
+use nablo_shape::math::Area;
+use nablo_shape::shape::Painter;
+use crate::Ui;
+use crate::Response;
+use crate::Widget;
+
+impl Hyperlink {
+	/// get a hyperlink showing `text` which opens `url` when clicked
+	pub fn new(text: impl Into<Text>, url: impl Into<String>) -> Self {
+		Self {
+			text: text.into().underline(true),
+			url: url.into(),
+		}
+	}
+
+	/// get a hyperlink showing its url
+	pub fn from_url(url: impl Into<String>) -> Self {
+		let url = url.into();
+		Self::new(url.clone(), url)
+	}
+}
+
+impl Widget for Hyperlink {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		if response.is_clicked() {
+			ui.send_output_event(OutputEvent::OpenUrl(self.url.clone()));
+		}
+		if self.text.color.is_none() {
+			self.text.color = Some(ui.style().primary_color);
+		}
+		if response.is_hovering() {
+			if let Some(color) = &mut self.text.color {
+				*color = color.brighter(ui.style().brighten_factor * 2.0);
+			}
+		}
+		self.text.text_draw(painter, response.area.left_top(), ui);
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		let mut painter = ui.painter();
+		let text_area = self.text_area(&mut painter);
+		let area = match area {
+			Some(t) => t,
+			None => Area::new(ui.available_position(), ui.available_position() + text_area.width_and_height())
+		};
+		ui.response(area, true, false)
+	}
+}
@@ -0,0 +1,202 @@
+use nablo_shape::prelude::shape_elements::Image as ShapeImage;
+use nablo_shape::prelude::shape_elements::Rect;
+use nablo_shape::prelude::shape_elements::Color;
+use nablo_shape::prelude::ShapeMask;
+use nablo_shape::shape::ShapeElement;
+use crate::widgets::ImageFit;
+use crate::texture::TextureStatus;
+use time::Duration;
+use time::OffsetDateTime;
+use crate::widgets::Image;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use nablo_shape::shape::Painter;
+use crate::Ui;
+use crate::Response;
+use crate::Widget;
+
+impl Image {
+	/// get a image showing texture `id` in `size`
+	pub fn new(id: impl Into<String>, size: Vec2) -> Self {
+		Self {
+			id: id.into(),
+			size,
+			fit: ImageFit::default(),
+			rounding: Vec2::ZERO,
+			tint: Color::from(1.0),
+			uv: Area::new(Vec2::ZERO, Vec2::same(1.0)),
+			rotate: 0.0,
+			is_clickable: false,
+		}
+	}
+
+	/// set how the texture fits into the size, by default its [`ImageFit::Contain`]
+	pub fn fit(self, fit: ImageFit) -> Self {
+		Self {
+			fit,
+			..self
+		}
+	}
+
+	/// set rounding of corners, only works when the whole image is visible
+	pub fn rounding(self, rounding: Vec2) -> Self {
+		Self {
+			rounding,
+			..self
+		}
+	}
+
+	/// multiply every pixel with this color, by default its white
+	pub fn tint(self, tint: impl Into<Color>) -> Self {
+		Self {
+			tint: tint.into(),
+			..self
+		}
+	}
+
+	/// only show part of the texture, `uv` is normalized, useful for sprite sheets
+	pub fn uv(self, uv: Area) -> Self {
+		Self {
+			uv,
+			..self
+		}
+	}
+
+	/// rotate the image around its center, in degrees
+	pub fn rotate(self, rotate: f32) -> Self {
+		Self {
+			rotate,
+			..self
+		}
+	}
+
+	/// make the image clickable like a [`crate::widgets::Button`]
+	pub fn clickable(self, is_clickable: bool) -> Self {
+		Self {
+			is_clickable,
+			..self
+		}
+	}
+
+	/// area the visible part of texture will be drawn in
+	fn display_area(&self, target: Area, texture_size: Vec2) -> Area {
+		let uv_size = self.uv.width_and_height();
+		let source = Vec2::new(texture_size.x * uv_size.x, texture_size.y * uv_size.y);
+		let size = if source.x <= 0.0 || source.y <= 0.0 {
+			target.width_and_height()
+		}else {
+			let scale = Vec2::new(target.width() / source.x, target.height() / source.y);
+			match self.fit {
+				ImageFit::Fill => target.width_and_height(),
+				ImageFit::Contain => source * scale.x.min(scale.y),
+				ImageFit::Cover => source * scale.x.max(scale.y),
+				ImageFit::None => source,
+			}
+		};
+		let left_top = target.left_top() + (target.width_and_height() - size) / 2.0;
+		Area::new(left_top, left_top + size)
+	}
+
+	/// dots turning around the center, one round per second
+	fn draw_spinner(&self, ui: &mut Ui, painter: &mut Painter, target: Area) {
+		const DOTS: usize = 8;
+		painter.set_color(ui.style().background_color.brighter(0.15));
+		painter.set_position(target.left_top());
+		painter.rect(target.width_and_height(), self.rounding);
+		let radius = (target.width().min(target.height()) / 4.0).clamp(4.0, 16.0);
+		let dot_radius = radius / 5.0;
+		let seconds = OffsetDateTime::now_utc().unix_timestamp_nanos() % 1_000_000_000;
+		let head = (seconds as f32 / 1e9 * DOTS as f32) as usize;
+		for index in 0..DOTS {
+			let angle = index as f32 / DOTS as f32 * std::f32::consts::TAU;
+			let fade = ((index + DOTS - head) % DOTS) as f32 / DOTS as f32;
+			painter.set_color(ui.style().text_color.set_alpha(1.0 - fade * 0.8));
+			painter.set_position(target.center() + Vec2::new(angle.cos(), angle.sin()) * radius - Vec2::same(dot_radius));
+			painter.cir(dot_radius);
+		}
+		ui.request_repaint_after(Duration::milliseconds(1000 / DOTS as i64));
+	}
+
+	/// rotate what is drawn around center of `target`
+	fn rotate_painter(&self, painter: &mut Painter, target: Area) {
+		if self.rotate != 0.0 {
+			painter.change_transform_origin(target.center());
+			painter.change_rotate(self.rotate);
+		}
+	}
+
+	fn draw_placeholder(&self, ui: &mut Ui, painter: &mut Painter, target: Area) {
+		painter.set_color(ui.style().background_color.brighter(0.15));
+		painter.set_stroke_color(ui.style().seprator_color);
+		painter.set_stroke_width(1.0);
+		painter.set_position(target.left_top());
+		painter.rect(target.width_and_height(), self.rounding);
+		painter.set_stroke_width(0.0);
+		painter.set_color(ui.style().seprator_color);
+		painter.set_position(Vec2::ZERO);
+		let width = Vec2::x(1.0);
+		painter.draw(ShapeElement::Polygon(vec!(target.left_top(), target.left_top() + width, target.right_bottom(), target.right_bottom() - width).into()));
+		painter.draw(ShapeElement::Polygon(vec!(target.right_top() - width, target.right_top(), target.left_bottom() + width, target.left_bottom()).into()));
+	}
+}
+
+impl Widget for Image {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		let target = response.area;
+		let mut image_painter = Painter::from_area(&painter.paint_area);
+		*image_painter.style_mut() = painter.style().clone();
+		if let Some(texture_size) = ui.texture_size(&self.id) {
+			// only the visible part is drawn, so rounding and rotation need no clipping
+			let display = self.display_area(target, texture_size);
+			let visible = display.cross_part(&target);
+			if visible.width() <= 0.0 || visible.height() <= 0.0 || display.width() <= 0.0 || display.height() <= 0.0 {
+				return;
+			}
+			let to_uv = |position: Vec2| {
+				let offset = position - display.left_top();
+				let normalized = Vec2::new(offset.x / display.width(), offset.y / display.height());
+				self.uv.left_top() + normalized * self.uv.width_and_height()
+			};
+			let uv = Area::new(to_uv(visible.left_top()), to_uv(visible.right_bottom()));
+			image_painter.set_position(visible.left_top());
+			image_painter.draw(ShapeElement::Image(ShapeImage {
+				id: self.id.clone(),
+				size: visible.width_and_height(),
+				mask: Some(ShapeMask::Rect(Rect {
+					width_and_height: visible.width_and_height(),
+					rounding: self.rounding,
+					..Default::default()
+				})),
+			}));
+			self.rotate_painter(&mut image_painter, target);
+			let tint = if self.is_clickable && response.is_hovering() {
+				self.tint.brighter(ui.style().brighten_factor)
+			}else {
+				self.tint
+			};
+			ui.paint_image(image_painter, uv, tint);
+			return;
+		}
+		if self.rotate == 0.0 {
+			image_painter.set_clip(painter.style().clip.cross_part(&target));
+		}
+		if let Some(TextureStatus::Loading) = ui.texture_status(&self.id) {
+			self.draw_spinner(ui, &mut image_painter, target);
+		}else {
+			self.draw_placeholder(ui, &mut image_painter, target);
+		}
+		self.rotate_painter(&mut image_painter, target);
+		if self.is_clickable && response.is_hovering() {
+			image_painter.brighter(ui.style().brighten_factor);
+		}
+		painter.append(&mut image_painter);
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		let area = match area {
+			Some(t) => t,
+			None => Area::new(ui.available_position(), ui.available_position() + self.size),
+		};
+		ui.response(area, self.is_clickable, false)
+	}
+}
@@ -1,7 +1,11 @@
 use crate::Response;
 use crate::Widget;
 use crate::widgets::Text;
+use crate::widgets::TextWrap;
+use crate::widgets::Truncate;
+use nablo_shape::shape::shape_elements::Layer;
 use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
 use nablo_shape::shape::Painter;
 use crate::Ui;
 use crate::widgets::TextSetting;
@@ -10,8 +14,46 @@ use crate::widgets::Label;
 impl Label {
 	/// create a new lable
 	pub fn new(text: impl Into<Text>) -> Self {
+		let mut text = text.into();
+		text.text = text.text.replace('\n', "");
+		for span in &mut text.spans {
+			span.text = span.text.replace('\n', "");
+		}
+		Self {
+			text,
+			show_full_on_hover: false,
+		}
+	}
+
+	/// set how this label breaks into lines, by default its [`TextWrap::Word`] and wraps at the available width
+	pub fn wrap(self, wrap: TextWrap) -> Self {
 		Self {
-			text: text.into().text.replace('\n', "").into(),
+			text: self.text.wrap(wrap),
+			..self
+		}
+	}
+
+	/// only show first `max_lines` lines
+	pub fn max_lines(self, max_lines: usize) -> Self {
+		Self {
+			text: self.text.max_lines(max_lines),
+			..self
+		}
+	}
+
+	/// set where to put `…` when label is too long
+	pub fn truncate(self, truncate: Truncate) -> Self {
+		Self {
+			text: self.text.truncate(truncate),
+			..self
+		}
+	}
+
+	/// show the whole text in a tooltip when hovering a truncated label
+	pub fn show_full_on_hover(self, show_full_on_hover: bool) -> Self {
+		Self {
+			show_full_on_hover,
+			..self
 		}
 	}
 }
@@ -19,9 +61,39 @@ impl Label {
 impl Widget for Label {
 	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
 		self.text_draw(painter, response.area.left_top(), ui);
+		if self.show_full_on_hover && response.is_hovering() && self.text.is_truncated() {
+			let space = ui.style().space;
+			let full = Text {
+				width: Some(ui.window_area().width() - space * 2.0),
+				height: None,
+				max_lines: None,
+				truncate: Truncate::Clip,
+				wrap: TextWrap::Word,
+				..self.text.clone()
+			};
+			let text_area = full.text_area(painter);
+			let position = response.area.left_bottom() + Vec2::y(space / 2.0);
+			let clip = painter.style().clip;
+			let layer = painter.style().layer;
+			painter.set_clip(Area::INF);
+			painter.set_layer(Layer::ToolTips);
+			painter.set_color(ui.style().card_color.brighter(0.1));
+			painter.set_position(position);
+			painter.rect(text_area.width_and_height() + Vec2::same(space), Vec2::same(5.0));
+			full.text_draw(painter, position + Vec2::same(space / 2.0), ui);
+			painter.set_layer(layer);
+			painter.set_clip(clip);
+		}
 	}
 
 	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		if self.text.width.is_none() {
+			let width = match area {
+				Some(area) => area.width(),
+				None => ui.window_area().width() - (ui.available_position().x - ui.start_position().x) - ui.style().space,
+			};
+			self.text.width = Some(width.max(0.0));
+		}
 		let mut painter = ui.painter();
 		let text_area = self.text_area(&mut painter);
 		let area = match area {
@@ -0,0 +1,697 @@
+use crate::OutputEvent;
+use crate::container::Card;
+use crate::container::Collapsing;
+use crate::widgets::rich_text::layout_spans;
+use crate::widgets::DivideLine;
+use crate::widgets::Image;
+use crate::widgets::Label;
+use crate::widgets::Markdown;
+use crate::widgets::RichText;
+use crate::widgets::Text;
+use crate::widgets::TextSetting;
+use crate::widgets::TextSpan;
+use crate::widgets::TextWrap;
+use nablo_shape::shape::shape_elements::EM;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use nablo_shape::shape::Painter;
+use crate::Ui;
+use crate::Response;
+use crate::Widget;
+use pulldown_cmark::CodeBlockKind;
+use pulldown_cmark::Event;
+use pulldown_cmark::Options;
+use pulldown_cmark::Parser;
+use pulldown_cmark::Tag;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+/// text scale of headings from `h1` to `h6`
+const HEADING_SCALES: [f32; 6] = [2.0, 1.6, 1.35, 1.15, 1.0, 0.9];
+/// width of the bar left to block quotes
+const QUOTE_BAR_WIDTH: f32 = 4.0;
+
+#[derive(Default, Clone, Debug, PartialEq)]
+struct Inline {
+	text: String,
+	is_bold: bool,
+	is_italic: bool,
+	is_strikethrough: bool,
+	is_code: bool,
+	link: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Piece {
+	Text(Inline),
+	/// url is a texture id
+	Image(String),
+}
+
+#[derive(Debug, PartialEq)]
+enum Block {
+	/// level from 1 to 6
+	Heading(usize, Vec<Piece>),
+	Paragraph(Vec<Piece>),
+	Code(String),
+	/// start number for ordered lists
+	List(Option<u64>, Vec<Vec<Block>>),
+	Quote(Vec<Block>),
+	Table {
+		header: Vec<Vec<Piece>>,
+		rows: Vec<Vec<Vec<Piece>>>,
+	},
+	Rule,
+	Details {
+		summary: String,
+		blocks: Vec<Block>,
+	},
+}
+
+enum Scope {
+	Root,
+	Quote,
+	List(Option<u64>, Vec<Vec<Block>>),
+	Item,
+	Details(String),
+}
+
+#[derive(Default)]
+struct TableBuilder {
+	header: Vec<Vec<Piece>>,
+	rows: Vec<Vec<Vec<Piece>>>,
+	row: Vec<Vec<Piece>>,
+}
+
+/// turns pulldown-cmark events into blocks
+struct Builder {
+	scopes: Vec<(Scope, Vec<Block>)>,
+	pieces: Vec<Piece>,
+	bold: usize,
+	italic: usize,
+	strikethrough: usize,
+	link: Option<String>,
+	/// texts inside a image are its alt, we do not show them
+	image: Option<String>,
+	heading: Option<usize>,
+	code: Option<String>,
+	table: Option<TableBuilder>,
+	summary: Option<String>,
+}
+
+impl Builder {
+	fn new() -> Self {
+		Self {
+			scopes: vec!((Scope::Root, vec!())),
+			pieces: vec!(),
+			bold: 0,
+			italic: 0,
+			strikethrough: 0,
+			link: None,
+			image: None,
+			heading: None,
+			code: None,
+			table: None,
+			summary: None,
+		}
+	}
+
+	fn push_block(&mut self, block: Block) {
+		if let Some((_, blocks)) = self.scopes.last_mut() {
+			blocks.push(block);
+		}
+	}
+
+	fn flush_paragraph(&mut self) {
+		let is_empty = self.pieces.iter().all(|piece| matches!(piece, Piece::Text(inline) if inline.text.trim().is_empty()));
+		let pieces = std::mem::take(&mut self.pieces);
+		if !is_empty {
+			self.push_block(Block::Paragraph(pieces));
+		}
+	}
+
+	fn close_scope(&mut self) {
+		if self.scopes.len() <= 1 {
+			return;
+		}
+		self.flush_paragraph();
+		let Some((scope, blocks)) = self.scopes.pop() else {
+			return;
+		};
+		match scope {
+			Scope::Root => self.scopes.push((Scope::Root, blocks)),
+			Scope::Quote => self.push_block(Block::Quote(blocks)),
+			Scope::List(start, items) => self.push_block(Block::List(start, items)),
+			Scope::Item => {
+				if let Some((Scope::List(_, items), _)) = self.scopes.last_mut() {
+					items.push(blocks);
+				}
+			},
+			Scope::Details(summary) => self.push_block(Block::Details {
+				summary: if summary.is_empty() { String::from("Details") } else { summary },
+				blocks,
+			}),
+		}
+	}
+
+	fn text(&mut self, text: &str, is_code: bool) {
+		if self.image.is_some() {
+			return;
+		}
+		if let Some(code) = &mut self.code {
+			code.push_str(text);
+		}else if let Some(summary) = &mut self.summary {
+			summary.push_str(text);
+		}else {
+			self.pieces.push(Piece::Text(Inline {
+				text: text.to_string(),
+				is_bold: self.bold > 0 || self.heading.is_some(),
+				is_italic: self.italic > 0,
+				is_strikethrough: self.strikethrough > 0,
+				is_code,
+				link: self.link.clone(),
+			}));
+		}
+	}
+
+	/// we only understand `<details>` and `<summary>`, other tags are ignored
+	fn html(&mut self, html: &str) {
+		let mut rest = html;
+		while let Some(start) = rest.find('<') {
+			if let Some(summary) = &mut self.summary {
+				summary.push_str(&rest[..start]);
+			}
+			let Some(end) = rest[start..].find('>') else {
+				return;
+			};
+			let tag = rest[start + 1..start + end].trim().to_lowercase();
+			rest = &rest[start + end + 1..];
+			match tag.split_whitespace().next().unwrap_or("") {
+				"details" => {
+					self.flush_paragraph();
+					self.scopes.push((Scope::Details(String::new()), vec!()));
+				},
+				"summary" => self.summary = Some(String::new()),
+				"/summary" => {
+					if let Some(summary) = self.summary.take() {
+						if let Some((Scope::Details(title), _)) = self.scopes.last_mut() {
+							*title = summary.trim().to_string();
+						}
+					}
+				},
+				"/details" => {
+					if matches!(self.scopes.last(), Some((Scope::Details(_), _))) {
+						self.close_scope();
+					}
+				},
+				_ => {},
+			}
+		}
+		if let Some(summary) = &mut self.summary {
+			summary.push_str(rest);
+		}
+	}
+
+	fn start(&mut self, tag: Tag) {
+		match tag {
+			Tag::Heading(level, _, _) => {
+				self.flush_paragraph();
+				self.heading = Some(level as usize);
+			},
+			Tag::BlockQuote => {
+				self.flush_paragraph();
+				self.scopes.push((Scope::Quote, vec!()));
+			},
+			Tag::CodeBlock(_) => {
+				self.flush_paragraph();
+				self.code = Some(String::new());
+			},
+			Tag::List(start) => {
+				self.flush_paragraph();
+				self.scopes.push((Scope::List(start, vec!()), vec!()));
+			},
+			Tag::Item => self.scopes.push((Scope::Item, vec!())),
+			Tag::Table(_) => {
+				self.flush_paragraph();
+				self.table = Some(TableBuilder::default());
+			},
+			Tag::TableCell => self.pieces.clear(),
+			Tag::Emphasis => self.italic += 1,
+			Tag::Strong => self.bold += 1,
+			Tag::Strikethrough => self.strikethrough += 1,
+			Tag::Link(_, url, _) => self.link = Some(url.to_string()),
+			Tag::Image(_, url, _) => self.image = Some(url.to_string()),
+			_ => {},
+		}
+	}
+
+	fn end(&mut self, tag: Tag) {
+		match tag {
+			Tag::Paragraph => self.flush_paragraph(),
+			Tag::Heading(_, _, _) => {
+				let pieces = std::mem::take(&mut self.pieces);
+				if let Some(level) = self.heading.take() {
+					self.push_block(Block::Heading(level, pieces));
+				}
+			},
+			Tag::BlockQuote | Tag::List(_) | Tag::Item => self.close_scope(),
+			Tag::CodeBlock(kind) => {
+				if let Some(code) = self.code.take() {
+					let code = match kind {
+						CodeBlockKind::Fenced(_) => code.trim_end_matches('\n').to_string(),
+						CodeBlockKind::Indented => code.trim_end().to_string(),
+					};
+					self.push_block(Block::Code(code));
+				}
+			},
+			Tag::TableCell => {
+				let cell = std::mem::take(&mut self.pieces);
+				if let Some(table) = &mut self.table {
+					table.row.push(cell);
+				}
+			},
+			Tag::TableHead => {
+				if let Some(table) = &mut self.table {
+					table.header = std::mem::take(&mut table.row);
+				}
+			},
+			Tag::TableRow => {
+				if let Some(table) = &mut self.table {
+					let row = std::mem::take(&mut table.row);
+					table.rows.push(row);
+				}
+			},
+			Tag::Table(_) => {
+				if let Some(table) = self.table.take() {
+					self.push_block(Block::Table { header: table.header, rows: table.rows });
+				}
+			},
+			Tag::Emphasis => self.italic = self.italic.saturating_sub(1),
+			Tag::Strong => self.bold = self.bold.saturating_sub(1),
+			Tag::Strikethrough => self.strikethrough = self.strikethrough.saturating_sub(1),
+			Tag::Link(_, _, _) => self.link = None,
+			Tag::Image(_, _, _) => {
+				if let Some(url) = self.image.take() {
+					self.pieces.push(Piece::Image(url));
+				}
+			},
+			_ => {},
+		}
+	}
+
+	fn finish(mut self) -> Vec<Block> {
+		while self.scopes.len() > 1 {
+			self.close_scope();
+		}
+		self.flush_paragraph();
+		self.scopes.pop().map(|(_, blocks)| blocks).unwrap_or_default()
+	}
+}
+
+fn parse(text: &str) -> Vec<Block> {
+	let mut builder = Builder::new();
+	for event in Parser::new_ext(text, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS) {
+		match event {
+			Event::Start(tag) => builder.start(tag),
+			Event::End(tag) => builder.end(tag),
+			Event::Text(text) => builder.text(&text, false),
+			Event::Code(code) => builder.text(&code, true),
+			Event::Html(html) => builder.html(&html),
+			Event::SoftBreak | Event::HardBreak => builder.text(" ", false),
+			Event::Rule => {
+				builder.flush_paragraph();
+				builder.push_block(Block::Rule);
+			},
+			Event::TaskListMarker(is_checked) => builder.text(if is_checked { "[x] " } else { "[ ] " }, false),
+			_ => {},
+		}
+	}
+	builder.finish()
+}
+
+fn content_hash(text: &str) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	text.hash(&mut hasher);
+	hasher.finish()
+}
+
+/// a paragraph with links in it, clicking a link asks the host to open its url
+struct LinkLabel {
+	label: Label,
+	/// link of every span
+	links: Vec<Option<String>>,
+}
+
+impl Widget for LinkLabel {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		self.label.draw(ui, response, painter);
+		if !response.is_clicked() {
+			return;
+		}
+		let Some(pointer) = response.pointer_position() else {
+			return;
+		};
+		let pointer = pointer / painter.style().scale_factor;
+		let (pieces, _) = layout_spans(&self.label.text, painter, self.label.text.width);
+		let clicked = pieces.iter().find(|piece| {
+			let left_top = response.area.left_top() + piece.position;
+			Area::new(left_top, left_top + piece.size).is_point_inside(&pointer)
+		});
+		if let Some(Some(url)) = clicked.map(|piece| &self.links[piece.span]) {
+			ui.send_output_event(OutputEvent::OpenUrl(url.clone()));
+		}
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		if self.label.text.width.is_none() {
+			let width = match area {
+				Some(area) => area.width(),
+				None => available_width(ui),
+			};
+			self.label.text.width = Some(width.max(0.0));
+		}
+		let mut painter = ui.painter();
+		let text_area = self.label.text_area(&mut painter);
+		let area = match area {
+			Some(t) => t,
+			None => Area::new(ui.available_position(), ui.available_position() + text_area.width_and_height())
+		};
+		ui.response(area, true, false)
+	}
+}
+
+fn available_width(ui: &Ui) -> f32 {
+	ui.window_area().width() - (ui.available_position().x - ui.start_position().x) - ui.style().space
+}
+
+fn span(inline: &Inline, ui: &Ui) -> TextSpan {
+	let mut span = TextSpan::new(inline.text.clone())
+		.bold(inline.is_bold)
+		.italic(inline.is_italic)
+		.strikethrough(inline.is_strikethrough);
+	if inline.is_code {
+		span = span.monospace(true).background(ui.style().card_color.brighter(0.1));
+	}
+	if inline.link.is_some() {
+		span = span.color(ui.style().primary_color).underline(true);
+	}
+	span
+}
+
+fn text_of(inlines: &[&Inline], ui: &Ui, scale: f32) -> Text {
+	let rich_text = inlines.iter().fold(RichText::new(), |rich_text, inline| rich_text.push(span(inline, ui)));
+	Text::from(rich_text).set_scale(Vec2::NOT_TO_SCALE * scale)
+}
+
+fn show_inlines(inlines: &[&Inline], ui: &mut Ui, scale: f32) {
+	if inlines.iter().all(|inline| inline.text.trim().is_empty()) {
+		return;
+	}
+	let label = Label::new(text_of(inlines, ui, scale));
+	if inlines.iter().any(|inline| inline.link.is_some()) {
+		ui.add(LinkLabel {
+			label,
+			links: inlines.iter().map(|inline| inline.link.clone()).collect(),
+		});
+	}else {
+		ui.add(label);
+	}
+}
+
+/// images break a paragraph, texts between them are shown as labels
+fn show_pieces(pieces: &[Piece], ui: &mut Ui, scale: f32) {
+	let mut inlines: Vec<&Inline> = vec!();
+	for piece in pieces {
+		match piece {
+			Piece::Text(inline) => inlines.push(inline),
+			Piece::Image(id) => {
+				show_inlines(&inlines, ui, scale);
+				inlines.clear();
+				let available = available_width(ui);
+				let size = match ui.texture_size(id) {
+					Some(size) if size.x > available && available > 0.0 => size * (available / size.x),
+					Some(size) => size,
+					None => Vec2::same(EM * 4.0),
+				};
+				ui.add(Image::new(id, size));
+			},
+		}
+	}
+	show_inlines(&inlines, ui, scale);
+}
+
+fn line_height(ui: &Ui) -> f32 {
+	Text::from(RichText::new().push(TextSpan::new("M").monospace(true))).text_area(&mut ui.painter()).height()
+}
+
+/// show `blocks` vertically from current position, returns where contents end
+fn show_vertically(blocks: &[Block], ui: &mut Ui, id: &str, quotes: &mut Vec<Area>) -> f32 {
+	ui.vertical(|ui| {
+		show_blocks(blocks, ui, id, quotes);
+		ui.available_position().y - ui.style().space
+	})
+}
+
+fn show_table(header: &[Vec<Piece>], rows: &[Vec<Vec<Piece>>], ui: &mut Ui) {
+	let cell_text = |cell: &[Piece], ui: &Ui, is_bold: bool| {
+		let inlines: Vec<Inline> = cell.iter().filter_map(|piece| match piece {
+			Piece::Text(inline) => Some(Inline { is_bold: inline.is_bold || is_bold, ..inline.clone() }),
+			Piece::Image(_) => None,
+		}).collect();
+		text_of(&inlines.iter().collect::<Vec<&Inline>>(), ui, 1.0)
+	};
+	let texts: Vec<Vec<Text>> = std::iter::once(header.iter().map(|cell| cell_text(cell, ui, true)).collect())
+		.chain(rows.iter().map(|row| row.iter().map(|cell| cell_text(cell, ui, false)).collect()))
+		.collect();
+	let columns = texts.iter().map(|row| row.len()).max().unwrap_or(0);
+	if columns == 0 {
+		return;
+	}
+	let space = ui.style().space;
+	let mut painter = ui.painter();
+	let mut widths = vec![0.0_f32; columns];
+	for row in &texts {
+		for (column, text) in row.iter().enumerate() {
+			widths[column] = widths[column].max(text.text_area(&mut painter).width());
+		}
+	}
+	// shrink all columns by same ratio when the table is too wide
+	let available = available_width(ui) - space * (columns - 1) as f32;
+	let total: f32 = widths.iter().sum();
+	if total > available && available > 0.0 {
+		widths.iter_mut().for_each(|width| *width *= available / total);
+	}
+	for (index, row) in texts.into_iter().enumerate() {
+		let height = row.iter().enumerate()
+			.map(|(column, text)| text.clone().set_width(widths[column]).text_area(&mut painter).height())
+			.fold(0.0, f32::max);
+		ui.horizental(|ui| {
+			for (column, text) in row.into_iter().enumerate() {
+				let position = ui.available_position();
+				ui.put(Label::new(text), Area::new(position, position + Vec2::new(widths[column], height)));
+			}
+		});
+		if index == 0 {
+			ui.add(DivideLine::new());
+		}
+	}
+}
+
+/// bars of block quotes are pushed into `quotes`, they are drawn by [`Markdown`] itself
+fn show_blocks(blocks: &[Block], ui: &mut Ui, id: &str, quotes: &mut Vec<Area>) {
+	for (index, block) in blocks.iter().enumerate() {
+		let id = format!("{}_{}", id, index);
+		match block {
+			Block::Heading(level, pieces) => show_pieces(pieces, ui, HEADING_SCALES[(*level).clamp(1, 6) - 1]),
+			Block::Paragraph(pieces) => show_pieces(pieces, ui, 1.0),
+			Block::Code(code) => {
+				let lines: Vec<&str> = code.lines().collect();
+				let space = ui.style().space;
+				let height = lines.len().max(1) as f32 * (line_height(ui) + space) + space;
+				let color = ui.style().card_color;
+				ui.show(&mut Card::new(id).set_color(color).set_height(height).set_scrollable_x(true), |ui, _| {
+					for line in lines {
+						let line = if line.is_empty() { " " } else { line };
+						ui.add(Label::new(RichText::new().push(TextSpan::new(line).monospace(true))).wrap(TextWrap::None));
+					}
+				});
+			},
+			Block::List(start, items) => {
+				for (number, item) in items.iter().enumerate() {
+					let marker = match start {
+						Some(start) => format!("{}.", start + number as u64),
+						None => String::from("•"),
+					};
+					let id = format!("{}_{}", id, number);
+					ui.horizental(|ui| {
+						ui.label(marker);
+						ui.vertical(|ui| show_blocks(item, ui, &id, quotes));
+					});
+				}
+			},
+			Block::Quote(blocks) => {
+				let top = ui.available_position();
+				let indent = QUOTE_BAR_WIDTH + ui.style().space;
+				ui.available_position.x += indent;
+				let bottom = show_vertically(blocks, ui, &id, quotes);
+				ui.available_position.x -= indent;
+				quotes.push(Area::new(top, Vec2::new(top.x + QUOTE_BAR_WIDTH, bottom.max(top.y))));
+			},
+			Block::Table { header, rows } => show_table(header, rows, ui),
+			Block::Rule => {
+				ui.add(DivideLine::new());
+			},
+			Block::Details { summary, blocks } => {
+				ui.show(&mut Collapsing::new(id.clone()).set_text(summary.clone()), |ui, _| show_blocks(blocks, ui, &id, quotes));
+			},
+		}
+	}
+}
+
+impl Markdown {
+	/// get a markdown showing `text`
+	pub fn new(text: impl Into<String>) -> Self {
+		Self {
+			text: text.into(),
+			id: None,
+			quotes: vec!(),
+		}
+	}
+
+	/// set id of containers inside, needed when showing same text twice in one [`Ui`]
+	pub fn id(self, id: impl Into<String>) -> Self {
+		Self {
+			id: Some(id.into()),
+			..self
+		}
+	}
+}
+
+impl Widget for Markdown {
+	fn draw(&mut self, ui: &mut Ui, _: &Response, painter: &mut Painter) {
+		painter.set_color(ui.style().primary_color);
+		for area in &self.quotes {
+			painter.set_position(area.left_top());
+			painter.rect(area.width_and_height(), Vec2::same(QUOTE_BAR_WIDTH / 2.0));
+		}
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		// parsing is cheaper than reading a parsed document back from memory, so we parse every frame
+		let blocks = parse(&self.text);
+		let id = self.id.clone().unwrap_or_else(|| format!("markdown_{:x}", content_hash(&self.text)));
+		let position = ui.available_position();
+		let start = match area {
+			Some(area) => area.left_top(),
+			None => position,
+		};
+		let width = available_width(ui);
+		ui.available_position = start;
+		let mut quotes = vec!();
+		let bottom = show_vertically(&blocks, ui, &id, &mut quotes);
+		self.quotes = quotes;
+		// contents moved the cursor already, the response moves it again
+		ui.available_position = position;
+		let area = match area {
+			Some(t) => t,
+			None => Area::new(start, Vec2::new(start.x + width.max(0.0), bottom.max(start.y))),
+		};
+		ui.response(area, false, false)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn plain(text: &str) -> Piece {
+		Piece::Text(Inline {
+			text: text.to_string(),
+			..Default::default()
+		})
+	}
+
+	#[test]
+	fn headings_and_emphasis() {
+		let blocks = parse("## nablo\n\nsome *soft* and **bold** ~~old~~ `code`");
+		assert_eq!(blocks.len(), 2);
+		assert_eq!(blocks[0], Block::Heading(2, vec!(Piece::Text(Inline { text: String::from("nablo"), is_bold: true, ..Default::default() }))));
+		let Block::Paragraph(pieces) = &blocks[1] else {
+			panic!("expected a paragraph, got {:?}", blocks[1]);
+		};
+		let inlines: Vec<&Inline> = pieces.iter().filter_map(|piece| match piece {
+			Piece::Text(inline) => Some(inline),
+			Piece::Image(_) => None,
+		}).collect();
+		assert!(inlines.iter().any(|inline| inline.text == "soft" && inline.is_italic && !inline.is_bold));
+		assert!(inlines.iter().any(|inline| inline.text == "bold" && inline.is_bold));
+		assert!(inlines.iter().any(|inline| inline.text == "old" && inline.is_strikethrough));
+		assert!(inlines.iter().any(|inline| inline.text == "code" && inline.is_code));
+	}
+
+	#[test]
+	fn links_and_images() {
+		let blocks = parse("[nablo](https://github.com/5av1ng/nablo) ![alt text](logo)");
+		let Block::Paragraph(pieces) = &blocks[0] else {
+			panic!("expected a paragraph, got {:?}", blocks[0]);
+		};
+		assert_eq!(pieces[0], Piece::Text(Inline {
+			text: String::from("nablo"),
+			link: Some(String::from("https://github.com/5av1ng/nablo")),
+			..Default::default()
+		}));
+		// alt texts are not shown
+		assert_eq!(pieces.last(), Some(&Piece::Image(String::from("logo"))));
+		assert!(!pieces.contains(&plain("alt text")));
+	}
+
+	#[test]
+	fn lists_keep_start_and_nesting() {
+		let blocks = parse("3. first\n4. second\n   - inner");
+		let Block::List(start, items) = &blocks[0] else {
+			panic!("expected a list, got {:?}", blocks[0]);
+		};
+		assert_eq!(*start, Some(3));
+		assert_eq!(items.len(), 2);
+		assert_eq!(items[0], vec!(Block::Paragraph(vec!(plain("first")))));
+		assert_eq!(items[1][0], Block::Paragraph(vec!(plain("second"))));
+		assert_eq!(items[1][1], Block::List(None, vec!(vec!(Block::Paragraph(vec!(plain("inner")))))));
+	}
+
+	#[test]
+	fn code_quotes_and_rules() {
+		let blocks = parse("```rust\nfn main() {}\n```\n\n> quoted\n\n---");
+		assert_eq!(blocks, vec!(
+			Block::Code(String::from("fn main() {}")),
+			Block::Quote(vec!(Block::Paragraph(vec!(plain("quoted"))))),
+			Block::Rule,
+		));
+	}
+
+	#[test]
+	fn tables_split_header_and_rows() {
+		let blocks = parse("| a | b |\n|---|---|\n| 1 | 2 |\n| 3 | 4 |");
+		assert_eq!(blocks, vec!(Block::Table {
+			header: vec!(vec!(plain("a")), vec!(plain("b"))),
+			rows: vec!(
+				vec!(vec!(plain("1")), vec!(plain("2"))),
+				vec!(vec!(plain("3")), vec!(plain("4"))),
+			),
+		}));
+	}
+
+	#[test]
+	fn details_become_collapsings() {
+		let blocks = parse("<details>\n<summary>More</summary>\n\nhidden\n\n</details>\n\nafter");
+		assert_eq!(blocks, vec!(
+			Block::Details {
+				summary: String::from("More"),
+				blocks: vec!(Block::Paragraph(vec!(plain("hidden")))),
+			},
+			Block::Paragraph(vec!(plain("after"))),
+		));
+		let blocks = parse("<details>\n\nno summary\n\n</details>");
+		assert!(matches!(&blocks[0], Block::Details { summary, .. } if summary == "Details"));
+	}
+}
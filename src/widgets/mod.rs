@@ -11,7 +11,28 @@ use nablo_shape::math::Area;
 use nablo_shape::math::Vec2;
 use nablo_shape::shape::Painter;
 use crate::Ui;
+use crate::integrator::ShapeExtra;
+use crate::texture::Playback;
 use nablo_shape::shape::shape_elements::EM;
+use std::ops::RangeInclusive;
+use std::ops::Range;
+use crate::font::family_id;
+use crate::font::font_book;
+use crate::font::DEFAULT_FAMILY;
+use crate::text_layout::layout;
+use crate::text_layout::Galley;
+use time::OffsetDateTime;
+use time::Weekday;
+use time::Date;
+use time::Time;
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+use std::sync::Arc;
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+use std::collections::HashMap;
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+use std::any::Any;
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+use std::any::TypeId;
 
 mod button;
 mod canvas;
@@ -22,6 +43,21 @@ mod slider;
 mod dragable_value;
 mod divide_line;
 mod progress_bar;
+mod calendar;
+mod time_picker;
+mod date_time_picker;
+mod plot;
+mod image;
+mod nine_patch;
+mod sprite_sheet;
+mod rich_text;
+mod hyperlink;
+mod markdown;
+mod code_editor;
+mod tokenizer;
+mod effect;
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+pub(crate) mod paint_callback;
 
 /// a general style used by all wigets
 #[derive(Clone)]
@@ -92,7 +128,175 @@ pub struct Text {
 	pub width: Option<f32>,
 	pub height: Option<f32>,
 	pub underline: bool,
-	pub style: TextStyle
+	pub style: TextStyle,
+	/// if not empty, text will be drawn span by span and `text` only keeps the plain string, see [`RichText`]
+	#[serde(default)]
+	pub spans: Vec<TextSpan>,
+	/// font family registered by [`Ui::register_font`], `None` for build-in font
+	#[serde(default)]
+	pub family: Option<String>,
+	#[serde(default)]
+	pub wrap: TextWrap,
+	#[serde(default)]
+	pub max_lines: Option<usize>,
+	#[serde(default)]
+	pub truncate: Truncate,
+	#[serde(default)]
+	pub outline: Option<TextOutline>,
+	#[serde(default)]
+	pub shadow: Option<TextShadow>,
+}
+
+/// a line around every charater of a [`Text`], see [`Text::outline`]
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct TextOutline {
+	pub width: f32,
+	pub color: Color,
+}
+
+/// a shadow under a [`Text`], see [`Text::shadow`]
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct TextShadow {
+	pub offset: Vec2,
+	/// how far the shadow spreads, 0.0 for a sharp shadow
+	pub blur: f32,
+	pub color: Color,
+}
+
+/// drawn with a shape beyond its flat color, see [`Ui::paint_with_effects`].
+///
+/// only the wgpu renderer draws them, other backends get the shape itself with its color as fallback.
+/// shapes with effects are always drawn by the default shader.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Effect {
+	/// fills the shape instead of its color, alpha of the color is kept
+	Gradient(Gradient),
+	/// drawn behind the shape
+	DropShadow(Shadow),
+	/// drawn inside the shape, over it
+	InnerShadow(Shadow),
+	/// blurs what is drawn behind the shape by this radius, the shape itself should be translucent to show it
+	BackdropBlur(f32),
+}
+
+/// a color changing across a shape, see [`Effect::Gradient`]
+///
+/// at most [`crate::MAX_GRADIENT_STOPS`] stops are used, each stop is a offset from 0.0 to 1.0 and its color.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Gradient {
+	/// `from` and `to` are normalized in the bounding box of the shape, (0.0, 0.0) for left top
+	Linear {
+		from: Vec2,
+		to: Vec2,
+		stops: Vec<(f32, Color)>,
+	},
+	/// `center` is normalized in the bounding box of the shape
+	Radial {
+		center: Vec2,
+		radius: f32,
+		stops: Vec<(f32, Color)>,
+	},
+}
+
+/// a blurred copy of a shape under it or along the inside of its outline, see [`Effect::DropShadow`] and [`Effect::InnerShadow`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shadow {
+	pub offset: Vec2,
+	/// how far the shadow fades out
+	pub blur: f32,
+	/// grows the shadow before blurring
+	pub spread: f32,
+	pub color: Color,
+}
+
+/// how a [`Text`] breaks into lines when its width is limited
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum TextWrap {
+	/// only breaks at `\n`
+	None,
+	/// breaks between words, words wider than a line will be broken as well
+	#[default]
+	Word,
+	/// breaks between any charaters
+	Character,
+}
+
+/// where to put `…` when a [`Text`] is longer than its width, height or max lines allowed
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Truncate {
+	/// just cut overflowed lines
+	#[default]
+	Clip,
+	Start,
+	Middle,
+	End,
+}
+
+/// how many truncated galleys we keep, less recently used half is dropped when exceeded
+const MAX_TRUNCATED: usize = 256;
+
+/// everything decides how a [`Text`] is truncated
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct TruncationKey {
+	text: String,
+	family: u32,
+	/// bits of `f32`s
+	size: [u32; 2],
+	width: Option<u32>,
+	height: Option<u32>,
+	wrap: TextWrap,
+	max_lines: Option<usize>,
+	truncate: Truncate,
+	/// see [`crate::font::FontBook::generation`]
+	fonts: u64,
+}
+
+/// truncated galleys of a [`crate::font::FontBook`], so they go with fonts of their [`Ui`]
+#[derive(Default)]
+pub(crate) struct TruncationCache {
+	/// galleys and when they are used last time
+	galleys: std::collections::HashMap<TruncationKey, (Galley, u64)>,
+	clock: u64,
+}
+
+impl TruncationCache {
+	fn get(&mut self, key: &TruncationKey) -> Option<Galley> {
+		self.clock += 1;
+		let (galley, used) = self.galleys.get_mut(key)?;
+		*used = self.clock;
+		Some(galley.clone())
+	}
+
+	fn insert(&mut self, key: TruncationKey, galley: Galley) {
+		if self.galleys.len() >= MAX_TRUNCATED {
+			let mut used: Vec<u64> = self.galleys.values().map(|(_, used)| *used).collect();
+			used.sort_unstable();
+			let median = used[used.len() / 2];
+			self.galleys.retain(|_, (_, used)| *used > median);
+		}
+		self.galleys.insert(key, (galley, self.clock));
+	}
+}
+
+/// charaters kept when truncating `chars` to `keep` charaters, by their index in `chars`. `None` is the `…`
+fn truncated_sources(chars: &[char], keep: usize, truncate: Truncate) -> Vec<Option<usize>> {
+	let len = chars.len();
+	let keep = keep.min(len);
+	let trim_start = |range: Range<usize>| range.skip_while(move |index| chars[*index].is_whitespace()).map(Some);
+	let trim_end = |range: Range<usize>| {
+		let end = range.clone().rev().find(|index| !chars[*index].is_whitespace()).map_or(range.start, |index| index + 1);
+		(range.start..end).map(Some)
+	};
+	let ellipsis = std::iter::once(None);
+	match truncate {
+		Truncate::Start => ellipsis.chain(trim_start(len - keep..len)).collect(),
+		Truncate::Middle => trim_end(0..(keep + 1) / 2).chain(ellipsis).chain(trim_start(len - keep / 2..len)).collect(),
+		_ => trim_end(0..keep).chain(ellipsis).collect(),
+	}
+}
+
+fn source_text(chars: &[char], sources: &[Option<usize>]) -> String {
+	sources.iter().map(|source| source.map_or('…', |index| chars[index])).collect()
 }
 
 impl Default for Text {
@@ -105,7 +309,14 @@ impl Default for Text {
 			width: None,
 			height: None,
 			underline: false,
-			style: TextStyle::default()
+			style: TextStyle::default(),
+			spans: vec!(),
+			family: None,
+			wrap: TextWrap::default(),
+			max_lines: None,
+			truncate: Truncate::default(),
+			outline: None,
+			shadow: None,
 		}
 	}
 }
@@ -114,6 +325,139 @@ impl Text {
 	pub fn is_empty(&self) -> bool {
 		self.text.is_empty()
 	}
+
+	/// lay out this text by [`crate::text_layout::layout`], spans are not included. if text is too long, it will be truncated as [`Self::truncate`] says.
+	///
+	/// truncated galleys are cached with fonts of current [`Ui`], [`crate::text_layout::PositionedGlyph::source`] maps their charaters back to [`Self::text`].
+	pub fn galley(&self) -> Galley {
+		if self.truncate == Truncate::Clip {
+			return self.layout_string(&self.text);
+		}
+		let fonts = font_book();
+		let key = TruncationKey {
+			text: self.text.clone(),
+			family: self.family_id(),
+			size: [self.size.x.to_bits(), self.size.y.to_bits()],
+			width: self.width.map(f32::to_bits),
+			height: self.height.map(f32::to_bits),
+			wrap: self.wrap,
+			max_lines: self.max_lines,
+			truncate: self.truncate,
+			fonts: fonts.read().unwrap().generation(),
+		};
+		let cached = fonts.read().unwrap().truncated.lock().unwrap().get(&key);
+		if let Some(galley) = cached {
+			return galley;
+		}
+		// laying out reads fonts as well, so no lock is held meanwhile
+		let galley = self.truncated_galley();
+		fonts.read().unwrap().truncated.lock().unwrap().insert(key, galley.clone());
+		galley
+	}
+
+	/// the longest truncated text fits, found by binary search
+	fn truncated_galley(&self) -> Galley {
+		let galley = self.layout_string(&self.text);
+		if self.fits(&galley) {
+			return galley;
+		}
+		let chars: Vec<char> = self.text.chars().collect();
+		let sources = |keep: usize| truncated_sources(&chars, keep, self.truncate);
+		let (mut low, mut high) = (0, chars.len());
+		while low < high {
+			let middle = (low + high + 1) / 2;
+			if self.fits(&self.layout_string(&source_text(&chars, &sources(middle)))) {
+				low = middle;
+			}else {
+				high = middle - 1;
+			}
+		}
+		let sources = sources(low);
+		let mut galley = self.layout_string(&source_text(&chars, &sources));
+		for (glyph, source) in galley.glyphs.iter_mut().zip(sources) {
+			glyph.source = source;
+		}
+		galley
+	}
+
+	/// is this text too long to be shown completely
+	pub fn is_truncated(&self) -> bool {
+		!self.fits(&self.layout_string(&self.text))
+	}
+
+	/// set how this text breaks into lines, by default its [`TextWrap::Word`]
+	pub fn wrap(self, wrap: TextWrap) -> Self {
+		Self {
+			wrap,
+			..self
+		}
+	}
+
+	/// only show first `max_lines` lines
+	pub fn max_lines(self, max_lines: usize) -> Self {
+		Self {
+			max_lines: Some(max_lines),
+			..self
+		}
+	}
+
+	/// set where to put `…` when text is too long, by default its [`Truncate::Clip`]
+	pub fn truncate(self, truncate: Truncate) -> Self {
+		Self {
+			truncate,
+			..self
+		}
+	}
+
+	fn family_id(&self) -> u32 {
+		self.family.as_deref().map_or(DEFAULT_FAMILY, family_id)
+	}
+
+	fn layout_string(&self, text: &str) -> Galley {
+		layout(text, self.family_id(), self.size, self.width, self.wrap)
+	}
+
+	fn fits(&self, galley: &Galley) -> bool {
+		let is_lines_fit = self.max_lines.map_or(true, |max_lines| galley.rows.len() <= max_lines);
+		let is_width_fit = self.wrap != TextWrap::None || self.width.map_or(true, |width| galley.size.x <= width);
+		let is_height_fit = self.height.map_or(true, |height| galley.size.y <= height);
+		is_lines_fit && is_width_fit && is_height_fit
+	}
+
+	/// how many rows can be shown in limited height and lines, at least one
+	fn visible_rows(&self, galley: &Galley) -> usize {
+		let rows = match self.height {
+			Some(height) => galley.rows.iter().filter(|row| row.top + row.height <= height).count().max(1),
+			None => galley.rows.len(),
+		};
+		rows.min(self.max_lines.unwrap_or(usize::MAX).max(1))
+	}
+
+	/// draw this text with a font family registered by [`Ui::register_font`]
+	pub fn family(self, family: impl Into<String>) -> Self {
+		Self {
+			family: Some(family.into()),
+			..self
+		}
+	}
+
+	/// draw a line with `width` around every charater. it is drawn from distance fields of glyphs by the wgpu renderer,
+	/// so it can not be wider than a sixth of the font size
+	pub fn outline(self, width: f32, color: impl Into<Color>) -> Self {
+		Self {
+			outline: Some(TextOutline { width, color: color.into() }),
+			..self
+		}
+	}
+
+	/// draw a shadow moved by `offset` under the text, larger `blur` makes it softer up to a sixth of the font size.
+	/// only the wgpu renderer draws it
+	pub fn shadow(self, offset: Vec2, blur: f32, color: impl Into<Color>) -> Self {
+		Self {
+			shadow: Some(TextShadow { offset, blur, color: color.into() }),
+			..self
+		}
+	}
 }
 
 /// a trait that allows you change text styles.
@@ -157,6 +501,7 @@ impl TextSetting for Text {
 	fn set_text(self, text: impl Into<String>) -> Self{
 		Self {
 			text: text.into(),
+			spans: vec!(),
 			..self
 		}
 	}
@@ -204,6 +549,10 @@ impl TextSetting for Text {
 	}
 
 	fn text_draw(&self, painter: &mut Painter, position_given: Vec2, ui: &mut Ui) {
+		if !self.spans.is_empty() {
+			rich_text::draw_spans(self, painter, position_given, ui);
+			return;
+		}
 		let scale = painter.style().size;
 		let position = painter.style().position;
 		let color = painter.style().fill;
@@ -212,15 +561,25 @@ impl TextSetting for Text {
 		painter.set_color(self.get_color(ui));
 		painter.set_position(position_given);
 		painter.set_scale(self.size);
-		if let Some(width) = self.width {
-			if let Some(height) = self.height {
-				painter.text_with_limit(self.text.clone(), width, height);
-			}else {
-				painter.text_with_width(self.text.clone(), width);
+		let galley = self.galley();
+		let rows = &galley.rows[..self.visible_rows(&galley).min(galley.rows.len())];
+		for row in rows {
+			let text = galley.visual_row_text(row);
+			if text.is_empty() {
+				continue;
 			}
-		}else {
-			painter.text(self.text.clone());
+			// glyphs, outline and shadow go with the text shape to the renderer
+			ui.shape.text_extras.push((text.clone(), ShapeExtra {
+				outline: self.outline,
+				text_shadow: self.shadow,
+				text_row: Some(Arc::new(galley.text_row(row))),
+				font_family: self.family_id(),
+				..Default::default()
+			}));
+			painter.set_position(position_given + Vec2::new(row.left, row.top));
+			painter.text(text);
 		}
+		painter.set_position(position_given);
 		if self.underline {
 			// TODO: make this changable
 			let rect_height = 5.0;
@@ -235,11 +594,16 @@ impl TextSetting for Text {
 	}
 
 	fn text_area(&self, painter: &mut Painter) -> Area {
-		let scale = painter.style().size;
-		painter.set_scale(self.size);
-		let back = painter.text_area(self.text.clone());
-		painter.set_scale(scale);
-		back
+		if !self.spans.is_empty() {
+			let position = painter.style().position;
+			let (_, size) = rich_text::layout_spans(self, painter, self.width);
+			let size = Vec2::new(size.x, self.height.map_or(size.y, |height| size.y.min(height)));
+			return Area::new(position, position + size);
+		}
+		let position = painter.style().position;
+		let galley = self.galley();
+		let height = galley.rows.iter().take(self.visible_rows(&galley)).last().map_or(0.0, |row| row.top + row.height);
+		Area::new(position, position + Vec2::new(galley.size.x, height))
 	}
 
 	fn set_color(self, color: impl Into<Color>) -> Self {
@@ -274,6 +638,39 @@ impl<T> From<T> for Text where
 	}
 }
 
+/// a piece of [`RichText`] with its own style
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct TextSpan {
+	pub text: String,
+	/// `None` for color of the whole text
+	pub color: Option<Color>,
+	/// `None` for scale of the whole text
+	pub size: Option<Vec2>,
+	pub is_bold: bool,
+	pub is_italic: bool,
+	pub underline: bool,
+	pub strikethrough: bool,
+	pub background: Option<Color>,
+	pub monospace: bool,
+}
+
+/// text made of several [`TextSpan`]s, can be used everywhere a [`Text`] is accepted. spans wrap together if width is limited.
+///
+/// # Example
+/// ```no_run
+/// # use nablo::prelude::*;
+/// # let mut ui = nablo::Ui::default();
+/// ui.label(RichText::new()
+///     .push(TextSpan::new("[ERROR] ").color([255, 0, 0, 255]).bold(true))
+///     .push(TextSpan::new("main.rs:42").monospace(true).background([64, 64, 64, 255]))
+///     .append(" something went wrong")
+/// );
+/// ```
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct RichText {
+	spans: Vec<TextSpan>,
+}
+
 /// a button
 ///
 /// # Example
@@ -290,7 +687,8 @@ pub struct Button {
 	painter: Painter,
 	space: Option<f32>,
 	status: Status,
-	style: ButtonStyle
+	style: ButtonStyle,
+	effects: Vec<Effect>,
 }
 
 impl Status {
@@ -336,6 +734,7 @@ pub struct Canvas {
 #[derive(Default)]
 pub struct Label {
 	text: Text,
+	show_full_on_hover: bool,
 }
 
 /// a smlicated way to imply [`TextSetting`] for structs have text
@@ -448,24 +847,357 @@ pub struct ProgressBar {
 	width: f32
 }
 
+/// a month grid to pick a date or a range of dates, weekday and month names follow [`Ui::language`].
+///
+/// # Example
+/// ```no_run
+/// # use nablo::prelude::*;
+/// # let mut ui = nablo::Ui::default();
+/// # let mut date = Date::MIN;
+/// ui.add(Calendar::new(&mut date));
+/// ```
+pub struct Calendar<'a> {
+	selection: CalendarSelection<'a>,
+	cell_size: f32,
+	first_weekday: Weekday,
+}
+
+pub(crate) enum CalendarSelection<'a> {
+	Single(&'a mut Date),
+	/// from and to
+	Range(&'a mut Date, &'a mut Date),
+}
+
+/// pick hours, minutes and seconds by draging or scrolling on each field
+pub struct TimePicker<'a> {
+	input: &'a mut Time,
+	show_seconds: bool,
+	speed: f64,
+	text: Text,
+}
+
+/// a date field with time fields, click the date to pick it in a popup [`Calendar`]
+pub struct DateTimePicker<'a> {
+	input: &'a mut OffsetDateTime,
+	show_seconds: bool,
+	cell_size: f32,
+	first_weekday: Weekday,
+	speed: f64,
+}
+
+/// a chart to draw several [`Series`], can be paned, zoomed and box zoomed. view bounds is kept in memory.
+///
+/// large series will be decimated to the pixel width of the plot.
+///
+/// # Example
+/// ```no_run
+/// # use nablo::prelude::*;
+/// # let mut ui = nablo::Ui::default();
+/// let points = (0..1000).map(|x| [x as f64, (x as f64 / 100.0).sin()]).collect();
+/// ui.add(Plot::new(Vec2::new(400.0, 200.0)).series(Series::line("sin", points)));
+/// ```
+pub struct Plot {
+	series: Vec<Series>,
+	width_and_height: Vec2,
+	x_range: Option<RangeInclusive<f64>>,
+	y_range: Option<RangeInclusive<f64>>,
+	show_grid: bool,
+	show_legend: bool,
+	is_interactive: bool,
+}
+
+/// a set of points shown in a [`Plot`]
+#[derive(Clone)]
+pub struct Series {
+	name: String,
+	points: Vec<[f64; 2]>,
+	kind: SeriesKind,
+	color: Option<Color>,
+	width: f32,
+}
+
+/// how a [`Series`] is drawn
+#[derive(Clone, Debug, PartialEq)]
+pub enum SeriesKind {
+	Line,
+	Scatter,
+	/// contains bar width in data units, histograms are bars as well
+	Bar(f64),
+	Area,
+}
+
+/// show a texture created by [`Ui::create_texture`] and so on, a placeholder will be drawn if texture is not found, or a spinner if it is loaded by [`Ui::load_texture`].
+///
+/// # Example
+/// ```no_run
+/// # use nablo::prelude::*;
+/// # let mut ui = nablo::Ui::default();
+/// ui.add(Image::new("avatar", Vec2::same(64.0)).fit(ImageFit::Cover).rounding(Vec2::same(32.0)));
+/// ```
+pub struct Image {
+	id: String,
+	size: Vec2,
+	fit: ImageFit,
+	rounding: Vec2,
+	tint: Color,
+	/// normalized
+	uv: Area,
+	rotate: f32,
+	is_clickable: bool,
+}
+
+/// how a [`Image`] fits its texture into its size
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum ImageFit {
+	/// keep aspect ratio and show the whole texture
+	#[default]
+	Contain,
+	/// keep aspect ratio and fill the whole size, overflowed part will be cut
+	Cover,
+	/// stretch the texture to the size
+	Fill,
+	/// keep original size of the texture, overflowed part will be cut
+	None,
+}
+
+/// show a texture in any size without stretching its borders, for skinned buttons and panels.
+///
+/// `margins` cut the texture into nine parts, corners are kept, edges are stretched along themselves and the center is stretched both ways.
+///
+/// # Example
+/// ```no_run
+/// # use nablo::prelude::*;
+/// # let mut ui = nablo::Ui::default();
+/// ui.add(NinePatch::new("panel", Vec2::new(240.0, 120.0), [12.0; 4]));
+/// ```
+pub struct NinePatch {
+	id: String,
+	size: Vec2,
+	/// left, top, right and bottom in texture pixels
+	margins: [f32; 4],
+	border_scale: f32,
+	tint: Color,
+	is_clickable: bool,
+}
+
+/// play frames laid out in a grid of one texture, left to right then top to bottom, by showing part of it.
+///
+/// # Example
+/// ```no_run
+/// # use nablo::prelude::*;
+/// # let mut ui = nablo::Ui::default();
+/// # let mut is_walking = true;
+/// ui.add(SpriteSheet::new("hero", Vec2::same(64.0), 8, 2).fps(12.0).playback(Playback::default().playing(is_walking)));
+/// ```
+pub struct SpriteSheet {
+	id: String,
+	size: Vec2,
+	columns: usize,
+	rows: usize,
+	/// used frames, the rest of the grid may be empty
+	frame_count: usize,
+	fps: f32,
+	playback: Playback,
+	fit: ImageFit,
+	tint: Color,
+	is_clickable: bool,
+}
+
+/// draw with your own wgpu pipelines inside ui, in the same layer order as other widgets.
+///
+/// `setup` runs once for each id to create gpu resources into [`TypeMap`], `prepare` runs before each draw to upload data,
+/// then `paint` records draw calls into a render pass whose viewport and scissor rect are already set to the widget.
+/// what `paint` draws is not compared between frames, call [`Ui::request_repaint`] when it changes.
+///
+/// not drawn inside [`Ui::offscreen`].
+///
+/// # Example
+/// ```no_run
+/// # use nablo::prelude::*;
+/// # let mut ui = nablo::Ui::default();
+/// struct Preview {
+///     pipeline: wgpu::RenderPipeline,
+/// }
+/// # fn create_pipeline(device: &wgpu::Device, info: &PaintCallbackInfo) -> wgpu::RenderPipeline { unimplemented!() }
+/// ui.add(PaintCallback::new("preview", Vec2::new(320.0, 240.0), |_, pass, resources| {
+///     if let Some(preview) = resources.get::<Preview>() {
+///         pass.set_pipeline(&preview.pipeline);
+///         pass.draw(0..3, 0..1);
+///     }
+/// }).setup(|device, _, info, resources| {
+///     resources.insert(Preview { pipeline: create_pipeline(device, info) });
+/// }));
+/// ```
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+#[derive(Clone)]
+pub struct PaintCallback {
+	id: String,
+	size: Vec2,
+	setup: Option<Arc<PrepareFn>>,
+	prepare: Option<Arc<PrepareFn>>,
+	paint: Arc<PaintFn>,
+}
+
+/// used by `setup` and `prepare` of [`PaintCallback`]
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+pub type PrepareFn = dyn Fn(&wgpu::Device, &wgpu::Queue, &PaintCallbackInfo, &mut TypeMap) + Send + Sync;
+
+/// used by `paint` of [`PaintCallback`]
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+pub type PaintFn = dyn for<'a> Fn(&PaintCallbackInfo, &mut wgpu::RenderPass<'a>, &'a TypeMap) + Send + Sync;
+
+/// where a [`PaintCallback`] is drawn
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaintCallbackInfo {
+	/// area of the widget in pixel, may be partly out of the target
+	pub area: Area,
+	/// part of `area` inside of the target, already set as viewport. differs from `area` only when the widget is partly out of the target,
+	/// scale your projection by them if you want it to stay in place then
+	pub viewport: Area,
+	/// visible part of `viewport` in pixel, already set as scissor rect
+	pub clip: Area,
+	/// in pixel, size of the window or texture drawn to
+	pub target_size: Vec2,
+	/// format of color attachment, pipelines should use it
+	pub format: wgpu::TextureFormat,
+	/// pipelines should use it as `multisample.count`
+	pub sample_count: u32,
+}
+
+/// gpu resources owned by you, one value for each type. kept by the renderer and shared by all [`PaintCallback`]s
+#[cfg(any(feature = "manager", feature = "baseview_manager"))]
+#[derive(Default)]
+pub struct TypeMap {
+	map: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+/// a underlined text that asks the host to open a url when clicked, see [`crate::OutputEvent::OpenUrl`]
+///
+/// # Example
+/// ```no_run
+/// # use nablo::prelude::*;
+/// # let mut ui = nablo::Ui::default();
+/// ui.add(Hyperlink::new("nablo", "https://github.com/5av1ng/nablo"));
+/// ```
+pub struct Hyperlink {
+	text: Text,
+	url: String,
+}
+
+/// renders CommonMark text by build-in widgets: headings, emphasis, code, lists, block quotes, tables, rules, links and images from registered texture ids.
+///
+/// `<details>` and `<summary>` html tags are shown as a [`crate::container::Collapsing`].
+/// # Example
+/// ```no_run
+/// # use nablo::prelude::*;
+/// # let mut ui = nablo::Ui::default();
+/// ui.add(Markdown::new("# nablo\nsee [github](https://github.com/5av1ng/nablo) for **more**."));
+/// ```
+pub struct Markdown {
+	text: String,
+	id: Option<String>,
+	/// bars of block quotes, found when showing contents
+	quotes: Vec<Area>,
+}
+
+/// a multi-line editor for code with a line number gutter, syntax highlighting by a [`Tokenizer`], bracket matching and auto indent.
+/// code is drawn with the build-in monospace font unless [`CodeEditor::family`] is set.
+///
+/// `Tab` indents selected lines and `Shift + Tab` unindents them. `Ctrl + Z` undoes, `Ctrl + Y` or `Ctrl + Shift + Z` redoes, typed words are undone as a whole. `Ctrl + F` opens the find bar and `Ctrl + H` opens it with a replace field,
+/// in the bar `Enter` goes to next match, `Shift + Enter` goes to previous one and `Tab` switches fields. `Enter` in replace field replaces current match,
+/// `Ctrl + Enter` replaces all, `Escape` closes the bar.
+///
+/// # Example
+/// ```no_run
+/// # use nablo::prelude::*;
+/// # let mut ui = nablo::Ui::default();
+/// let mut code = String::from("@fragment\nfn fs_main() -> @location(0) vec4f {\n\treturn vec3f(1.0);\n}");
+/// ui.add(CodeEditor::new(&mut code)
+///     .tokenizer(WgslTokenizer)
+///     .marker(CodeMarker::error(3, 9..14, "expected vec4f, found vec3f")));
+/// ```
+pub struct CodeEditor<'a> {
+	text: Text,
+	input: &'a mut String,
+	tokenizer: Box<dyn Tokenizer>,
+	markers: Vec<CodeMarker>,
+	width: Option<f32>,
+	rows: usize,
+	indent: String,
+	tab_size: usize,
+	line_numbers: bool,
+}
+
+/// what a [`Token`] is, decides its color in [`CodeEditor`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TokenKind {
+	#[default]
+	Plain,
+	Keyword,
+	Type,
+	Function,
+	Number,
+	String,
+	Comment,
+	/// attributes like `@vertex` or `#[derive(Debug)]`
+	Attribute,
+	/// keys of objects
+	Property,
+	Punctuation,
+}
+
+/// a highlighted piece of code
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+	/// byte range in the code
+	pub range: Range<usize>,
+	pub kind: TokenKind,
+}
+
+/// splits code into [`Token`]s for [`CodeEditor`], implement this to highlight languages not build in.
+pub trait Tokenizer {
+	/// tokens of `code` in order, charaters not covered by any token are drawn as [`TokenKind::Plain`]
+	fn tokenize(&self, code: &str) -> Vec<Token>;
+}
+
+/// highlights nothing, default [`Tokenizer`] of [`CodeEditor`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlainTokenizer;
+
+/// highlights wgsl shaders
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WgslTokenizer;
+
+/// highlights json
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonTokenizer;
+
+/// highlights rust
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RustTokenizer;
+
+/// a wavy underline in [`CodeEditor`] supplied by the caller, usually a error from a compiler. message shows when hovering.
+///
+/// problems of a shader from [`crate::Event::ShaderError`] can be turned into markers by [`CodeMarker::from`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CodeMarker {
+	/// starts from 1
+	pub line: usize,
+	/// columns in charaters, starts from 1. an empty range underlines the whole line
+	pub columns: Range<usize>,
+	/// decides color of the underline
+	pub status: Status,
+	pub message: String,
+}
+
 imply_text_trait!(SingleTextInput<'_>);
+imply_text_trait!(CodeEditor<'_>);
+imply_text_trait!(Hyperlink);
+imply_text_trait!(TimePicker<'_>);
 imply_text_trait!(Button);
 imply_text_trait!(SelectableValue);
-impl TextSetting for Label {
-	fn set_status(self, status: Status) -> Self { Self { text: self.text.set_status(status) } }
-	fn get_color(&self, ui: &mut Ui) -> Color { self.text.get_color(ui) }
-	fn set_width(self, width: f32) -> Self { Self { text: self.text.set_width(width) } }
-	fn set_height(self, height: f32) -> Self { Self { text: self.text.set_height(height)} }
-	fn set_scale(self, scale: Vec2) -> Self { Self { text: self.text.set_scale(scale) } }
-	fn set_em(self, em: Vec2) -> Self { Self { text: self.text.set_em(em)} }
-	fn underline(self, underline: bool) -> Self { Self { text: self.text.underline(underline)} }
-	fn text_area(&self, painter: &mut Painter) -> Area { self.text.text_area(painter) }
-	fn text_draw(&self, painter: &mut Painter, position: Vec2, ui: &mut Ui) { self.text.text_draw(painter, position, ui) }
-	fn set_text(self, text: impl Into<String>) -> Self { Self { text: self.text.set_text(text)} }
-	fn set_color(self, color: impl Into<Color>) -> Self { Self { text: self.text.set_color(color)} }
-	fn set_bold(self, is_bold: bool) -> Self { Self { text: self.text.set_bold(is_bold)} }
-	fn set_italic(self, is_italic: bool) -> Self { Self { text: self.text.set_italic(is_italic)} }
-}
+imply_text_trait!(Label);
 imply_text_trait!(Collapsing);
 imply_text_trait!(Message);
 imply_text_trait!(TooltipProvider);
@@ -498,4 +1230,69 @@ impl_num!(u16);
 impl_num!(u32);
 impl_num!(u64);
 impl_num!(u128);
-impl_num!(usize);
\ No newline at end of file
+impl_num!(usize);
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn kept(text: &str, keep: usize, truncate: Truncate) -> (String, Vec<Option<usize>>) {
+		let chars: Vec<char> = text.chars().collect();
+		let sources = truncated_sources(&chars, keep, truncate);
+		(source_text(&chars, &sources), sources)
+	}
+
+	#[test]
+	fn truncating_trims_spaces_near_ellipsis() {
+		assert_eq!(kept("hello world", 6, Truncate::End), (String::from("hello…"), vec!(Some(0), Some(1), Some(2), Some(3), Some(4), None)));
+		assert_eq!(kept("hello world", 6, Truncate::Start), (String::from("…world"), vec!(None, Some(6), Some(7), Some(8), Some(9), Some(10))));
+		assert_eq!(kept("ab cd ef", 4, Truncate::Middle), (String::from("ab…ef"), vec!(Some(0), Some(1), None, Some(6), Some(7))));
+		assert_eq!(kept("abc", 0, Truncate::End).0, "…");
+	}
+
+	#[test]
+	fn truncated_galley_maps_back_to_source() {
+		let text = Text::from("a long sentence which does not fit").truncate(Truncate::Start).wrap(TextWrap::None);
+		let full_width = text.galley().size.x;
+		let text = text.set_width(full_width / 2.0);
+		let galley = text.galley();
+		assert!(galley.size.x <= full_width / 2.0);
+		assert!(galley.text.starts_with('…'));
+		assert_eq!(galley.glyphs[0].source, None);
+		let chars: Vec<char> = text.text.chars().collect();
+		for glyph in &galley.glyphs[1..] {
+			assert_eq!(Some(glyph.chr), glyph.source.map(|index| chars[index]));
+		}
+		// a caret before `…` goes to the first shown charater, at the end goes to the end
+		assert_eq!(galley.source_index(0), galley.glyphs[1].source.unwrap());
+		assert_eq!(galley.source_index(galley.char_len()), chars.len());
+		// same text gets the cached galley
+		assert_eq!(text.galley(), galley);
+	}
+
+	#[test]
+	fn truncation_cache_drops_less_used_half() {
+		let mut cache = TruncationCache::default();
+		let key = |index: usize| TruncationKey {
+			text: index.to_string(),
+			family: DEFAULT_FAMILY,
+			size: [0, 0],
+			width: None,
+			height: None,
+			wrap: TextWrap::Word,
+			max_lines: None,
+			truncate: Truncate::End,
+			fonts: 0,
+		};
+		for index in 0..MAX_TRUNCATED {
+			cache.get(&key(index));
+			cache.insert(key(index), Galley::default());
+		}
+		// keep the first one used
+		assert!(cache.get(&key(0)).is_some());
+		cache.insert(key(MAX_TRUNCATED), Galley::default());
+		assert!(cache.galleys.len() <= MAX_TRUNCATED / 2 + 1);
+		assert!(cache.get(&key(0)).is_some());
+		assert!(cache.get(&key(1)).is_none());
+		assert!(cache.get(&key(MAX_TRUNCATED)).is_some());
+	}
+}
@@ -0,0 +1,103 @@
+use nablo_shape::prelude::shape_elements::Image as ShapeImage;
+use nablo_shape::prelude::shape_elements::Color;
+use nablo_shape::shape::ShapeElement;
+use crate::widgets::NinePatch;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use nablo_shape::shape::Painter;
+use crate::Ui;
+use crate::Response;
+use crate::Widget;
+
+impl NinePatch {
+	/// get a nine patch showing texture `id` in `size`, `margins` are left, top, right and bottom borders in texture pixels
+	pub fn new(id: impl Into<String>, size: Vec2, margins: [f32; 4]) -> Self {
+		Self {
+			id: id.into(),
+			size,
+			margins,
+			border_scale: 1.0,
+			tint: Color::from(1.0),
+			is_clickable: false,
+		}
+	}
+
+	/// scale borders, such as 2.0 for a texture made for high dpi
+	pub fn border_scale(self, border_scale: f32) -> Self {
+		Self {
+			border_scale,
+			..self
+		}
+	}
+
+	/// multiply every pixel with this color, by default its white
+	pub fn tint(self, tint: impl Into<Color>) -> Self {
+		Self {
+			tint: tint.into(),
+			..self
+		}
+	}
+
+	/// make the nine patch clickable like a [`crate::widgets::Button`]
+	pub fn clickable(self, is_clickable: bool) -> Self {
+		Self {
+			is_clickable,
+			..self
+		}
+	}
+}
+
+impl Widget for NinePatch {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		let Some(texture_size) = ui.texture_size(&self.id) else {
+			return;
+		};
+		let target = response.area;
+		let [left, top, right, bottom] = self.margins.map(|margin| margin.max(0.0));
+		// borders are shrunk when the target is too small for them
+		let border_scale = self.border_scale
+			.min(target.width() / (left + right).max(f32::EPSILON))
+			.min(target.height() / (top + bottom).max(f32::EPSILON));
+		let source_x = [0.0, left, texture_size.x - right, texture_size.x];
+		let source_y = [0.0, top, texture_size.y - bottom, texture_size.y];
+		let target_x = [target.left_top().x, target.left_top().x + left * border_scale, target.right_bottom().x - right * border_scale, target.right_bottom().x].map(f32::round);
+		let target_y = [target.left_top().y, target.left_top().y + top * border_scale, target.right_bottom().y - bottom * border_scale, target.right_bottom().y].map(f32::round);
+
+		let tint = if self.is_clickable && response.is_hovering() {
+			self.tint.brighter(ui.style().brighten_factor)
+		}else {
+			self.tint
+		};
+		for column in 0..3 {
+			for row in 0..3 {
+				let source = Area::new(Vec2::new(source_x[column], source_y[row]), Vec2::new(source_x[column + 1], source_y[row + 1]));
+				let slice = Area::new(Vec2::new(target_x[column], target_y[row]), Vec2::new(target_x[column + 1], target_y[row + 1]));
+				if source.width() <= 0.0 || source.height() <= 0.0 || slice.width() <= 0.0 || slice.height() <= 0.0 {
+					continue;
+				}
+				// one quad per slice, showing only its part of the texture
+				let uv = Area::new(
+					Vec2::new(source.left_top().x / texture_size.x, source.left_top().y / texture_size.y),
+					Vec2::new(source.right_bottom().x / texture_size.x, source.right_bottom().y / texture_size.y),
+				);
+				let mut slice_painter = Painter::from_area(&painter.paint_area);
+				*slice_painter.style_mut() = painter.style().clone();
+				slice_painter.set_position(slice.left_top());
+				slice_painter.draw(ShapeElement::Image(ShapeImage {
+					id: self.id.clone(),
+					size: slice.width_and_height(),
+					mask: None,
+				}));
+				ui.paint_image(slice_painter, uv, tint);
+			}
+		}
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		let area = match area {
+			Some(t) => t,
+			None => Area::new(ui.available_position(), ui.available_position() + self.size),
+		};
+		ui.response(area, self.is_clickable, false)
+	}
+}
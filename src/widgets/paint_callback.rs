@@ -0,0 +1,109 @@
+use nablo_shape::prelude::shape_elements::Rect;
+use nablo_shape::shape::ShapeElement;
+use crate::integrator::ShapeExtra;
+use crate::widgets::PaintCallback;
+use crate::widgets::PaintCallbackInfo;
+use crate::widgets::TypeMap;
+use crate::Response;
+use crate::Ui;
+use crate::Widget;
+use nablo_shape::shape::Painter;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use std::any::Any;
+use std::any::TypeId;
+use std::sync::Arc;
+
+impl PaintCallback {
+	/// `paint` records draw calls, see [`PaintCallback`]
+	pub fn new(id: impl Into<String>, size: Vec2, paint: impl for<'a> Fn(&PaintCallbackInfo, &mut wgpu::RenderPass<'a>, &'a TypeMap) + Send + Sync + 'static) -> Self {
+		Self {
+			id: id.into(),
+			size,
+			setup: None,
+			prepare: None,
+			paint: Arc::new(paint),
+		}
+	}
+
+	/// runs once for this id before first draw, creating pipelines and buffers into [`TypeMap`]
+	pub fn setup(self, setup: impl Fn(&wgpu::Device, &wgpu::Queue, &PaintCallbackInfo, &mut TypeMap) + Send + Sync + 'static) -> Self {
+		Self {
+			setup: Some(Arc::new(setup)),
+			..self
+		}
+	}
+
+	/// runs before every draw, usually writing buffers
+	pub fn prepare(self, prepare: impl Fn(&wgpu::Device, &wgpu::Queue, &PaintCallbackInfo, &mut TypeMap) + Send + Sync + 'static) -> Self {
+		Self {
+			prepare: Some(Arc::new(prepare)),
+			..self
+		}
+	}
+
+	pub(crate) fn run_setup(&self, device: &wgpu::Device, queue: &wgpu::Queue, info: &PaintCallbackInfo, resources: &mut TypeMap) {
+		if let Some(setup) = &self.setup {
+			setup(device, queue, info, resources);
+		}
+	}
+
+	pub(crate) fn run_prepare(&self, device: &wgpu::Device, queue: &wgpu::Queue, info: &PaintCallbackInfo, resources: &mut TypeMap) {
+		if let Some(prepare) = &self.prepare {
+			prepare(device, queue, info, resources);
+		}
+	}
+
+	pub(crate) fn run_paint<'a>(&self, info: &PaintCallbackInfo, render_pass: &mut wgpu::RenderPass<'a>, resources: &'a TypeMap) {
+		(self.paint)(info, render_pass, resources);
+	}
+}
+
+impl Widget for PaintCallback {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		let mut callback_painter = Painter::from_area(&painter.paint_area);
+		*callback_painter.style_mut() = painter.style().clone();
+		callback_painter.set_clip(painter.style().clip.cross_part(&response.area));
+		callback_painter.set_position(response.area.left_top());
+		callback_painter.draw(ShapeElement::Rect(Rect {
+			width_and_height: response.area.width_and_height(),
+			..Default::default()
+		}));
+		ui.shape.append_with_extras(callback_painter, vec!(ShapeExtra {
+			paint_callback: Some(self.id.clone()),
+			..Default::default()
+		}));
+		ui.paint_callbacks.lock().unwrap().insert(self.id.clone(), self.clone());
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		let area = match area {
+			Some(t) => t,
+			None => Area::new(ui.available_position(), ui.available_position() + self.size),
+		};
+		ui.response(area, false, false)
+	}
+}
+
+impl TypeMap {
+	/// returns the old value of this type
+	pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<T> {
+		self.map.insert(TypeId::of::<T>(), Box::new(value)).and_then(|old| old.downcast().ok()).map(|old| *old)
+	}
+
+	pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+		self.map.get(&TypeId::of::<T>()).and_then(|value| value.downcast_ref())
+	}
+
+	pub fn get_mut<T: Any + Send + Sync>(&mut self) -> Option<&mut T> {
+		self.map.get_mut(&TypeId::of::<T>()).and_then(|value| value.downcast_mut())
+	}
+
+	pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<T> {
+		self.map.remove(&TypeId::of::<T>()).and_then(|old| old.downcast().ok()).map(|old| *old)
+	}
+
+	pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+		self.map.contains_key(&TypeId::of::<T>())
+	}
+}
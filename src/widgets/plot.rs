@@ -0,0 +1,674 @@
+use crate::widgets::SeriesKind;
+use crate::widgets::Series;
+use crate::widgets::Plot;
+use crate::widgets::TextSetting;
+use crate::widgets::Text;
+use crate::widgets::Color;
+use crate::prelude::ShapeElement;
+use nablo_shape::shape::shape_elements::Layer;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use nablo_shape::shape::Painter;
+use std::ops::RangeInclusive;
+use crate::Key;
+use crate::Ui;
+use crate::Response;
+use crate::Widget;
+
+/// the part of data space we're looking at
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq)]
+struct PlotBounds {
+	min: [f64; 2],
+	max: [f64; 2],
+}
+
+impl PlotBounds {
+	const NOTHING: Self = Self {
+		min: [f64::INFINITY; 2],
+		max: [f64::NEG_INFINITY; 2],
+	};
+
+	fn extend(&mut self, point: [f64; 2]) {
+		for i in 0..2 {
+			if point[i].is_finite() {
+				self.min[i] = self.min[i].min(point[i]);
+				self.max[i] = self.max[i].max(point[i]);
+			}
+		}
+	}
+
+	/// make sure the bounds is finite and not empty, then pad it a little.
+	fn normalize(mut self, padding: f64) -> Self {
+		for i in 0..2 {
+			if !self.min[i].is_finite() || !self.max[i].is_finite() {
+				self.min[i] = 0.0;
+				self.max[i] = 1.0;
+			}
+			if self.max[i] - self.min[i] <= f64::EPSILON {
+				self.min[i] -= 0.5;
+				self.max[i] += 0.5;
+			}
+			let pad = (self.max[i] - self.min[i]) * padding;
+			self.min[i] -= pad;
+			self.max[i] += pad;
+		}
+		self
+	}
+
+	fn size(&self, index: usize) -> f64 {
+		self.max[index] - self.min[index]
+	}
+
+	fn to_screen(&self, point: [f64; 2], area: &Area) -> Vec2 {
+		let x = (point[0] - self.min[0]) / self.size(0) * area.width() as f64;
+		let y = (self.max[1] - point[1]) / self.size(1) * area.height() as f64;
+		area.left_top() + Vec2::new(x as f32, y as f32)
+	}
+
+	fn to_data(&self, position: Vec2, area: &Area) -> [f64; 2] {
+		let local = position - area.left_top();
+		[
+			self.min[0] + (local.x / area.width()) as f64 * self.size(0),
+			self.max[1] - (local.y / area.height()) as f64 * self.size(1),
+		]
+	}
+
+	fn translate(&mut self, delta: [f64; 2]) {
+		for i in 0..2 {
+			self.min[i] += delta[i];
+			self.max[i] += delta[i];
+		}
+	}
+
+	fn zoom(&mut self, center: [f64; 2], factor: f64) {
+		for i in 0..2 {
+			self.min[i] = center[i] + (self.min[i] - center[i]) * factor;
+			self.max[i] = center[i] + (self.max[i] - center[i]) * factor;
+		}
+	}
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+struct PlotTemp {
+	/// [`Option::None`] for auto bounds
+	bounds: Option<PlotBounds>,
+	box_start: Option<Vec2>,
+	box_end: Option<Vec2>,
+	is_panning: bool,
+}
+
+impl Series {
+	fn new(name: impl Into<String>, points: Vec<[f64; 2]>, kind: SeriesKind) -> Self {
+		Self {
+			name: name.into(),
+			points,
+			kind,
+			color: None,
+			width: 2.0,
+		}
+	}
+
+	/// a series connected by lines, points should be sorted by x
+	pub fn line(name: impl Into<String>, points: Vec<[f64; 2]>) -> Self {
+		Self::new(name, points, SeriesKind::Line)
+	}
+
+	/// a series drawn as dots
+	pub fn scatter(name: impl Into<String>, points: Vec<[f64; 2]>) -> Self {
+		Self::new(name, points, SeriesKind::Scatter)
+	}
+
+	/// a series drawn as bars from zero, `bar_width` is in data units
+	pub fn bar(name: impl Into<String>, points: Vec<[f64; 2]>, bar_width: f64) -> Self {
+		Self::new(name, points, SeriesKind::Bar(bar_width))
+	}
+
+	/// a line series with area below it filled, points should be sorted by x
+	pub fn area(name: impl Into<String>, points: Vec<[f64; 2]>) -> Self {
+		Self::new(name, points, SeriesKind::Area)
+	}
+
+	/// count `values` into `bins` bins with same width, and draw them as bars
+	pub fn histogram(name: impl Into<String>, values: &[f64], bins: usize) -> Self {
+		let bins = bins.max(1);
+		let (min, max) = values.iter().filter(|value| value.is_finite()).fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(*value), max.max(*value)));
+		if !min.is_finite() {
+			return Self::new(name, vec!(), SeriesKind::Bar(1.0));
+		}
+		if max <= min {
+			// all values are the same, one bin around them
+			return Self::new(name, vec!([min, values.iter().filter(|value| value.is_finite()).count() as f64]), SeriesKind::Bar(1.0));
+		}
+		let bin_width = (max - min) / bins as f64;
+		let mut counts = vec![0.0; bins];
+		for value in values.iter().filter(|value| value.is_finite()) {
+			let index = (((value - min) / bin_width) as usize).min(bins - 1);
+			counts[index] += 1.0;
+		}
+		let points = counts.into_iter().enumerate().map(|(i, count)| [min + (i as f64 + 0.5) * bin_width, count]).collect();
+		Self::new(name, points, SeriesKind::Bar(bin_width))
+	}
+
+	/// set color of this series, by default it will pick one from [`crate::widgets::Style`]
+	pub fn color(self, color: impl Into<Color>) -> Self {
+		Self {
+			color: Some(color.into()),
+			..self
+		}
+	}
+
+	/// set line width or dot radius of this series, by default its 2.0
+	pub fn width(self, width: f32) -> Self {
+		Self {
+			width,
+			..self
+		}
+	}
+
+	fn bounds(&self, bounds: &mut PlotBounds) {
+		match self.kind {
+			SeriesKind::Bar(bar_width) => {
+				for point in &self.points {
+					bounds.extend([point[0] - bar_width / 2.0, 0.0]);
+					bounds.extend([point[0] + bar_width / 2.0, point[1]]);
+				}
+			},
+			SeriesKind::Area => {
+				for point in &self.points {
+					bounds.extend([point[0], 0.0]);
+					bounds.extend(*point);
+				}
+			},
+			_ => {
+				for point in &self.points {
+					bounds.extend(*point);
+				}
+			}
+		}
+	}
+}
+
+/// reduce points to at most four points per pixel column, keeps the shape of the line.
+/// points outside the view collapse into one column on each side so lines still leave the view correctly.
+fn decimate(points: &[[f64; 2]], bounds: &PlotBounds, columns: usize) -> Vec<[f64; 2]> {
+	let points = points.iter().filter(|point| point[0].is_finite() && point[1].is_finite());
+	if columns == 0 {
+		return points.copied().collect();
+	}
+	let mut back = vec!();
+	// column, first, min, max, last
+	let mut bucket: Option<(i64, [f64; 2], [f64; 2], [f64; 2], [f64; 2])> = None;
+	let flush = |bucket: (i64, [f64; 2], [f64; 2], [f64; 2], [f64; 2]), back: &mut Vec<[f64; 2]>| {
+		let (_, first, min, max, last) = bucket;
+		let (a, b) = if min[0] <= max[0] { (min, max) } else { (max, min) };
+		for point in [first, a, b, last] {
+			if back.last() != Some(&point) {
+				back.push(point);
+			}
+		}
+	};
+	for point in points {
+		let column = ((point[0] - bounds.min[0]) / bounds.size(0) * columns as f64).floor().clamp(-1.0, columns as f64) as i64;
+		bucket = match bucket {
+			Some((current, first, min, max, _)) if current == column => {
+				Some((current, first, if point[1] < min[1] { *point } else { min }, if point[1] > max[1] { *point } else { max }, *point))
+			},
+			Some(inner) => {
+				flush(inner, &mut back);
+				Some((column, *point, *point, *point, *point))
+			},
+			None => Some((column, *point, *point, *point, *point)),
+		};
+	}
+	if let Some(inner) = bucket {
+		flush(inner, &mut back);
+	}
+	back
+}
+
+/// nice looking ticks between `min` and `max`, returns ticks and step
+fn ticks(min: f64, max: f64, count: f32) -> (Vec<f64>, f64) {
+	let raw = (max - min) / count.max(1.0) as f64;
+	if !raw.is_finite() || raw <= 0.0 {
+		return (vec!(), 1.0);
+	}
+	let magnitude = 10_f64.powf(raw.log10().floor());
+	let residual = raw / magnitude;
+	let step = if residual > 5.0 {
+		10.0
+	}else if residual > 2.0 {
+		5.0
+	}else if residual > 1.0 {
+		2.0
+	}else {
+		1.0
+	} * magnitude;
+	let mut back = vec!();
+	let mut value = (min / step).ceil() * step;
+	while value <= max + step * 1e-9 && back.len() < 100 {
+		back.push(value);
+		value += step;
+	}
+	(back, step)
+}
+
+fn format_value(value: f64, step: f64) -> String {
+	let decimals = (-step.log10().floor()).clamp(0.0, 10.0) as usize;
+	let back = format!("{:.*}", decimals, value);
+	if back.starts_with('-') && back.trim_start_matches(['-', '0', '.']).is_empty() {
+		back.trim_start_matches('-').to_string()
+	}else {
+		back
+	}
+}
+
+/// a thick line from `from` to `to`
+fn segment(painter: &mut Painter, from: Vec2, to: Vec2, width: f32) {
+	let direction = to - from;
+	let length = direction.len();
+	if length <= f32::EPSILON {
+		return;
+	}
+	let normal = Vec2::new(-direction.y, direction.x) / length * width / 2.0;
+	painter.draw(ShapeElement::Polygon(vec!(from + normal, to + normal, to - normal, from - normal).into()));
+}
+
+impl Plot {
+	/// get a new plot
+	pub fn new(width_and_height: Vec2) -> Self {
+		Self {
+			series: vec!(),
+			width_and_height,
+			x_range: None,
+			y_range: None,
+			show_grid: true,
+			show_legend: true,
+			is_interactive: true,
+		}
+	}
+
+	/// add a series to this plot
+	pub fn series(mut self, series: Series) -> Self {
+		self.series.push(series);
+		self
+	}
+
+	/// fix x axis to given range, by default its auto scaled
+	pub fn x_range(self, range: RangeInclusive<f64>) -> Self {
+		Self {
+			x_range: Some(range),
+			..self
+		}
+	}
+
+	/// fix y axis to given range, by default its auto scaled
+	pub fn y_range(self, range: RangeInclusive<f64>) -> Self {
+		Self {
+			y_range: Some(range),
+			..self
+		}
+	}
+
+	/// show grid lines or not, by default its true
+	pub fn grid(self, show_grid: bool) -> Self {
+		Self {
+			show_grid,
+			..self
+		}
+	}
+
+	/// show legend or not, by default its true
+	pub fn legend(self, show_legend: bool) -> Self {
+		Self {
+			show_legend,
+			..self
+		}
+	}
+
+	/// allow pan, zoom and box zoom or not, by default its true.
+	///
+	/// drag to pan, scroll to zoom, drag with shift pressed to zoom into a box, double click to reset.
+	pub fn interactive(self, is_interactive: bool) -> Self {
+		Self {
+			is_interactive,
+			..self
+		}
+	}
+
+	fn default_bounds(&self) -> PlotBounds {
+		let mut bounds = PlotBounds::NOTHING;
+		for series in &self.series {
+			series.bounds(&mut bounds);
+		}
+		let mut bounds = bounds.normalize(0.05);
+		if let Some(range) = &self.x_range {
+			bounds.min[0] = *range.start();
+			bounds.max[0] = *range.end();
+		}
+		if let Some(range) = &self.y_range {
+			bounds.min[1] = *range.start();
+			bounds.max[1] = *range.end();
+		}
+		bounds.normalize(0.0)
+	}
+
+	fn series_color(&self, ui: &Ui, index: usize) -> Color {
+		if let Some(color) = self.series[index].color {
+			return color;
+		}
+		let palette = [ui.style().info_color, ui.style().success_color, ui.style().warning_color, ui.style().error_color, ui.style().primary_color.brighter(0.2), ui.style().text_color];
+		palette[index % palette.len()]
+	}
+}
+
+impl Widget for Plot {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		painter.set_transform_origin(response.area.area[0]);
+		let space = ui.style().space;
+		let scale_factor = painter.style().scale_factor;
+		let mut temp: PlotTemp = response.memory_read().unwrap_or_default();
+		let mut bounds = temp.bounds.unwrap_or_else(|| self.default_bounds());
+
+		// layout, leave place for tick labels
+		let label_height = Text::from("0").text_area(painter).height();
+		let (y_ticks, y_step) = ticks(bounds.min[1], bounds.max[1], (response.area.height() - label_height) / (label_height * 3.0));
+		let y_labels: Vec<String> = y_ticks.iter().map(|tick| format_value(*tick, y_step)).collect();
+		let label_width = y_labels.iter().map(|label| Text::from(label.clone()).text_area(painter).width()).fold(0.0, f32::max);
+		let plot_area = Area::new(response.area.left_top() + Vec2::x(label_width + space / 2.0), response.area.right_bottom() - Vec2::y(label_height + space / 2.0));
+		let (x_ticks, x_step) = ticks(bounds.min[0], bounds.max[0], plot_area.width() / 80.0);
+
+		// interaction
+		let cursor = ui.input().cursor_position().map(|inner| inner / scale_factor);
+		if self.is_interactive {
+			if response.is_multi_clicked(2) {
+				temp = PlotTemp::default();
+				bounds = self.default_bounds();
+			}
+			if let Some(drag) = response.drag() {
+				let is_box = temp.box_start.is_some() || (!temp.is_panning && (ui.input().is_key_pressing(Key::ShiftLeft) || ui.input().is_key_pressing(Key::ShiftRight)));
+				if is_box {
+					if temp.box_start.is_none() {
+						temp.box_start = response.pointer_position().map(|pointer| (pointer - drag) / scale_factor);
+					}
+					temp.box_end = cursor;
+				}else {
+					temp.is_panning = true;
+					let delta = response.drag_delta();
+					bounds.translate([- delta.x as f64 / plot_area.width() as f64 * bounds.size(0), delta.y as f64 / plot_area.height() as f64 * bounds.size(1)]);
+					temp.bounds = Some(bounds);
+				}
+			}else {
+				if let (Some(start), Some(end)) = (temp.box_start, temp.box_end) {
+					let start = bounds.to_data(start, &plot_area);
+					let end = bounds.to_data(end, &plot_area);
+					let mut zoomed = PlotBounds::NOTHING;
+					zoomed.extend(start);
+					zoomed.extend(end);
+					if zoomed.size(0) > 0.0 && zoomed.size(1) > 0.0 {
+						bounds = zoomed;
+						temp.bounds = Some(bounds);
+					}
+				}
+				temp.box_start = None;
+				temp.box_end = None;
+				temp.is_panning = false;
+			}
+			if response.is_hovering() {
+				let scroll = ui.input().scroll().y;
+				if scroll != 0.0 {
+					if let Some(cursor) = cursor {
+						bounds.zoom(bounds.to_data(cursor, &plot_area), (- scroll as f64 * 0.002).exp());
+						temp.bounds = Some(bounds);
+					}
+				}
+			}
+		}
+		ui.memory_save(&response.id, &temp);
+
+		// # background and grid
+		painter.set_color(ui.style().background_color.brighter(0.05));
+		painter.set_position(plot_area.left_top());
+		painter.rect(plot_area.width_and_height(), Vec2::same(2.5));
+		let clip = painter.style().clip;
+		let label_color = ui.style().slider_peek_color;
+		for (tick, label) in y_ticks.iter().zip(y_labels) {
+			let y = bounds.to_screen([bounds.min[0], *tick], &plot_area).y;
+			if self.show_grid {
+				painter.set_color(ui.style().seprator_color);
+				painter.set_position(Vec2::new(plot_area.left_top().x, y));
+				painter.rect(Vec2::new(plot_area.width(), 1.0), Vec2::ZERO);
+			}
+			let text = Text::from(label).set_color(label_color);
+			let text_area = text.text_area(painter);
+			text.text_draw(painter, Vec2::new(plot_area.left_top().x - space / 2.0 - text_area.width(), y - text_area.height() / 2.0), ui);
+		}
+		for tick in &x_ticks {
+			let x = bounds.to_screen([*tick, bounds.min[1]], &plot_area).x;
+			if self.show_grid {
+				painter.set_color(ui.style().seprator_color);
+				painter.set_position(Vec2::new(x, plot_area.left_top().y));
+				painter.rect(Vec2::new(1.0, plot_area.height()), Vec2::ZERO);
+			}
+			let text = Text::from(format_value(*tick, x_step)).set_color(label_color);
+			let text_area = text.text_area(painter);
+			text.text_draw(painter, Vec2::new(x - text_area.width() / 2.0, plot_area.left_bottom().y + space / 2.0), ui);
+		}
+
+		// # series
+		painter.set_clip(plot_area.cross_part(&clip));
+		painter.set_position(Vec2::ZERO);
+		let columns = (plot_area.width() * scale_factor).max(1.0) as usize;
+		let baseline = bounds.to_screen([0.0, 0.0], &plot_area).y.clamp(plot_area.left_top().y, plot_area.left_bottom().y);
+		// series index, screen position, data
+		let mut nearest: Option<(usize, Vec2, [f64; 2])> = None;
+		let mut check_nearest = |index: usize, position: Vec2, point: [f64; 2]| {
+			if let Some(cursor) = cursor {
+				if plot_area.is_point_inside(&position) && (cursor - position).len() < 16.0 {
+					let is_nearer = match &nearest {
+						Some((_, current, _)) => (cursor - position).len() < (cursor - *current).len(),
+						None => true,
+					};
+					if is_nearer {
+						nearest = Some((index, position, point));
+					}
+				}
+			}
+		};
+		for index in 0..self.series.len() {
+			let color = self.series_color(ui, index);
+			let series = &self.series[index];
+			painter.set_color(color);
+			match series.kind {
+				SeriesKind::Line | SeriesKind::Area => {
+					let points = decimate(&series.points, &bounds, columns);
+					let positions: Vec<Vec2> = points.iter().map(|point| bounds.to_screen(*point, &plot_area)).collect();
+					if let SeriesKind::Area = series.kind {
+						painter.set_color(color.set_alpha(64));
+						for pair in positions.windows(2) {
+							painter.draw(ShapeElement::Polygon(vec!(pair[0], pair[1], Vec2::new(pair[1].x, baseline), Vec2::new(pair[0].x, baseline)).into()));
+						}
+						painter.set_color(color);
+					}
+					for pair in positions.windows(2) {
+						segment(painter, pair[0], pair[1], series.width);
+					}
+					for (position, point) in positions.into_iter().zip(points) {
+						check_nearest(index, position, point);
+					}
+				},
+				SeriesKind::Scatter => {
+					for point in decimate(&series.points, &bounds, columns) {
+						let position = bounds.to_screen(point, &plot_area);
+						if plot_area.is_point_inside(&position) {
+							painter.set_position(position - Vec2::same(series.width));
+							painter.cir(series.width);
+							check_nearest(index, position, point);
+						}
+					}
+					painter.set_position(Vec2::ZERO);
+				},
+				SeriesKind::Bar(bar_width) => {
+					// bars thinner than a pixel cover each other, keep the tallest and lowest ones of each column
+					for point in &decimate(&series.points, &bounds, columns) {
+						let left_top = bounds.to_screen([point[0] - bar_width / 2.0, point[1].max(0.0)], &plot_area);
+						let right_bottom = bounds.to_screen([point[0] + bar_width / 2.0, point[1].min(0.0)], &plot_area);
+						let bar = Area::new(left_top, right_bottom);
+						if bar.cross_part(&plot_area).width() > 0.0 {
+							painter.set_position(bar.left_top() + Vec2::x(0.5));
+							painter.rect(bar.width_and_height() - Vec2::x(1.0), Vec2::ZERO);
+							check_nearest(index, bounds.to_screen(*point, &plot_area), *point);
+						}
+					}
+					painter.set_position(Vec2::ZERO);
+				},
+			}
+		}
+
+		// # box zoom
+		if let (Some(start), Some(end)) = (temp.box_start, temp.box_end) {
+			let mut area = Area::new(start, start);
+			area.combine(&Area::new(end, end));
+			painter.set_color(ui.style().primary_color.set_alpha(40));
+			painter.set_stroke_color(ui.style().primary_color);
+			painter.set_stroke_width(1.0);
+			painter.set_position(area.left_top());
+			painter.rect(area.width_and_height(), Vec2::ZERO);
+			painter.set_stroke_width(0.0);
+		}
+		painter.set_clip(clip);
+
+		// # legend
+		let named: Vec<usize> = (0..self.series.len()).filter(|index| !self.series[*index].name.is_empty()).collect();
+		if self.show_legend && !named.is_empty() {
+			let texts: Vec<Text> = named.iter().map(|index| Text::from(self.series[*index].name.clone())).collect();
+			let swatch = label_height * 0.6;
+			let width = texts.iter().map(|text| text.text_area(painter).width()).fold(0.0, f32::max) + swatch + space * 1.5;
+			let height = label_height * named.len() as f32 + space;
+			let position = plot_area.right_top() + Vec2::new(- width - space / 2.0, space / 2.0);
+			painter.set_color(ui.style().card_color.set_alpha(200));
+			painter.set_position(position);
+			painter.rect(Vec2::new(width, height), Vec2::same(5.0));
+			for (row, (index, text)) in named.iter().zip(texts).enumerate() {
+				let row_position = position + Vec2::new(space / 2.0, space / 2.0 + row as f32 * label_height);
+				painter.set_color(self.series_color(ui, *index));
+				painter.set_position(row_position + Vec2::y((label_height - swatch) / 2.0));
+				painter.rect(Vec2::same(swatch), Vec2::same(swatch / 4.0));
+				text.text_draw(painter, row_position + Vec2::x(swatch + space / 2.0), ui);
+			}
+		}
+
+		// # hover read out
+		if let Some((index, position, point)) = nearest {
+			let layer = painter.style().layer;
+			painter.set_layer(Layer::ToolTips);
+			painter.set_color(self.series_color(ui, index));
+			painter.set_position(position - Vec2::same(4.0));
+			painter.cir(4.0);
+			let name = &self.series[index].name;
+			let text = if name.is_empty() {
+				format!("({}, {})", format_value(point[0], x_step / 100.0), format_value(point[1], y_step / 100.0))
+			}else {
+				format!("{}: ({}, {})", name, format_value(point[0], x_step / 100.0), format_value(point[1], y_step / 100.0))
+			};
+			let text = Text::from(text);
+			let text_area = text.text_area(painter);
+			let position = position + Vec2::same(space / 2.0);
+			painter.set_color(ui.style().card_color.brighter(0.1).set_alpha(230));
+			painter.set_position(position);
+			painter.rect(text_area.width_and_height() + Vec2::same(space), Vec2::same(5.0));
+			text.text_draw(painter, position + Vec2::same(space / 2.0), ui);
+			painter.set_layer(layer);
+		}
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		let area = match area {
+			Some(t) => t,
+			None => Area::new(ui.available_position(), ui.available_position() + self.width_and_height)
+		};
+		ui.response(area, true, self.is_interactive)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn close(a: f64, b: f64) -> bool {
+		(a - b).abs() < 1e-9
+	}
+
+	#[test]
+	fn decimate_keeps_extremes_of_each_column() {
+		let bounds = PlotBounds { min: [0.0, -1.0], max: [10.0, 1.0] };
+		let points: Vec<[f64; 2]> = (0..100).map(|i| [i as f64 * 0.1, if i == 17 { 1.0 } else if i == 33 { -1.0 } else { 0.0 }]).collect();
+		let back = decimate(&points, &bounds, 2);
+		assert!(back.len() <= 8);
+		assert_eq!(back.first(), points.first());
+		assert_eq!(back.last(), points.last());
+		assert!(back.contains(&points[17]));
+		assert!(back.contains(&points[33]));
+		assert!(back.windows(2).all(|pair| pair[0][0] <= pair[1][0]));
+	}
+
+	#[test]
+	fn decimate_collapses_points_outside_view() {
+		let bounds = PlotBounds { min: [0.0, 0.0], max: [1.0, 1.0] };
+		let points: Vec<[f64; 2]> = (0..50).map(|i| [-100.0 + i as f64, i as f64]).chain([[0.5, 0.5]]).chain((0..50).map(|i| [10.0 + i as f64, 0.0])).collect();
+		let back = decimate(&points, &bounds, 10);
+		assert!(back.len() <= 9);
+		assert!(back.contains(&[0.5, 0.5]));
+		// lines still leave the view toward the last point outside
+		assert_eq!(back.last(), points.last());
+	}
+
+	#[test]
+	fn decimate_drops_non_finite_points() {
+		let bounds = PlotBounds { min: [0.0, 0.0], max: [1.0, 1.0] };
+		let points = vec!([0.1, f64::NAN], [0.2, 0.5], [f64::INFINITY, 0.0]);
+		assert_eq!(decimate(&points, &bounds, 0), vec!([0.2, 0.5]));
+		assert_eq!(decimate(&points, &bounds, 4), vec!([0.2, 0.5]));
+	}
+
+	#[test]
+	fn ticks_are_nice() {
+		let (back, step) = ticks(0.0, 10.0, 5.0);
+		assert_eq!(step, 2.0);
+		assert_eq!(back, vec!(0.0, 2.0, 4.0, 6.0, 8.0, 10.0));
+		let (back, step) = ticks(0.15, 0.95, 4.0);
+		assert!(close(step, 0.2));
+		assert_eq!(back.len(), 4);
+		assert!(close(back[0], 0.2) && close(back[3], 0.8));
+		let (back, step) = ticks(-7.0, 7.0, 3.0);
+		assert_eq!(step, 5.0);
+		assert_eq!(back, vec!(-5.0, 0.0, 5.0));
+	}
+
+	#[test]
+	fn ticks_of_empty_ranges() {
+		assert_eq!(ticks(1.0, 1.0, 5.0), (vec!(), 1.0));
+		assert_eq!(ticks(2.0, 1.0, 5.0), (vec!(), 1.0));
+		assert_eq!(ticks(0.0, f64::INFINITY, 5.0), (vec!(), 1.0));
+		// at least one tick interval
+		assert_eq!(ticks(0.0, 1.0, 0.0).0, vec!(0.0, 1.0));
+	}
+
+	#[test]
+	fn histogram_counts_into_bins() {
+		let series = Series::histogram("", &[0.0, 1.0, 2.0, 3.0, 4.0, f64::NAN], 2);
+		assert_eq!(series.kind, SeriesKind::Bar(2.0));
+		// the maximum goes into the last bin
+		assert_eq!(series.points, vec!([1.0, 2.0], [3.0, 3.0]));
+		let series = Series::histogram("", &[1.0, 2.0], 0);
+		assert_eq!(series.points, vec!([1.5, 2.0]));
+	}
+
+	#[test]
+	fn histogram_of_same_or_no_values() {
+		let series = Series::histogram("", &[5.0, 5.0, 5.0], 3);
+		assert_eq!(series.points, vec!([5.0, 3.0]));
+		assert_eq!(series.kind, SeriesKind::Bar(1.0));
+		assert!(Series::histogram("", &[], 3).points.is_empty());
+		assert!(Series::histogram("", &[f64::NAN], 3).points.is_empty());
+	}
+}
@@ -0,0 +1,284 @@
+use crate::widgets::RichText;
+use crate::widgets::TextSpan;
+use crate::widgets::Text;
+use crate::widgets::TextSetting;
+use crate::widgets::Color;
+use nablo_shape::shape::shape_elements::EM;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use nablo_shape::shape::Painter;
+use crate::Ui;
+use crate::font::MONOSPACE;
+
+/// a piece of a span which will not be broken into lines anymore
+pub(crate) struct SpanPiece {
+	pub(crate) span: usize,
+	pub(crate) text: String,
+	/// relative to the left top of the text
+	pub(crate) position: Vec2,
+	pub(crate) size: Vec2,
+}
+
+impl TextSpan {
+	/// get a span with default style
+	pub fn new(text: impl Into<String>) -> Self {
+		Self {
+			text: text.into(),
+			..Default::default()
+		}
+	}
+
+	/// set color of this span, otherwise color of the whole text will be used
+	pub fn color(self, color: impl Into<Color>) -> Self {
+		Self {
+			color: Some(color.into()),
+			..self
+		}
+	}
+
+	/// set scale of this span, otherwise scale of the whole text will be used
+	pub fn scale(self, scale: Vec2) -> Self {
+		Self {
+			size: Some(scale),
+			..self
+		}
+	}
+
+	/// set scale of this span by given em measure
+	pub fn em(self, em: Vec2) -> Self {
+		Self {
+			size: Some(em / Vec2::same(EM)),
+			..self
+		}
+	}
+
+	/// make this span bold or not
+	pub fn bold(self, is_bold: bool) -> Self {
+		Self {
+			is_bold,
+			..self
+		}
+	}
+
+	/// make this span italic or not
+	pub fn italic(self, is_italic: bool) -> Self {
+		Self {
+			is_italic,
+			..self
+		}
+	}
+
+	/// show or not show the underline
+	pub fn underline(self, underline: bool) -> Self {
+		Self {
+			underline,
+			..self
+		}
+	}
+
+	/// show or not show a line through the middle
+	pub fn strikethrough(self, strikethrough: bool) -> Self {
+		Self {
+			strikethrough,
+			..self
+		}
+	}
+
+	/// highlight this span with a background color
+	pub fn background(self, background: impl Into<Color>) -> Self {
+		Self {
+			background: Some(background.into()),
+			..self
+		}
+	}
+
+	/// draw this span with the build-in [`MONOSPACE`] font, or the font registered as it
+	pub fn monospace(self, monospace: bool) -> Self {
+		Self {
+			monospace,
+			..self
+		}
+	}
+
+	fn as_text(&self, text: &Text, content: impl Into<String>) -> Text {
+		Text {
+			text: content.into(),
+			size: self.size.unwrap_or(text.size),
+			style: text.style.clone().set_bold(self.is_bold).set_italic(self.is_italic),
+			family: if self.monospace { Some(MONOSPACE.into()) } else { text.family.clone() },
+			outline: text.outline,
+			shadow: text.shadow,
+			..Default::default()
+		}
+	}
+}
+
+impl<T> From<T> for TextSpan where
+	T: Into<String>
+{
+	fn from(value: T) -> Self {
+		Self::new(value)
+	}
+}
+
+impl RichText {
+	/// get a rich text with nothing
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// add a span at the end
+	pub fn push(mut self, span: impl Into<TextSpan>) -> Self {
+		self.spans.push(span.into());
+		self
+	}
+
+	/// add a span using default style at the end
+	pub fn append(self, text: impl Into<String>) -> Self {
+		self.push(TextSpan::new(text))
+	}
+
+	/// get all spans
+	pub fn spans(&self) -> &Vec<TextSpan> {
+		&self.spans
+	}
+}
+
+impl From<RichText> for Text {
+	fn from(value: RichText) -> Self {
+		Text {
+			text: value.spans.iter().map(|span| span.text.as_str()).collect(),
+			spans: value.spans,
+			..Default::default()
+		}
+	}
+}
+
+/// split text into words and spaces
+fn split_words(text: &str) -> Vec<String> {
+	let mut back: Vec<String> = vec!();
+	let mut last_is_space = None;
+	for chr in text.chars() {
+		let is_space = chr.is_whitespace();
+		if chr == '\n' || last_is_space != Some(is_space) || back.last().is_some_and(|inner| inner == "\n") {
+			back.push(String::new());
+		}
+		back.last_mut().unwrap().push(chr);
+		last_is_space = Some(is_space);
+	}
+	back
+}
+
+/// put all spans of `text` into lines, words will only be broken if a single word is wider than `max_width`
+pub(crate) fn layout_spans(text: &Text, painter: &mut Painter, max_width: Option<f32>) -> (Vec<SpanPiece>, Vec2) {
+	let max_width = max_width.unwrap_or(f32::INFINITY);
+	let mut pieces: Vec<SpanPiece> = vec!();
+	let mut line_start = 0;
+	let mut cursor = Vec2::ZERO;
+	let mut line_height: f32 = 0.0;
+	let mut width: f32 = 0.0;
+	let finish_line = |pieces: &mut Vec<SpanPiece>, line_start: &mut usize, cursor: &mut Vec2, line_height: &mut f32| {
+		// align every piece in a line to the bottom
+		for piece in &mut pieces[*line_start..] {
+			piece.position.y = cursor.y + *line_height - piece.size.y;
+		}
+		*line_start = pieces.len();
+		cursor.x = 0.0;
+		cursor.y += *line_height;
+		*line_height = 0.0;
+	};
+	for (index, span) in text.spans.iter().enumerate() {
+		for word in split_words(&span.text) {
+			if word == "\n" {
+				if line_height == 0.0 {
+					line_height = span.as_text(text, " ").text_area(painter).height();
+				}
+				finish_line(&mut pieces, &mut line_start, &mut cursor, &mut line_height);
+				continue;
+			}
+			let size = span.as_text(text, word.clone()).text_area(painter).width_and_height();
+			let is_space = word.chars().all(|chr| chr.is_whitespace());
+			if cursor.x + size.x > max_width && cursor.x > 0.0 {
+				finish_line(&mut pieces, &mut line_start, &mut cursor, &mut line_height);
+				if is_space {
+					continue;
+				}
+			}
+			if size.x > max_width && !is_space {
+				// a single word wider than the whole line, break it by characters
+				for chr in word.chars() {
+					let size = span.as_text(text, chr).text_area(painter).width_and_height();
+					if cursor.x + size.x > max_width && cursor.x > 0.0 {
+						finish_line(&mut pieces, &mut line_start, &mut cursor, &mut line_height);
+					}
+					pieces.push(SpanPiece { span: index, text: chr.to_string(), position: cursor, size });
+					cursor.x += size.x;
+					width = width.max(cursor.x);
+					line_height = line_height.max(size.y);
+				}
+				continue;
+			}
+			pieces.push(SpanPiece { span: index, text: word, position: cursor, size });
+			cursor.x += size.x;
+			width = width.max(cursor.x);
+			line_height = line_height.max(size.y);
+		}
+	}
+	finish_line(&mut pieces, &mut line_start, &mut cursor, &mut line_height);
+	(pieces, Vec2::new(width, cursor.y))
+}
+
+/// draw all spans of `text` with its left top at `position`
+pub(crate) fn draw_spans(text: &Text, painter: &mut Painter, position: Vec2, ui: &mut Ui) {
+	let (pieces, _) = layout_spans(text, painter, text.width);
+	let default_color = text.get_color(ui);
+	let color = painter.style().fill;
+	let max_height = text.height.unwrap_or(f32::INFINITY);
+	for piece in pieces {
+		if piece.position.y + piece.size.y > max_height {
+			break;
+		}
+		let span = &text.spans[piece.span];
+		let left_top = position + piece.position;
+		if let Some(background) = span.background {
+			painter.set_color(background);
+			painter.set_position(left_top);
+			painter.rect(piece.size, Vec2::ZERO);
+		}
+		let span_color = span.color.unwrap_or(default_color);
+		let piece_text = span.as_text(text, piece.text).set_color(span_color);
+		piece_text.text_draw(painter, left_top, ui);
+		let line_height = (piece.size.y / 12.0).max(1.0);
+		painter.set_color(span_color);
+		if span.underline {
+			painter.set_position(left_top + Vec2::y(piece.size.y - line_height));
+			painter.rect(Vec2::new(piece.size.x, line_height), Vec2::ZERO);
+		}
+		if span.strikethrough {
+			painter.set_position(left_top + Vec2::y((piece.size.y - line_height) / 2.0));
+			painter.rect(Vec2::new(piece.size.x, line_height), Vec2::ZERO);
+		}
+	}
+	painter.set_color(color);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn words_and_spaces() {
+		assert_eq!(split_words("ab  cd\n\nef"), vec!("ab", "  ", "cd", "\n", "\n", "ef"));
+		assert!(split_words("").is_empty());
+	}
+
+	#[test]
+	fn monospace_spans_use_monospace_font() {
+		let text = Text::from(RichText::new().push(TextSpan::new("iii").monospace(true)).push(TextSpan::new("MMM").monospace(true)).append("iii"));
+		assert_eq!(text.spans[0].as_text(&text, "a").family.as_deref(), Some(MONOSPACE));
+		assert_eq!(text.spans[2].as_text(&text, "a").family, None);
+		let (pieces, _) = layout_spans(&text, &mut Painter::default(), None);
+		assert_eq!(pieces[0].size.x, pieces[1].size.x);
+		assert!(pieces[2].size.x < pieces[0].size.x);
+	}
+}
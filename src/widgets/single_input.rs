@@ -1,6 +1,8 @@
 use crate::OutputEvent;
 use crate::PASSWORD;
 use crate::widgets::TextSetting;
+use crate::widgets::Text;
+use crate::text_layout::Galley;
 use nablo_shape::prelude::shape_elements::Color;
 use time::Duration;
 use nablo_shape::prelude::animation::Animation;
@@ -102,6 +104,27 @@ struct Select {
 	is_backwards: bool,
 }
 
+impl SingleTextInput<'_> {
+	/// what we actually show, passwords are hidden
+	fn shown_text(&self) -> String {
+		if self.is_password {
+			(0..utf8_slice::len(self.input)).map(|_| PASSWORD).collect()
+		}else {
+			self.input.clone()
+		}
+	}
+}
+
+/// where text starts, text scrolls left when the caret goes out of the input
+fn text_offset(galley: &Galley, pointer: usize, width: f32, text_start: f32, space_minus: f32) -> f32 {
+	let front = galley.caret_position(pointer).x;
+	if front < width - space_minus {
+		text_start
+	}else {
+		text_start + width - space_minus - front
+	}
+}
+
 // completely mess
 
 impl Widget for SingleTextInput<'_> {
@@ -205,8 +228,17 @@ impl Widget for SingleTextInput<'_> {
 				}
 			}
 
-			// move pointer and select
-			if ui.input().is_key_repeat(Key::ArrowLeft) {
+			// move pointer and select, arrows are swapped in right to left runs
+			let is_rtl = Text {
+				text: self.shown_text(),
+				..self.text.clone()
+			}.galley().is_rtl_at(temp.pointer);
+			let (backward_key, forward_key) = if is_rtl {
+				(Key::ArrowRight, Key::ArrowLeft)
+			}else {
+				(Key::ArrowLeft, Key::ArrowRight)
+			};
+			if ui.input().is_key_repeat(backward_key) {
 				if ui.input().is_key_pressing(Key::ShiftLeft) {
 					if let Some(t) = &mut temp.select {
 						if t.is_backwards {
@@ -231,7 +263,7 @@ impl Widget for SingleTextInput<'_> {
 					}
 					temp.select = None;
 				}
-			}else if ui.input().is_key_repeat(Key::ArrowRight) {
+			}else if ui.input().is_key_repeat(forward_key) {
 				if ui.input().is_key_pressing(Key::ShiftLeft) {
 					if let Some(t) = &mut temp.select {
 						let add_with_ceil = |input: &mut usize| { 
@@ -265,24 +297,13 @@ impl Widget for SingleTextInput<'_> {
 				}
 			}
 			if response.is_clicked() {
-				fn compress(input: f32) -> f32 {
-					if input > 1.0 {
-						return 1.0;
-					}else if input < 0.0 {
-						return 0.0;
-					}
-					input
-				}
 				let cursor_position = ui.input().cursor_position().unwrap_or(Vec2::INF);
-				let text_width = painter.text_area(self.input.to_string()).width();
-				let len = if response.area.width() < text_width {
-					1.0 - (response.area.area[1].x - cursor_position.x) / text_width
-				}else {
-					(cursor_position.x - response.area.area[0].x - text_start) / text_width
-				}; 
-				let len = compress(len);
-				let len = (len * utf8_slice::len(self.input) as f32) as usize;
-				temp.pointer = len;
+				let galley = Text {
+					text: self.shown_text(),
+					..self.text.clone()
+				}.galley();
+				let x = text_offset(&galley, temp.pointer, response.area.width(), text_start, space_minus);
+				temp.pointer = galley.char_index_at(Vec2::x(cursor_position.x - response.area.area[0].x - x));
 				temp.select = None;
 			}
 			if self.input.is_empty() {
@@ -335,11 +356,7 @@ impl Widget for SingleTextInput<'_> {
 
 		// draw
 		// # background
-		if self.is_password {
-			self.text.text = (0..utf8_slice::len(self.input)).map(|_| PASSWORD).collect();
-		}else {
-			self.text.text.clone_from(self.input);
-		}
+		self.text.text = self.shown_text();
 		painter.set_color(background_color);
 		painter.set_position(response.area.area[0] + Vec2::same(2.0));
 		let stroke_color: Color = ui.style().slider_unreached_color;
@@ -358,19 +375,14 @@ impl Widget for SingleTextInput<'_> {
 			self.text.text.clone_from(&self.place_holder);
 			self.text.color = Some([0.5,0.5,0.5,0.5].into());
 		}
-		let front = utf8_slice::till(&self.text.text, temp.pointer).to_string();
-		let x = if painter.text_area(front.clone()).width() < response.area.width() - space_minus {
-			text_start
-		}else {
-			text_start + response.area.width() - space_minus - painter.text_area(front.clone()).width()
-		};
+		let galley = self.text.galley();
+		let x = text_offset(&galley, temp.pointer, response.area.width(), text_start, space_minus);
 		let y = (response.area.height() - 16.0) / 2.0;
 		let position = response.area.area[0] + Vec2::new(x, y);
 		painter.set_clip(Area::from([text_start + response.area.area[0].x, response.area.area[0].y, response.area.area[1].x - space, response.area.area[1].y]).cross_part(&painter.style().clip));
-		self.text = self.text.clone().set_width(response.area.width());
 		self.text.text_draw(painter, position, ui);
 		// # pointer
-		let pointer = painter.text_area(front).width() + x;
+		let pointer = galley.caret_position(temp.pointer).x + x;
 		let position = response.area.area[0] + Vec2::new(pointer, (response.area.height() - 16.0) / 2.0);
 		painter.set_clip([text_start + response.area.area[0].x, response.area.area[0].y, response.area.area[1].x, response.area.area[1].y].into());
 		painter.set_color([1.0,1.0,1.0,brighter * 2.0 - 1.0]);
@@ -379,14 +391,11 @@ impl Widget for SingleTextInput<'_> {
 		painter.rect([2.0, 16.0].into(), Vec2::ZERO);
 		// # select
 		if let Some(select) = temp.select {
-			let text = utf8_slice::till(&self.text.text, select.begin).to_string();
-			let front_x = painter.text_area(text).width() + x;
-			let text = utf8_slice::slice(&self.text.text, select.begin, select.end).to_string();
-			let select_width = painter.text_area(text).width();
-			let position = response.area.area[0] + Vec2::new(front_x, y);
 			painter.set_color(ui.style().primary_color.set_alpha(100));
-			painter.set_position(position);
-			painter.rect([select_width, 16.0].into(), Vec2::ZERO);
+			for area in galley.selection_areas(select.begin..select.end) {
+				painter.set_position(response.area.area[0] + Vec2::new(area.left_top().x + x, y));
+				painter.rect([area.width(), 16.0].into(), Vec2::ZERO);
+			}
 		}
 
 		painter.brighter(light_factor);
@@ -0,0 +1,138 @@
+use nablo_shape::prelude::shape_elements::Color;
+use crate::texture::Playback;
+use crate::widgets::ImageFit;
+use crate::widgets::Image;
+use crate::widgets::SpriteSheet;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use nablo_shape::shape::Painter;
+use time::Duration;
+use crate::Instant;
+use crate::Ui;
+use crate::Response;
+use crate::Widget;
+
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+struct SpriteSheetTemp {
+	frame: usize,
+	/// seconds spent on current frame
+	elapsed: f32,
+	last_update: Instant,
+	was_playing: bool,
+}
+
+impl Default for SpriteSheetTemp {
+	fn default() -> Self {
+		Self {
+			frame: 0,
+			elapsed: 0.0,
+			last_update: Instant::now(),
+			was_playing: false,
+		}
+	}
+}
+
+impl SpriteSheet {
+	/// get a sprite sheet showing texture `id` in `size`, cut into `columns` and `rows`
+	pub fn new(id: impl Into<String>, size: Vec2, columns: usize, rows: usize) -> Self {
+		let columns = columns.max(1);
+		let rows = rows.max(1);
+		Self {
+			id: id.into(),
+			size,
+			columns,
+			rows,
+			frame_count: columns * rows,
+			fps: 12.0,
+			playback: Playback::default(),
+			fit: ImageFit::default(),
+			tint: Color::from(1.0),
+			is_clickable: false,
+		}
+	}
+
+	/// set how many frames are used, by default the whole grid
+	pub fn frames(self, frame_count: usize) -> Self {
+		Self {
+			frame_count: frame_count.clamp(1, self.columns * self.rows),
+			..self
+		}
+	}
+
+	/// set frames per second, by default its 12
+	pub fn fps(self, fps: f32) -> Self {
+		Self {
+			fps,
+			..self
+		}
+	}
+
+	/// set how the animation plays, a finished animation plays from the beginning again once `is_playing` turns true
+	pub fn playback(self, playback: Playback) -> Self {
+		Self {
+			playback,
+			..self
+		}
+	}
+
+	/// set how a frame fits into the size, by default its [`ImageFit::Contain`]
+	pub fn fit(self, fit: ImageFit) -> Self {
+		Self {
+			fit,
+			..self
+		}
+	}
+
+	/// multiply every pixel with this color, by default its white
+	pub fn tint(self, tint: impl Into<Color>) -> Self {
+		Self {
+			tint: tint.into(),
+			..self
+		}
+	}
+
+	/// make the sprite clickable like a [`crate::widgets::Button`]
+	pub fn clickable(self, is_clickable: bool) -> Self {
+		Self {
+			is_clickable,
+			..self
+		}
+	}
+
+	/// part of the texture showing `frame`, normalized
+	fn frame_uv(&self, frame: usize) -> Area {
+		let cell = Vec2::new(1.0 / self.columns as f32, 1.0 / self.rows as f32);
+		let left_top = Vec2::new((frame % self.columns) as f32 * cell.x, (frame / self.columns) as f32 * cell.y);
+		Area::new(left_top, left_top + cell)
+	}
+}
+
+impl Widget for SpriteSheet {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		let mut temp: SpriteSheetTemp = ui.memory_read(&response.id).unwrap_or_default();
+		let delay = 1.0 / self.fps.max(f32::EPSILON);
+		let is_finished = !self.playback.is_looping && temp.frame + 1 >= self.frame_count && temp.elapsed >= delay;
+		if self.playback.is_playing && !temp.was_playing && is_finished {
+			temp.frame = 0;
+			temp.elapsed = 0.0;
+		}
+		temp.frame = temp.frame.min(self.frame_count - 1);
+		let delta = temp.last_update.elapsed().as_seconds_f32();
+		if let Some(next) = self.playback.advance(&mut temp.frame, &mut temp.elapsed, delta, self.frame_count, |_| delay) {
+			ui.request_repaint_after(Duration::seconds_f32(next));
+		}
+		temp.last_update = Instant::now();
+		temp.was_playing = self.playback.is_playing;
+		let uv = self.frame_uv(temp.frame);
+		ui.memory_save(&response.id, &temp);
+		Image::new(self.id.clone(), self.size).uv(uv).fit(self.fit).tint(self.tint).clickable(self.is_clickable).draw(ui, response, painter);
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		let area = match area {
+			Some(t) => t,
+			None => Area::new(ui.available_position(), ui.available_position() + self.size),
+		};
+		ui.response(area, self.is_clickable, false)
+	}
+}
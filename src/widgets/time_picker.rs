@@ -0,0 +1,183 @@
+use time::Time;
+use crate::widgets::TimePicker;
+use crate::widgets::TextSetting;
+use crate::widgets::Text;
+use crate::widgets::Color;
+use nablo_shape::math::Area;
+use nablo_shape::math::Vec2;
+use nablo_shape::shape::Painter;
+use crate::Ui;
+use crate::Response;
+use crate::Widget;
+
+/// remembers which field we're draging and how much drag haven't been applied yet.
+#[derive(serde::Deserialize, serde::Serialize, Default, Clone, Copy)]
+pub(crate) struct TimeDragTemp {
+	field: Option<usize>,
+	accumulated: f64,
+}
+
+/// width of one `HH`, `MM` or `SS` field
+pub(crate) fn time_field_width(painter: &mut Painter, ui: &Ui) -> f32 {
+	Text::from("00").text_area(painter).width() + ui.style().space
+}
+
+/// width of the `:` between two fields
+pub(crate) fn time_separator_width(painter: &mut Painter, ui: &Ui) -> f32 {
+	Text::from(":").text_area(painter).width() + ui.style().space / 2.0
+}
+
+/// how large do time fields take
+pub(crate) fn time_fields_size(painter: &mut Painter, ui: &Ui, show_seconds: bool) -> Vec2 {
+	let count = if show_seconds { 3.0 } else { 2.0 };
+	let height = Text::from("00").text_area(painter).height() + ui.style().space;
+	Vec2::new(time_field_width(painter, ui) * count + time_separator_width(painter, ui) * (count - 1.0), height)
+}
+
+/// areas of each time field, `origin` is the left top of the first field
+fn time_field_areas(painter: &mut Painter, ui: &Ui, origin: Vec2, show_seconds: bool) -> Vec<Area> {
+	let field_width = time_field_width(painter, ui);
+	let separator_width = time_separator_width(painter, ui);
+	let height = time_fields_size(painter, ui, show_seconds).y;
+	let count = if show_seconds { 3 } else { 2 };
+	(0..count).map(|i| {
+		let left_top = origin + Vec2::x(i as f32 * (field_width + separator_width));
+		Area::new(left_top, left_top + Vec2::new(field_width, height))
+	}).collect()
+}
+
+/// add `units` to hour, minute or second of `time`, wraps around instead of overflowing.
+fn time_add(time: Time, field: usize, units: i64) -> Time {
+	let (hour, minute, second) = (time.hour() as i64, time.minute() as i64, time.second() as i64);
+	let (hour, minute, second) = match field {
+		0 => ((hour + units).rem_euclid(24), minute, second),
+		1 => (hour, (minute + units).rem_euclid(60), second),
+		_ => (hour, minute, (second + units).rem_euclid(60)),
+	};
+	Time::from_hms_nano(hour as u8, minute as u8, second as u8, time.nanosecond()).unwrap_or(time)
+}
+
+/// apply drag and scroll on time fields, returns the field currently active
+pub(crate) fn handle_time_fields(ui: &mut Ui, painter: &mut Painter, response: &Response, temp: &mut TimeDragTemp, origin: Vec2, show_seconds: bool, speed: f64, time: &mut Time) -> Option<usize> {
+	let scale_factor = painter.style().scale_factor;
+	let areas = time_field_areas(painter, ui, origin, show_seconds);
+	let field_at = |position: Vec2| areas.iter().position(|area| area.is_point_inside(&position));
+	if let Some(drag) = response.drag() {
+		if temp.field.is_none() {
+			temp.field = response.pointer_position().and_then(|pointer| field_at((pointer - drag) / scale_factor));
+			temp.accumulated = 0.0;
+		}
+		if let Some(field) = temp.field {
+			temp.accumulated += response.drag_delta().x as f64 * speed;
+			let units = temp.accumulated.trunc();
+			temp.accumulated -= units;
+			*time = time_add(*time, field, units as i64);
+		}
+	}else {
+		temp.field = None;
+		temp.accumulated = 0.0;
+	}
+	let hovered = if response.is_hovering() {
+		ui.input().cursor_position().and_then(|cursor| field_at(cursor / scale_factor))
+	}else {
+		None
+	};
+	if let Some(field) = hovered {
+		let scroll = ui.input().scroll().y;
+		if scroll != 0.0 {
+			*time = time_add(*time, field, scroll.signum() as i64);
+		}
+	}
+	temp.field.or(hovered)
+}
+
+/// draw time fields as `HH:MM:SS`
+pub(crate) fn draw_time_fields(ui: &mut Ui, painter: &mut Painter, origin: Vec2, show_seconds: bool, time: &Time, active: Option<usize>, color: Color) {
+	let areas = time_field_areas(painter, ui, origin, show_seconds);
+	let values = [time.hour(), time.minute(), time.second()];
+	for (i, area) in areas.iter().enumerate() {
+		painter.set_color(if active == Some(i) {
+			ui.style().background_color.brighter(0.25)
+		}else {
+			ui.style().background_color.brighter(0.15)
+		});
+		painter.set_stroke_color(ui.style().slider_unreached_color);
+		painter.set_stroke_width(if active == Some(i) { 2.0 } else { 1.0 });
+		painter.set_position(area.left_top());
+		painter.rect(area.width_and_height(), Vec2::same(2.5));
+		painter.set_stroke_width(0.0);
+		let text = Text::from(format!("{:02}", values[i])).set_color(color);
+		let text_area = text.text_area(painter);
+		text.text_draw(painter, area.left_top() + (area.width_and_height() - text_area.width_and_height()) / 2.0, ui);
+		if i + 1 < areas.len() {
+			let separator = Text::from(":").set_color(color);
+			let separator_area = separator.text_area(painter);
+			let position = Vec2::new((area.right_top().x + areas[i + 1].left_top().x - separator_area.width()) / 2.0, area.left_top().y + (area.height() - separator_area.height()) / 2.0);
+			separator.text_draw(painter, position, ui);
+		}
+	}
+}
+
+impl<'a> TimePicker<'a> {
+	/// get a time picker
+	pub fn new(input: &'a mut Time) -> Self {
+		Self {
+			input,
+			show_seconds: true,
+			speed: 0.1,
+			text: "".into(),
+		}
+	}
+
+	/// show seconds or not, by default its true
+	pub fn show_seconds(self, show_seconds: bool) -> Self {
+		Self {
+			show_seconds,
+			..self
+		}
+	}
+
+	/// set how many units will change when draging a pixel, by default its 0.1
+	pub fn speed(self, speed: f64) -> Self {
+		Self {
+			speed,
+			..self
+		}
+	}
+}
+
+impl Widget for TimePicker<'_> {
+	fn draw(&mut self, ui: &mut Ui, response: &Response, painter: &mut Painter) {
+		painter.set_transform_origin(response.area.area[0]);
+		let text_area = self.text.text_area(painter);
+		let space = if text_area.width() == 0.0 {
+			0.0
+		}else {
+			text_area.width() + ui.style().space
+		};
+		let origin = response.area.left_top() + Vec2::x(space);
+		let mut temp: TimeDragTemp = response.memory_read().unwrap_or_default();
+		let active = handle_time_fields(ui, painter, response, &mut temp, origin, self.show_seconds, self.speed, self.input);
+		ui.memory_save(&response.id, &temp);
+
+		self.text.text_draw(painter, response.area.left_top() + Vec2::y((response.area.height() - text_area.height()) / 2.0), ui);
+		let color = ui.style().text_color;
+		draw_time_fields(ui, painter, origin, self.show_seconds, self.input, active, color);
+	}
+
+	fn ui(&mut self, ui: &mut Ui, area: Option<Area>) -> Response {
+		if let Some(area) = area {
+			return ui.response(area, true, true);
+		}
+		let mut painter = ui.painter();
+		let text_area = self.text.text_area(&mut painter);
+		let space = if text_area.width() == 0.0 {
+			0.0
+		}else {
+			text_area.width() + ui.style().space
+		};
+		let fields = time_fields_size(&mut painter, ui, self.show_seconds);
+		let size = Vec2::new(space + fields.x, fields.y.max(text_area.height()));
+		ui.response(Area::new(ui.available_position(), ui.available_position() + size), true, true)
+	}
+}
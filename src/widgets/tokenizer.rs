@@ -0,0 +1,349 @@
+use crate::widgets::Token;
+use crate::widgets::TokenKind;
+use crate::widgets::Tokenizer;
+use crate::widgets::PlainTokenizer;
+use crate::widgets::WgslTokenizer;
+use crate::widgets::JsonTokenizer;
+use crate::widgets::RustTokenizer;
+
+const WGSL_KEYWORDS: &[&str] = &[
+	"alias", "break", "case", "const", "const_assert", "continue", "continuing", "default", "diagnostic", "discard", "else", "enable",
+	"false", "fn", "for", "if", "let", "loop", "override", "requires", "return", "struct", "switch", "true", "var", "while",
+	"function", "private", "workgroup", "uniform", "storage", "read", "write", "read_write",
+];
+
+const WGSL_TYPES: &[&str] = &[
+	"bool", "f16", "f32", "i32", "u32",
+	"vec2", "vec3", "vec4", "vec2f", "vec3f", "vec4f", "vec2i", "vec3i", "vec4i", "vec2u", "vec3u", "vec4u", "vec2h", "vec3h", "vec4h",
+	"mat2x2", "mat2x3", "mat2x4", "mat3x2", "mat3x3", "mat3x4", "mat4x2", "mat4x3", "mat4x4",
+	"mat2x2f", "mat2x3f", "mat2x4f", "mat3x2f", "mat3x3f", "mat3x4f", "mat4x2f", "mat4x3f", "mat4x4f",
+	"array", "atomic", "ptr", "sampler", "sampler_comparison",
+	"texture_1d", "texture_2d", "texture_2d_array", "texture_3d", "texture_cube", "texture_cube_array", "texture_multisampled_2d",
+	"texture_storage_1d", "texture_storage_2d", "texture_storage_2d_array", "texture_storage_3d",
+	"texture_depth_2d", "texture_depth_2d_array", "texture_depth_cube", "texture_depth_cube_array", "texture_depth_multisampled_2d",
+];
+
+const RUST_KEYWORDS: &[&str] = &[
+	"as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for", "if", "impl",
+	"in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+	"true", "type", "unsafe", "use", "where", "while",
+];
+
+const RUST_TYPES: &[&str] = &[
+	"bool", "char", "str", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64",
+];
+
+/// rules of a language looks like c
+struct Grammar {
+	keywords: &'static [&'static str],
+	types: &'static [&'static str],
+	/// `@` for wgsl attributes, `#` for rust attributes
+	attribute: char,
+	/// identifiers starting with a upper case letter are types
+	capitalized_types: bool,
+	/// rust only, `name!` macros, `'a` lifetimes, `'c'` charaters and `r#"raw"#` strings
+	is_rust: bool,
+}
+
+const WGSL: Grammar = Grammar {
+	keywords: WGSL_KEYWORDS,
+	types: WGSL_TYPES,
+	attribute: '@',
+	capitalized_types: true,
+	is_rust: false,
+};
+
+const RUST: Grammar = Grammar {
+	keywords: RUST_KEYWORDS,
+	types: RUST_TYPES,
+	attribute: '#',
+	capitalized_types: true,
+	is_rust: true,
+};
+
+impl Tokenizer for PlainTokenizer {
+	fn tokenize(&self, _: &str) -> Vec<Token> {
+		vec!()
+	}
+}
+
+impl Tokenizer for WgslTokenizer {
+	fn tokenize(&self, code: &str) -> Vec<Token> {
+		tokenize_with(code, &WGSL)
+	}
+}
+
+impl Tokenizer for RustTokenizer {
+	fn tokenize(&self, code: &str) -> Vec<Token> {
+		tokenize_with(code, &RUST)
+	}
+}
+
+impl Tokenizer for JsonTokenizer {
+	fn tokenize(&self, code: &str) -> Vec<Token> {
+		let chars: Vec<(usize, char)> = code.char_indices().collect();
+		let byte_at = |index: usize| chars.get(index).map_or(code.len(), |(byte, _)| *byte);
+		let mut tokens = vec!();
+		let mut index = 0;
+		while index < chars.len() {
+			let start = index;
+			let chr = chars[index].1;
+			let kind = if chr == '"' {
+				index = skip_string(&chars, index + 1, '"');
+				// a string followed by a colon is a key
+				let next = chars[index..].iter().find(|(_, chr)| !chr.is_whitespace());
+				if next.is_some_and(|(_, chr)| *chr == ':') {
+					TokenKind::Property
+				}else {
+					TokenKind::String
+				}
+			}else if chr == '-' || chr.is_ascii_digit() {
+				index += 1;
+				while index < chars.len() && (chars[index].1.is_ascii_alphanumeric() || matches!(chars[index].1, '.' | '+' | '-')) {
+					index += 1;
+				}
+				TokenKind::Number
+			}else if chr.is_alphabetic() {
+				while index < chars.len() && chars[index].1.is_alphanumeric() {
+					index += 1;
+				}
+				match &code[byte_at(start)..byte_at(index)] {
+					"true" | "false" | "null" => TokenKind::Keyword,
+					_ => TokenKind::Plain,
+				}
+			}else if chr.is_whitespace() {
+				index += 1;
+				continue;
+			}else {
+				index += 1;
+				TokenKind::Punctuation
+			};
+			tokens.push(Token { range: byte_at(start)..byte_at(index), kind });
+		}
+		tokens
+	}
+}
+
+/// index right after the closing `quote`, backslashes escape next charater
+fn skip_string(chars: &[(usize, char)], mut index: usize, quote: char) -> usize {
+	while index < chars.len() {
+		match chars[index].1 {
+			'\\' => index += 2,
+			chr if chr == quote => return index + 1,
+			_ => index += 1,
+		}
+	}
+	chars.len()
+}
+
+fn is_identifier_start(chr: char) -> bool {
+	chr.is_alphabetic() || chr == '_'
+}
+
+fn is_identifier(chr: char) -> bool {
+	chr.is_alphanumeric() || chr == '_'
+}
+
+/// tokens of a c-like language, block comments can be nested
+fn tokenize_with(code: &str, grammar: &Grammar) -> Vec<Token> {
+	let chars: Vec<(usize, char)> = code.char_indices().collect();
+	let byte_at = |index: usize| chars.get(index).map_or(code.len(), |(byte, _)| *byte);
+	let char_at = |index: usize| chars.get(index).map(|(_, chr)| *chr);
+	let mut tokens: Vec<Token> = vec!();
+	let mut index = 0;
+	while index < chars.len() {
+		let start = index;
+		let chr = chars[index].1;
+		let kind = if chr == '/' && char_at(index + 1) == Some('/') {
+			while index < chars.len() && chars[index].1 != '\n' {
+				index += 1;
+			}
+			TokenKind::Comment
+		}else if chr == '/' && char_at(index + 1) == Some('*') {
+			let mut depth = 0;
+			while index < chars.len() {
+				if chars[index].1 == '/' && char_at(index + 1) == Some('*') {
+					depth += 1;
+					index += 2;
+				}else if chars[index].1 == '*' && char_at(index + 1) == Some('/') {
+					depth -= 1;
+					index += 2;
+					if depth == 0 {
+						break;
+					}
+				}else {
+					index += 1;
+				}
+			}
+			TokenKind::Comment
+		}else if chr == '"' {
+			index = skip_string(&chars, index + 1, '"');
+			TokenKind::String
+		}else if let Some(end) = grammar.is_rust.then(|| raw_string_end(&chars, index)).flatten() {
+			index = end;
+			TokenKind::String
+		}else if grammar.is_rust && chr == '\'' {
+			// 'a' and '\n' are charaters, 'a without closing quote is a lifetime
+			if char_at(index + 1) == Some('\\') || (char_at(index + 1).is_some() && char_at(index + 2) == Some('\'')) {
+				index = skip_string(&chars, index + 1, '\'');
+				TokenKind::String
+			}else {
+				index += 1;
+				while index < chars.len() && is_identifier(chars[index].1) {
+					index += 1;
+				}
+				TokenKind::Keyword
+			}
+		}else if grammar.attribute == '#' && chr == '#' && matches!(char_at(index + 1), Some('[') | Some('!')) {
+			let mut depth = 0;
+			while index < chars.len() {
+				match chars[index].1 {
+					'[' => depth += 1,
+					']' => {
+						depth -= 1;
+						if depth == 0 {
+							index += 1;
+							break;
+						}
+					},
+					'\n' if depth == 0 => break,
+					_ => {}
+				}
+				index += 1;
+			}
+			TokenKind::Attribute
+		}else if grammar.attribute == '@' && chr == '@' {
+			index += 1;
+			while index < chars.len() && is_identifier(chars[index].1) {
+				index += 1;
+			}
+			TokenKind::Attribute
+		}else if chr.is_ascii_digit() {
+			let is_hex = chr == '0' && matches!(char_at(index + 1), Some('x') | Some('X'));
+			index += 1;
+			while let Some(chr) = char_at(index) {
+				let is_exponent_sign = !is_hex && matches!(chr, '+' | '-') && matches!(char_at(index - 1), Some('e') | Some('E'));
+				let is_point = chr == '.' && char_at(index + 1).is_some_and(|chr| chr.is_ascii_digit());
+				if is_identifier(chr) || is_point || is_exponent_sign {
+					index += 1;
+				}else {
+					break;
+				}
+			}
+			TokenKind::Number
+		}else if is_identifier_start(chr) {
+			while index < chars.len() && is_identifier(chars[index].1) {
+				index += 1;
+			}
+			let word = &code[byte_at(start)..byte_at(index)];
+			let next = chars[index..].iter().find(|(_, chr)| !chr.is_whitespace()).map(|(_, chr)| *chr);
+			let after_fn = tokens.last().is_some_and(|token| &code[token.range.clone()] == "fn");
+			if grammar.keywords.contains(&word) {
+				TokenKind::Keyword
+			}else if grammar.types.contains(&word) || (grammar.capitalized_types && word.starts_with(|chr: char| chr.is_uppercase())) {
+				TokenKind::Type
+			}else if grammar.is_rust && char_at(index) == Some('!') && char_at(index + 1) != Some('=') {
+				index += 1;
+				TokenKind::Function
+			}else if next == Some('(') || after_fn {
+				TokenKind::Function
+			}else {
+				TokenKind::Plain
+			}
+		}else if chr.is_whitespace() {
+			index += 1;
+			continue;
+		}else {
+			index += 1;
+			if chr.is_ascii_punctuation() {
+				TokenKind::Punctuation
+			}else {
+				TokenKind::Plain
+			}
+		};
+		tokens.push(Token { range: byte_at(start)..byte_at(index), kind });
+	}
+	tokens
+}
+
+/// end of a raw string like `r"..."` or `br#"..."#` starting at `index`
+fn raw_string_end(chars: &[(usize, char)], index: usize) -> Option<usize> {
+	let mut current = index;
+	if chars[current].1 == 'b' {
+		current += 1;
+	}
+	if chars.get(current)?.1 != 'r' {
+		return None;
+	}
+	current += 1;
+	let mut hashes = 0;
+	while chars.get(current)?.1 == '#' {
+		hashes += 1;
+		current += 1;
+	}
+	if chars.get(current)?.1 != '"' {
+		return None;
+	}
+	current += 1;
+	while current < chars.len() {
+		if chars[current].1 == '"' && (1..=hashes).all(|offset| chars.get(current + offset).is_some_and(|(_, chr)| *chr == '#')) {
+			return Some(current + 1 + hashes);
+		}
+		current += 1;
+	}
+	Some(chars.len())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use TokenKind::*;
+
+	fn tokens<'a>(code: &'a str, tokenizer: impl Tokenizer) -> Vec<(&'a str, TokenKind)> {
+		tokenizer.tokenize(code).into_iter().map(|token| (&code[token.range], token.kind)).collect()
+	}
+
+	#[test]
+	fn wgsl() {
+		let code = "@fragment fn main(x: vec4f) -> f32 { return 1.5e-3; } // done";
+		assert_eq!(tokens(code, WgslTokenizer), vec!(
+			("@fragment", Attribute), ("fn", Keyword), ("main", Function), ("(", Punctuation), ("x", Plain), (":", Punctuation),
+			("vec4f", Type), (")", Punctuation), ("-", Punctuation), (">", Punctuation), ("f32", Type), ("{", Punctuation),
+			("return", Keyword), ("1.5e-3", Number), (";", Punctuation), ("}", Punctuation), ("// done", Comment),
+		));
+	}
+
+	#[test]
+	fn rust() {
+		let code = "#[derive(Debug)] fn f<'a>(c: char) { println!(\"{}\", 'x'); /* a /* b */ c */ }";
+		assert_eq!(tokens(code, RustTokenizer), vec!(
+			("#[derive(Debug)]", Attribute), ("fn", Keyword), ("f", Function), ("<", Punctuation), ("'a", Keyword), (">", Punctuation),
+			("(", Punctuation), ("c", Plain), (":", Punctuation), ("char", Type), (")", Punctuation), ("{", Punctuation),
+			("println!", Function), ("(", Punctuation), ("\"{}\"", String), (",", Punctuation), ("'x'", String), (")", Punctuation),
+			(";", Punctuation), ("/* a /* b */ c */", Comment), ("}", Punctuation),
+		));
+	}
+
+	#[test]
+	fn rust_raw_strings() {
+		let code = r###"let s = r#"a "b""#;"###;
+		assert_eq!(tokens(code, RustTokenizer), vec!(
+			("let", Keyword), ("s", Plain), ("=", Punctuation), (r##"r#"a "b""#"##, String), (";", Punctuation),
+		));
+	}
+
+	#[test]
+	fn json() {
+		let code = r#"{"key": [1, -2.5e3, true, null], "s": "v\"q"}"#;
+		assert_eq!(tokens(code, JsonTokenizer), vec!(
+			("{", Punctuation), ("\"key\"", Property), (":", Punctuation), ("[", Punctuation), ("1", Number), (",", Punctuation),
+			("-2.5e3", Number), (",", Punctuation), ("true", Keyword), (",", Punctuation), ("null", Keyword), ("]", Punctuation),
+			(",", Punctuation), ("\"s\"", Property), (":", Punctuation), (r#""v\"q""#, String), ("}", Punctuation),
+		));
+	}
+
+	#[test]
+	fn plain_has_no_tokens() {
+		assert!(PlainTokenizer.tokenize("fn main() {}").is_empty());
+	}
+}